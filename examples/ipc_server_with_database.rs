@@ -401,4 +401,6 @@ fn main() {
 
     db_child.kill().unwrap();
     webserver_child.kill().unwrap();
+    let _db_status = db_child.wait().unwrap();
+    let _webserver_status = webserver_child.wait().unwrap();
 }
@@ -1,6 +1,6 @@
 #![cfg(feature = "isolate")]
-/// Tests for isolate have to go in examples because tests in the tests/ directory get compiled as
-/// test binaries and have their main fn overridden
+//! Tests for isolate have to go in examples because tests in the tests/ directory get compiled as
+//! test binaries and have their main fn overridden
 
 // TODO: check unix domain sockets work as expected with isolated network namespace
 
@@ -4,15 +4,11 @@ use std::io::Write;
 fn main() {
     // create a safety context
     // enable no systemIO
-    // but allow stdout and stderr
+    // but allow stdin, stdout and stderr
 
     println!("disabling IO-related syscalls...");
     let res = extrasafe::SafetyContext::new()
-        .enable(
-            extrasafe::builtins::SystemIO::nothing()
-                .allow_stdout()
-                .allow_stderr()
-        )
+        .enable(extrasafe::builtins::SystemIO::stdio())
         .unwrap()
         .apply_to_all_threads();
     assert!(res.is_ok(), "extrasafe failed {:?}", res.unwrap_err());
@@ -60,8 +60,8 @@ fn custom_ruleset() {
 #[cfg(feature = "landlock")]
 fn with_landlock() {
     use std::fs::File;
-    let tmp_dir_allow = tempfile::tempdir().unwrap().into_path();
-    let tmp_dir_deny = tempfile::tempdir().unwrap().into_path();
+    let tmp_dir_allow = tempfile::tempdir().unwrap().keep();
+    let tmp_dir_deny = tempfile::tempdir().unwrap().keep();
 
     extrasafe::SafetyContext::new()
         .enable(
@@ -0,0 +1,99 @@
+//! Estimating how close the seccomp filter of a [`SafetyContext`] is to the kernel's limits, e.g.
+//! to catch problems with large generated policies before the kernel rejects the filter.
+
+use std::fmt;
+
+use crate::{ExtraSafeError, SafetyContext, SeccompilerTargetArch};
+
+/// The maximum number of instructions in a single BPF program, `BPF_MAXINSNS` in
+/// `<linux/bpf_common.h>`.
+const BPF_MAXINSNS: usize = 4096;
+
+/// The architectures filters are estimated for.
+const ARCHES: [SeccompilerTargetArch; 2] = [SeccompilerTargetArch::x86_64, SeccompilerTargetArch::aarch64];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How large the seccomp filter of a [`SafetyContext`] is compared to the kernel's limits.
+/// Returned by [`SafetyContext::budget_report`].
+///
+/// The `Display` implementation prints one line per field and a warning for each architecture
+/// in [`near_limit`](Self::near_limit).
+pub struct BudgetReport {
+    /// The number of distinct syscalls with rules, not counting the
+    /// [`BasicCapabilities`](crate::builtins::BasicCapabilities) that are added when the filter is
+    /// applied.
+    pub syscalls: usize,
+    /// The number of conditional rules, over all syscalls.
+    pub conditional_rules: usize,
+    /// The length of the main seccomp filter in BPF instructions on each architecture. The
+    /// length on the current architecture is exact; on other architectures it is estimated using
+    /// the current architecture's syscall numbers.
+    pub instructions: Vec<(SeccompilerTargetArch, usize)>,
+    /// The architectures on which the filter uses more than [`WARNING_THRESHOLD`](Self::WARNING_THRESHOLD)
+    /// of `BPF_MAXINSNS`.
+    pub near_limit: Vec<SeccompilerTargetArch>,
+}
+
+impl BudgetReport {
+    /// The fraction of `BPF_MAXINSNS` above which an architecture is reported in
+    /// [`near_limit`](Self::near_limit).
+    pub const WARNING_THRESHOLD: f64 = 0.8;
+
+    #[must_use]
+    /// Returns true if the filter is close to the kernel's limit on any architecture.
+    pub fn is_near_limit(&self) -> bool {
+        !self.near_limit.is_empty()
+    }
+}
+
+impl fmt::Display for BudgetReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "syscalls: {}", self.syscalls)?;
+        writeln!(f, "conditional rules: {}", self.conditional_rules)?;
+        for (arch, instructions) in &self.instructions {
+            writeln!(f, "instructions on {:?}: {}/{}", arch, instructions, BPF_MAXINSNS)?;
+        }
+        for arch in &self.near_limit {
+            writeln!(f, "warning: the filter on {:?} is close to the limit of {} instructions", arch, BPF_MAXINSNS)?;
+        }
+        Ok(())
+    }
+}
+
+impl SafetyContext {
+    /// Compile the seccomp filter for each supported architecture, the same way as
+    /// [`into_filter`](Self::into_filter), and report its size compared to the kernel's limits,
+    /// without applying it.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`into_filter`](Self::into_filter), including a
+    /// [`SeccompError`](ExtraSafeError::SeccompError) if the filter is already too large for
+    /// seccompiler to build on some architecture.
+    pub fn budget_report(&self) -> Result<BudgetReport, ExtraSafeError> {
+        let syscalls = self.seccomp_rules.len();
+        let conditional_rules = self.seccomp_rules.values()
+            .flatten()
+            .filter(|labeled_rule| !labeled_rule.1.argument_filters.is_empty())
+            .count();
+
+        let instructions = ARCHES.iter()
+            .map(|&arch| {
+                let filter = self.clone().into_filter_for_arch(arch)?;
+                Ok((arch, filter.seccomp.map_or(0, |program| program.len())))
+            })
+            .collect::<Result<Vec<_>, ExtraSafeError>>()?;
+
+        #[allow(clippy::cast_precision_loss)]
+        let near_limit = instructions.iter()
+            .filter(|&&(_, len)| len as f64 > BPF_MAXINSNS as f64 * BudgetReport::WARNING_THRESHOLD)
+            .map(|&(arch, _)| arch)
+            .collect();
+
+        Ok(BudgetReport {
+            syscalls,
+            conditional_rules,
+            instructions,
+            near_limit,
+        })
+    }
+}
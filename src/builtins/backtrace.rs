@@ -0,0 +1,57 @@
+//! Contains a [`RuleSet`] for allowing the syscalls needed to print a backtrace when panicking.
+
+use std::collections::HashMap;
+
+use syscalls::Sysno;
+
+use crate::{RuleSet, SeccompRule};
+
+use super::SystemIO;
+
+/// [`Backtrace`] allows the syscalls that `std` uses to print a backtrace with symbol names when
+/// a sandboxed thread panics with `RUST_BACKTRACE` set, so that the panic message and backtrace
+/// are printed instead of the panic handler failing or, with
+/// [`kill_process_on_violation`](crate::SafetyContext::kill_process_on_violation), the process
+/// being killed by `SIGSYS` before the message is visible. Writing the backtrace additionally
+/// requires [`SystemIO::allow_stderr`].
+///
+/// The syscalls were determined by panicking under a filter using
+/// [`trap_on_violation`](crate::SafetyContext::trap_on_violation) and logging the syscalls that
+/// were denied:
+/// - `openat` (and `open` on x86_64) with read-only flags, `statx`, `fstat`, `read`, `lseek`
+///   and `close`, to find and load the debug info of the executable.
+/// - `mremap`, which `realloc` uses to grow large allocations.
+/// - `getcwd`, to print source paths relative to the working directory.
+///
+/// Some of these have fallbacks when they fail, but all of them are called, so the process would
+/// still be killed with `kill_process_on_violation` if any were denied.
+///
+/// # Security considerations
+/// `read` is allowed on any file descriptor, so unless the rest of the policy already allows it,
+/// enabling this lets the sandboxed code read from all files, sockets and pipes that are open.
+/// Together with opening files read-only, this gives read access to every file the process can
+/// access, so consider combining it with landlock rules.
+///
+/// Because opening files is only allowed with read-only flags, this conflicts with rulesets that
+/// allow `openat` unconditionally, e.g. [`SystemIO::allow_open`]. In that case, enable it with
+/// [`Precedence::Simple`](crate::Precedence::Simple).
+pub struct Backtrace;
+impl RuleSet for Backtrace {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        vec![Sysno::statx, Sysno::fstat, Sysno::read, Sysno::lseek, Sysno::close, Sysno::mremap, Sysno::getcwd]
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        SystemIO::nothing()
+            .allow_open_readonly()
+            .conditional_rules()
+    }
+
+    fn name(&self) -> &'static str {
+        "Backtrace"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows printing a backtrace when panicking"
+    }
+}
@@ -41,8 +41,11 @@ impl RuleSet for BasicCapabilities {
             Sysno::set_robust_list,
 
             // Readlink isn't dangerous because you still need to be able to open the file to do
-            // anything with the resolved name.
+            // anything with the resolved name. aarch64 only has readlinkat.
+            #[cfg(target_arch = "x86_64")]
             Sysno::readlink,
+            #[cfg(target_arch = "aarch64")]
+            Sysno::readlinkat,
 
             // Getpid/tid is fine.
             Sysno::getpid,
@@ -72,4 +75,8 @@ impl RuleSet for BasicCapabilities {
     fn name(&self) -> &'static str {
         "BasicCapabilities"
     }
+
+    fn description(&self) -> &'static str {
+        "allows memory allocation, signal handling, futexes, and other syscalls all programs need"
+    }
 }
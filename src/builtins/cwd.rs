@@ -0,0 +1,76 @@
+//! Contains a [`RuleSet`] for allowing reading and changing the current working directory.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+#[must_use]
+/// Enable syscalls for reading the current working directory with `getcwd` and changing it with
+/// `chdir` and `fchdir`.
+///
+/// # Landlock
+/// The paths of landlock rules, e.g. from `SystemIO::allow_read_path`, are opened when the
+/// [`SafetyContext`](crate::SafetyContext) is applied, so relative paths are resolved against the
+/// working directory at that point. Changing the working directory afterwards does not change
+/// which files can be accessed, but relative paths passed to e.g. `open` will then be resolved
+/// against the new directory and may fall outside of the landlock rules.
+pub struct Cwd {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Cwd {
+    /// Create a new [`Cwd`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Cwd {
+        Cwd {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow reading and changing the working directory.
+    pub fn everything() -> Cwd {
+        Cwd::nothing()
+            .allow_getcwd()
+            .allow_chdir()
+    }
+
+    /// Allow reading the working directory, but not changing it.
+    pub fn read_only() -> Cwd {
+        Cwd::nothing()
+            .allow_getcwd()
+    }
+
+    /// Allow `getcwd`, which returns the path of the current working directory.
+    pub fn allow_getcwd(mut self) -> Cwd {
+        self.allowed.extend([Sysno::getcwd]);
+
+        self
+    }
+
+    /// Allow `chdir` and `fchdir`, which change the current working directory to a path or an
+    /// open directory file descriptor.
+    ///
+    /// Changing the working directory does not give access to any files by itself, but see the
+    /// [type-level docs](Cwd) for how it interacts with landlock rules using relative paths.
+    pub fn allow_chdir(mut self) -> Cwd {
+        self.allowed.extend([Sysno::chdir, Sysno::fchdir]);
+
+        self
+    }
+}
+
+impl RuleSet for Cwd {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Cwd"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows reading and changing the current working directory"
+    }
+}
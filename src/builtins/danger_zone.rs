@@ -9,7 +9,14 @@ use crate::{SeccompRule, RuleSet};
 use super::YesReally;
 
 // const CLONE_PARENT: u64 = libc::CLONE_PARENT as u64;
-// const CLONE_THREAD: u64 = libc::CLONE_THREAD as u64;
+
+/// The flags `clone` requires to create a thread rather than a process. `CLONE_THREAD` requires
+/// `CLONE_SIGHAND`, which in turn requires `CLONE_VM`.
+const CLONE_THREAD_FLAGS: u64 = (libc::CLONE_VM | libc::CLONE_SIGHAND | libc::CLONE_THREAD) as u64;
+
+/// The `clone` flags that create new namespaces.
+const CLONE_NAMESPACE_FLAGS: u64 = (libc::CLONE_NEWNS | libc::CLONE_NEWCGROUP | libc::CLONE_NEWUTS
+    | libc::CLONE_NEWIPC | libc::CLONE_NEWUSER | libc::CLONE_NEWPID | libc::CLONE_NEWNET) as u64;
 
 /// Allows `clone` and `sleep` syscalls, which allow creating new threads and processes, and
 /// pausing them.
@@ -22,6 +29,7 @@ use super::YesReally;
 #[must_use]
 pub struct Threads {
     allowed: HashSet<Sysno>,
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
 }
 
 impl Threads {
@@ -29,16 +37,43 @@ impl Threads {
     pub fn nothing() -> Threads {
         Threads {
             allowed: HashSet::new(),
+            custom: HashMap::new(),
         }
     }
 
-    /// Allow creating new threads and processes.
+    /// Allow creating new threads and processes. This replaces the rule added by
+    /// [`allow_create_threads_only`](Self::allow_create_threads_only).
     pub fn allow_create(mut self) -> Threads {
+        let _removed = self.custom.remove(&Sysno::clone);
         self.allowed.extend([Sysno::clone, Sysno::clone3]);
 
         self
     }
 
+    /// Allow creating new threads, but not new processes or namespaces: `clone` is only allowed
+    /// if its flags contain `CLONE_VM`, `CLONE_SIGHAND` and `CLONE_THREAD`, and none of the
+    /// `CLONE_NEW*` flags. If [`allow_create`](Self::allow_create) was already called, this does
+    /// nothing.
+    ///
+    /// `clone3` is not allowed, because its flags are passed in a struct in memory, which seccomp
+    /// can't inspect. glibc and other libcs fall back to `clone` only if `clone3` fails with
+    /// `ENOSYS`, so use [`SafetyContext::with_errno`](crate::SafetyContext::with_errno) with
+    /// `ENOSYS` when creating threads through e.g. [`std::thread::spawn`], otherwise thread
+    /// creation fails with the default `EPERM`.
+    pub fn allow_create_threads_only(mut self) -> Threads {
+        if self.allowed.contains(&Sysno::clone) {
+            return self;
+        }
+
+        // The flags are the first argument on both x86_64 and aarch64
+        let rule = SeccompRule::new(Sysno::clone)
+            .and_condition(seccomp_arg_filter!(arg0 & CLONE_THREAD_FLAGS == CLONE_THREAD_FLAGS))
+            .and_condition(seccomp_arg_filter!(arg0 & CLONE_NAMESPACE_FLAGS == 0));
+        let _old = self.custom.insert(Sysno::clone, vec![rule]);
+
+        self
+    }
+
     /// Allow sleeping on the current thread
     ///
     /// # Security considerations
@@ -58,27 +93,16 @@ impl RuleSet for Threads {
     }
 
     fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
-        // let mut rules = HashMap::new();
-
-        // let clone = SeccompRule::new(Sysno::clone)
-        //     .and_condition(seccomp_arg_filter!(arg2 & CLONE_THREAD == CLONE_THREAD));
-        // rules.entry(Sysno::clone)
-        //     .or_insert_with(Vec::new)
-        //     .push(clone);
-
-        // let clone3 = SeccompRule::new(Sysno::clone3)
-        //     .and_condition(seccomp_arg_filter!(arg2 & CLONE_THREAD == CLONE_THREAD));
-        // rules.entry(Sysno::clone3)
-        //     .or_insert_with(Vec::new)
-        //     .push(clone3);
-
-        // rules
-        HashMap::new()
+        self.custom.clone()
     }
 
     fn name(&self) -> &'static str {
         "Threads"
     }
+
+    fn description(&self) -> &'static str {
+        "allows creating threads"
+    }
 }
 
 /// [`ForkAndExec`] is in the danger zone because it can be used to start another process,
@@ -91,7 +115,9 @@ pub struct ForkAndExec;
 impl RuleSet for ForkAndExec {
     fn simple_rules(&self) -> Vec<Sysno> {
         let mut rules = vec![
-             Sysno::fork, Sysno::vfork,
+             // fork and vfork only exist on x86_64, elsewhere libc uses clone
+             #[cfg(target_arch = "x86_64")] Sysno::fork,
+             #[cfg(target_arch = "x86_64")] Sysno::vfork,
              Sysno::execve, Sysno::execveat,
              Sysno::wait4, Sysno::waitid,
              Sysno::clone, Sysno::clone3,
@@ -100,7 +126,7 @@ impl RuleSet for ForkAndExec {
         // musl creates a pipe when it starts a new process, and fails the operation if it can't
         // create the pipe
         if cfg!(target_env = "musl") {
-            rules.extend([Sysno::pipe, Sysno::pipe2]);
+            rules.extend([#[cfg(target_arch = "x86_64")] Sysno::pipe, Sysno::pipe2]);
         }
 
         rules
@@ -130,4 +156,234 @@ impl RuleSet for ForkAndExec {
     fn name(&self) -> &'static str {
         "ForkAndExec"
     }
+
+    fn description(&self) -> &'static str {
+        "allows forking and executing new processes"
+    }
+}
+
+/// The `clone` flags that must not be set when creating a process with [`Fork`]: creating threads
+/// or namespaces.
+const CLONE_FORK_DENIED_FLAGS: u64 = libc::CLONE_THREAD as u64 | CLONE_NAMESPACE_FLAGS;
+
+/// [`Fork`] allows creating new processes without allowing them to execute other programs, e.g.
+/// for double-forking to daemonize or for pre-forking worker processes.
+///
+/// Unlike [`ForkAndExec`], this does not allow `execve` or `execveat`, so the child processes can
+/// only run the code that is already loaded, under the same filter. `clone` is only allowed
+/// without `CLONE_THREAD` and the `CLONE_NEW*` namespace flags, so it can be combined with
+/// [`Threads::allow_create_threads_only`], but not with [`ForkAndExec`] or
+/// [`Threads::allow_create`], which allow `clone` unconditionally. `clone3` is not allowed
+/// because its flags can't be inspected, see [`Threads::allow_create_threads_only`]. To wait for
+/// the children, also enable [`ProcessControl::allow_wait`](super::ProcessControl::allow_wait).
+///
+/// # Security considerations
+/// Child processes inherit the filter, but they are separate processes: killing the child
+/// doesn't kill the parent, and a process that can fork can exhaust the system's process limit.
+#[must_use]
+pub struct Fork {
+    allowed: HashSet<Sysno>,
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl Fork {
+    /// Create a new [`Fork`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Fork {
+        Fork {
+            allowed: HashSet::new(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow creating processes with `fork`, and with `clone` without the flags for creating
+    /// threads or namespaces, which is what libc's `fork` uses. `fork` only exists on x86_64.
+    ///
+    /// # Security considerations
+    /// See the [`Fork`] documentation.
+    pub fn allow_fork(mut self) -> YesReally<Fork> {
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend([Sysno::fork]);
+
+        let rule = SeccompRule::new(Sysno::clone)
+            .and_condition(seccomp_arg_filter!(arg0 & CLONE_FORK_DENIED_FLAGS == 0));
+        let _old = self.custom.insert(Sysno::clone, vec![rule]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow creating processes with `vfork`, which only exists on x86_64. Elsewhere, libc uses
+    /// `clone` with `CLONE_VM | CLONE_VFORK`, which [`allow_fork`](Self::allow_fork) allows.
+    ///
+    /// # Security considerations
+    /// See the [`Fork`] documentation. The child shares the parent's memory until it exits.
+    #[cfg_attr(not(target_arch = "x86_64"), allow(unused_mut))]
+    pub fn allow_vfork(mut self) -> YesReally<Fork> {
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend([Sysno::vfork]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for Fork {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "Fork"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows forking new processes without executing other programs"
+    }
+}
+
+/// [`Namespaces`] allows mounting filesystems and creating or joining namespaces, which is needed
+/// to construct sandboxes and containers, e.g. when running containers inside of containers.
+///
+/// # Security considerations
+/// These syscalls can change what the process sees as the filesystem and which other processes,
+/// users, and network interfaces it can interact with. With the right capabilities (which can be
+/// obtained inside of a new user namespace) they can be used to get around filesystem
+/// restrictions, so every method returns a [`YesReally`].
+#[must_use]
+pub struct Namespaces {
+    allowed: HashSet<Sysno>,
+}
+
+impl Namespaces {
+    /// Create a new [`Namespaces`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Namespaces {
+        Namespaces {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow mounting and unmounting filesystems with `mount` and `umount2`.
+    ///
+    /// # Security considerations
+    /// Mounting over parts of the filesystem can hide or replace files that other code relies on.
+    pub fn allow_mount(mut self) -> YesReally<Namespaces> {
+        self.allowed.extend([Sysno::mount, Sysno::umount2]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow creating and joining namespaces with `unshare` and `setns`.
+    ///
+    /// # Security considerations
+    /// Creating a new user namespace gives the process all capabilities inside of it, which
+    /// greatly increases the kernel attack surface available to it.
+    pub fn allow_namespace_ops(mut self) -> YesReally<Namespaces> {
+        self.allowed.extend([Sysno::unshare, Sysno::setns]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow changing the root filesystem with `pivot_root`.
+    ///
+    /// # Security considerations
+    /// Changing the root filesystem changes what every path the process opens refers to.
+    pub fn allow_pivot_root(mut self) -> YesReally<Namespaces> {
+        self.allowed.extend([Sysno::pivot_root]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for Namespaces {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Namespaces"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows mounting filesystems and creating or joining namespaces"
+    }
+}
+
+/// `prctl` options used to drop privileges: dropping capabilities from the bounding set and
+/// setting `no_new_privs`.
+const PRCTL_PRIVILEGE_OPTIONS: &[libc::c_int] = &[libc::PR_CAPBSET_DROP, libc::PR_SET_NO_NEW_PRIVS];
+
+/// [`Capabilities`] allows reading and changing the process's capabilities, e.g. to drop
+/// privileges before running untrusted work.
+#[must_use]
+pub struct Capabilities {
+    allowed: HashSet<Sysno>,
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl Capabilities {
+    /// Create a new [`Capabilities`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Capabilities {
+        Capabilities {
+            allowed: HashSet::new(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow reading the capabilities of a thread with `capget`.
+    pub fn allow_capget(mut self) -> Capabilities {
+        self.allowed.extend([Sysno::capget]);
+
+        self
+    }
+
+    /// Allow changing the capabilities of the current thread with `capset`.
+    ///
+    /// # Security considerations
+    /// `capset` can only drop capabilities from the permitted set, but it can also raise
+    /// capabilities that are already permitted into the effective set, which gives the thread
+    /// those privileges again.
+    pub fn allow_capset(mut self) -> YesReally<Capabilities> {
+        self.allowed.extend([Sysno::capset]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow `prctl` with only `PR_CAPBSET_DROP` and `PR_SET_NO_NEW_PRIVS`, to drop capabilities
+    /// from the bounding set and prevent gaining privileges via `execve`.
+    ///
+    /// Note that because these are conditional rules on `prctl`, this can't be enabled at the same
+    /// time as other rulesets that allow `prctl` unconditionally.
+    pub fn allow_drop_privileges(mut self) -> Capabilities {
+        let rules = self.custom.entry(Sysno::prctl)
+            .or_insert_with(Vec::new);
+        for &option in PRCTL_PRIVILEGE_OPTIONS {
+            #[allow(clippy::cast_sign_loss)]
+            let option = option as u64;
+            let rule = SeccompRule::new(Sysno::prctl)
+                .and_condition(seccomp_arg_filter!(arg0 == option));
+            rules.push(rule);
+        }
+
+        self
+    }
+}
+
+impl RuleSet for Capabilities {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "Capabilities"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows reading, changing, and dropping process capabilities"
+    }
 }
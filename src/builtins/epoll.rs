@@ -0,0 +1,86 @@
+//! Contains a [`RuleSet`] for allowing epoll syscalls, used by event loops.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+/// A [`RuleSet`] for creating, configuring, and waiting on epoll instances, which are used by
+/// event loops and async runtimes like tokio.
+///
+/// Note that [`Networking`](super::Networking) already allows these when you allow running
+/// servers or clients.
+#[must_use]
+pub struct Epoll {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Epoll {
+    /// Create a new [`Epoll`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Epoll {
+        Epoll {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow all epoll syscalls, including the legacy `epoll_create`.
+    pub fn everything() -> Epoll {
+        Epoll::nothing()
+            .allow_create()
+            .allow_legacy_create()
+            .allow_ctl()
+            .allow_wait()
+    }
+
+    /// Allow creating epoll instances with `epoll_create1`.
+    pub fn allow_create(mut self) -> Epoll {
+        self.allowed.extend([Sysno::epoll_create1]);
+
+        self
+    }
+
+    /// Allow creating epoll instances with the legacy `epoll_create` syscall. Most modern code
+    /// uses `epoll_create1` instead, which is allowed by [`allow_create`](Self::allow_create).
+    ///
+    /// `epoll_create` only exists on x86_64, so this allows nothing on aarch64.
+    #[cfg_attr(not(target_arch = "x86_64"), allow(unused_mut))]
+    pub fn allow_legacy_create(mut self) -> Epoll {
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend([Sysno::epoll_create]);
+
+        self
+    }
+
+    /// Allow adding, modifying, and removing fds from epoll instances with `epoll_ctl`.
+    pub fn allow_ctl(mut self) -> Epoll {
+        self.allowed.extend([Sysno::epoll_ctl]);
+
+        self
+    }
+
+    /// Allow waiting for events with `epoll_wait`, `epoll_pwait`, and `epoll_pwait2`.
+    /// `epoll_wait` only exists on x86_64.
+    pub fn allow_wait(mut self) -> Epoll {
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend([Sysno::epoll_wait]);
+        self.allowed.extend([Sysno::epoll_pwait, Sysno::epoll_pwait2]);
+
+        self
+    }
+}
+
+impl RuleSet for Epoll {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Epoll"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows creating and waiting on epoll instances"
+    }
+}
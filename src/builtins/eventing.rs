@@ -0,0 +1,85 @@
+//! Contains a [`RuleSet`] for allowing file descriptor based event notification syscalls, like
+//! timerfd and eventfd.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+/// A [`RuleSet`] for creating and using timerfds, eventfds, and signalfds.
+///
+/// These are typically used together with [`Epoll`](super::Epoll) in event loops. Reading from
+/// the created fds requires `read`, e.g. via [`SystemIO::allow_read`](super::SystemIO::allow_read).
+#[must_use]
+pub struct Eventing {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Eventing {
+    /// Create a new [`Eventing`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Eventing {
+        Eventing {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow creating and using timerfds.
+    pub fn timers() -> Eventing {
+        Eventing::nothing()
+            .allow_timerfd()
+    }
+
+    /// Allow creating eventfds.
+    pub fn eventfd() -> Eventing {
+        Eventing::nothing()
+            .allow_eventfd()
+    }
+
+    /// Allow creating timerfds, eventfds, and signalfds.
+    pub fn everything() -> Eventing {
+        Eventing::nothing()
+            .allow_timerfd()
+            .allow_eventfd()
+            .allow_signalfd()
+    }
+
+    /// Allow `timerfd_create`, `timerfd_settime`, and `timerfd_gettime`.
+    pub fn allow_timerfd(mut self) -> Eventing {
+        self.allowed.extend([Sysno::timerfd_create, Sysno::timerfd_settime, Sysno::timerfd_gettime]);
+
+        self
+    }
+
+    /// Allow `eventfd2`.
+    pub fn allow_eventfd(mut self) -> Eventing {
+        self.allowed.extend([Sysno::eventfd2]);
+
+        self
+    }
+
+    /// Allow `signalfd4`, for receiving signals via a file descriptor.
+    ///
+    /// Note that the signals also have to be blocked with `rt_sigprocmask`, which is currently
+    /// allowed by [`BasicCapabilities`](super::BasicCapabilities).
+    pub fn allow_signalfd(mut self) -> Eventing {
+        self.allowed.extend([Sysno::signalfd4]);
+
+        self
+    }
+}
+
+impl RuleSet for Eventing {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Eventing"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows file descriptor based event notification with eventfd, timerfd, and signalfd"
+    }
+}
@@ -0,0 +1,97 @@
+//! Contains a [`RuleSet`] for allowing file descriptor management syscalls like `dup`.
+
+use std::collections::{HashMap, HashSet};
+
+use syscalls::Sysno;
+
+use crate::{SeccompArgumentFilter, SeccompilerComparator, SeccompRule, RuleSet};
+
+/// `fcntl` commands for duplicating file descriptors and getting and setting their flags.
+const FCNTL_FD_COMMANDS: &[libc::c_int] = &[libc::F_DUPFD, libc::F_DUPFD_CLOEXEC, libc::F_GETFD, libc::F_SETFD];
+
+/// A [`RuleSet`] for duplicating and closing file descriptors, e.g. for redirecting stdio.
+///
+/// This complements [`SystemIO::allow_close`](super::SystemIO::allow_close). Of its methods, only
+/// [`allow_fcntl`](Self::allow_fcntl) adds conditional rules, which are subject to the conflicts
+/// described in [`SafetyContext::enable`](crate::SafetyContext::enable).
+#[must_use]
+pub struct FdOps {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+    /// Syscalls that are allowed with custom rules, i.e. only specific `fcntl` commands
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl FdOps {
+    /// Create a new [`FdOps`] ruleset with nothing allowed by default.
+    pub fn nothing() -> FdOps {
+        FdOps {
+            allowed: HashSet::new(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow all file descriptor management syscalls.
+    pub fn everything() -> FdOps {
+        FdOps::nothing()
+            .allow_dup()
+            .allow_close_range()
+            .allow_fcntl()
+    }
+
+    /// Allow duplicating file descriptors with the `dup` syscalls, but not `fcntl`.
+    pub fn duplication_only() -> FdOps {
+        FdOps::nothing()
+            .allow_dup()
+    }
+
+    /// Allow `dup`, `dup2`, and `dup3`. `dup2` only exists on x86_64; libc uses `dup3` elsewhere.
+    pub fn allow_dup(mut self) -> FdOps {
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend([Sysno::dup2]);
+        self.allowed.extend([Sysno::dup, Sysno::dup3]);
+
+        self
+    }
+
+    /// Allow closing ranges of file descriptors with `close_range`.
+    pub fn allow_close_range(mut self) -> FdOps {
+        self.allowed.extend([Sysno::close_range]);
+
+        self
+    }
+
+    /// Allow `fcntl` with `F_DUPFD`, `F_DUPFD_CLOEXEC`, `F_GETFD`, and `F_SETFD`, which are used to
+    /// duplicate file descriptors and to get and set their close-on-exec flag.
+    pub fn allow_fcntl(mut self) -> FdOps {
+        let rules = self.custom.entry(Sysno::fcntl)
+            .or_insert_with(Vec::new);
+        for &command in FCNTL_FD_COMMANDS {
+            #[allow(clippy::cast_sign_loss)]
+            let command = command as u32;
+            let rule = SeccompRule::new(Sysno::fcntl)
+                .and_condition(SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, command));
+            rules.push(rule);
+        }
+
+        self
+    }
+}
+
+impl RuleSet for FdOps {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "FdOps"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows duplicating and closing file descriptors"
+    }
+}
@@ -0,0 +1,81 @@
+//! Contains a [`RuleSet`] for allowing advisory file locks with `flock` and `fcntl`.
+
+use std::collections::{HashMap, HashSet};
+
+use syscalls::Sysno;
+
+use crate::{SeccompArgumentFilter, SeccompilerComparator, SeccompRule, RuleSet};
+
+/// `fcntl` commands for getting and setting POSIX record locks.
+const FCNTL_LOCK_COMMANDS: &[libc::c_int] = &[libc::F_SETLK, libc::F_SETLKW, libc::F_GETLK];
+
+/// A [`RuleSet`] for taking advisory file locks, e.g. for databases or pid files, without allowing
+/// all `fcntl` commands with [`SystemIO::allow_ioctl`](super::SystemIO::allow_ioctl). The
+/// `fcntl` rules from [`allow_fcntl_locks`](Self::allow_fcntl_locks) are conditional, so they
+/// can't be combined with rulesets allowing `fcntl` unconditionally, see
+/// [`SafetyContext::enable`](crate::SafetyContext::enable); `flock` is allowed unconditionally.
+#[must_use]
+pub struct FileLocking {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+    /// Syscalls that are allowed with custom rules, i.e. only specific `fcntl` commands
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl FileLocking {
+    /// Create a new [`FileLocking`] ruleset with nothing allowed by default.
+    pub fn nothing() -> FileLocking {
+        FileLocking {
+            allowed: HashSet::new(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow taking advisory locks with both `flock` and `fcntl`.
+    pub fn advisory() -> FileLocking {
+        FileLocking::nothing()
+            .allow_flock()
+            .allow_fcntl_locks()
+    }
+
+    /// Allow the `flock` syscall.
+    pub fn allow_flock(mut self) -> FileLocking {
+        self.allowed.extend([Sysno::flock]);
+
+        self
+    }
+
+    /// Allow `fcntl` with `F_SETLK`, `F_SETLKW`, and `F_GETLK`, which are used for POSIX record
+    /// locks, e.g. by `lockf`.
+    pub fn allow_fcntl_locks(mut self) -> FileLocking {
+        let rules = self.custom.entry(Sysno::fcntl)
+            .or_insert_with(Vec::new);
+        for &command in FCNTL_LOCK_COMMANDS {
+            #[allow(clippy::cast_sign_loss)]
+            let command = command as u32;
+            let rule = SeccompRule::new(Sysno::fcntl)
+                .and_condition(SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, command));
+            rules.push(rule);
+        }
+
+        self
+    }
+}
+
+impl RuleSet for FileLocking {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "FileLocking"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows locking files with flock and fcntl"
+    }
+}
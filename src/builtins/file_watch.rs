@@ -0,0 +1,76 @@
+//! Contains a [`RuleSet`] for allowing filesystem watching syscalls, like inotify.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+use super::YesReally;
+
+/// A [`RuleSet`] for watching the filesystem for changes with inotify or fanotify.
+///
+/// Note that reading events from the watch fd requires `read`, e.g. via
+/// [`SystemIO::allow_read`](super::SystemIO::allow_read).
+#[must_use]
+pub struct FileWatch {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl FileWatch {
+    /// Create a new [`FileWatch`] ruleset with nothing allowed by default.
+    pub fn nothing() -> FileWatch {
+        FileWatch {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow watching files and directories with inotify.
+    pub fn inotify() -> FileWatch {
+        FileWatch::nothing()
+            .allow_inotify()
+    }
+
+    /// Allow creating inotify instances and adding and removing watches: `inotify_init`,
+    /// `inotify_init1`, `inotify_add_watch`, and `inotify_rm_watch`. `inotify_init` only exists
+    /// on x86_64.
+    pub fn allow_inotify(mut self) -> FileWatch {
+        self.allowed.extend([
+            #[cfg(target_arch = "x86_64")] Sysno::inotify_init,
+            Sysno::inotify_init1,
+            Sysno::inotify_add_watch, Sysno::inotify_rm_watch,
+        ]);
+
+        self
+    }
+
+    /// Allow creating fanotify instances and marking files and mounts with `fanotify_init` and
+    /// `fanotify_mark`.
+    ///
+    /// # Security considerations
+    ///
+    /// Depending on the flags, fanotify can watch entire mounts or filesystems, give you open fds
+    /// to the accessed files (allowing you to read their contents), and even block other processes'
+    /// access to files until you respond. Most of this functionality requires `CAP_SYS_ADMIN`, but
+    /// if your process has it, fanotify is effectively a privileged operation.
+    pub fn allow_fanotify(mut self) -> YesReally<FileWatch> {
+        self.allowed.extend([Sysno::fanotify_init, Sysno::fanotify_mark]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for FileWatch {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "FileWatch"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows watching files for changes with inotify and fanotify"
+    }
+}
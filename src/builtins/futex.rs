@@ -0,0 +1,115 @@
+//! Contains a [`RuleSet`] for allowing futex syscalls, which are used to implement mutexes,
+//! condition variables, and other thread synchronization primitives.
+
+use std::collections::{HashMap, HashSet};
+
+use syscalls::Sysno;
+
+use crate::{SeccompArgumentFilter, SeccompilerComparator, SeccompRule, RuleSet};
+
+use super::YesReally;
+
+/// The bits of the futex operation that select the command, i.e. everything except the private
+/// and realtime clock flags.
+const FUTEX_CMD_MASK: u32 = !((libc::FUTEX_PRIVATE_FLAG | libc::FUTEX_CLOCK_REALTIME) as u32);
+
+/// Futex operations that don't use priority inheritance.
+const NON_PI_OPS: &[i32] = &[
+    libc::FUTEX_WAIT, libc::FUTEX_WAKE,
+    libc::FUTEX_REQUEUE, libc::FUTEX_CMP_REQUEUE,
+    libc::FUTEX_WAKE_OP,
+    libc::FUTEX_WAIT_BITSET, libc::FUTEX_WAKE_BITSET,
+];
+
+/// A [`RuleSet`] for futex syscalls.
+///
+/// Note that [`BasicCapabilities`](super::BasicCapabilities), which is always enabled when a
+/// `SafetyContext` is applied, allows `futex` unconditionally. If you enable the restricted
+/// version here with [`allow_futex`](Self::allow_futex), the restriction takes precedence over
+/// `BasicCapabilities`.
+#[must_use]
+pub struct Futex {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+    /// Syscalls that are allowed with custom rules, e.g. only specific futex operations
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl Futex {
+    /// Create a new [`Futex`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Futex {
+        Futex {
+            allowed: HashSet::new(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow the futex operations used by the standard library's synchronization primitives:
+    /// `futex` without priority inheritance, and `futex_waitv`.
+    pub fn everything() -> Futex {
+        Futex::nothing()
+            .allow_futex()
+            .allow_futex_waitv()
+    }
+
+    /// Allow `futex`, but only with operations that don't use priority inheritance: `FUTEX_WAIT`,
+    /// `FUTEX_WAKE`, `FUTEX_REQUEUE`, `FUTEX_CMP_REQUEUE`, `FUTEX_WAKE_OP`, `FUTEX_WAIT_BITSET`,
+    /// and `FUTEX_WAKE_BITSET`, with or without the private and realtime clock flags.
+    pub fn allow_futex(mut self) -> Futex {
+        if self.allowed.contains(&Sysno::futex) {
+            return self;
+        }
+
+        // The operation is an int, so only compare the lower 32 bits.
+        let rules = self.custom.entry(Sysno::futex)
+            .or_insert_with(Vec::new);
+        for &op in NON_PI_OPS {
+            let filter = SeccompArgumentFilter::new32(1,
+                SeccompilerComparator::MaskedEq(u64::from(FUTEX_CMD_MASK)), op as u32);
+            rules.push(SeccompRule::new(Sysno::futex).and_condition(filter));
+        }
+
+        self
+    }
+
+    /// Allow `futex` with any operation, including the priority inheritance operations like
+    /// `FUTEX_LOCK_PI`.
+    ///
+    /// # Security considerations
+    ///
+    /// The priority inheritance operations involve considerably more complex kernel code, which
+    /// has historically been the source of privilege escalation vulnerabilities (e.g.
+    /// CVE-2014-3153). They are only needed if you use priority inheritance mutexes, e.g.
+    /// `pthread_mutexattr_setprotocol` with `PTHREAD_PRIO_INHERIT`.
+    pub fn allow_futex_pi(mut self) -> YesReally<Futex> {
+        let _removed = self.custom.remove(&Sysno::futex);
+        self.allowed.extend([Sysno::futex]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow `futex_waitv`, for waiting on multiple futexes at once.
+    pub fn allow_futex_waitv(mut self) -> Futex {
+        self.allowed.extend([Sysno::futex_waitv]);
+
+        self
+    }
+}
+
+impl RuleSet for Futex {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "Futex"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows waiting on and waking futexes"
+    }
+}
@@ -0,0 +1,61 @@
+//! Contains a [`RuleSet`] for allowing getting and setting group ids.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+use super::YesReally;
+
+#[must_use]
+/// Enable syscalls for getting and setting the group ids of the current process, e.g. to drop
+/// privileges.
+pub struct GroupId {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl GroupId {
+    /// Create a new GroupId [`RuleSet`] with nothing allowed by default.
+    pub fn nothing() -> GroupId {
+        GroupId {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow getting the real, effective, and saved group ids, and the supplementary groups:
+    /// `getgid`, `getegid`, `getresgid`, and `getgroups`.
+    pub fn allow_get(mut self) -> GroupId {
+        self.allowed.extend([Sysno::getgid, Sysno::getegid, Sysno::getresgid, Sysno::getgroups]);
+
+        self
+    }
+
+    /// Allow setting the group ids and supplementary groups: `setgid`, `setregid`, `setresgid`,
+    /// `setfsgid`, and `setgroups`.
+    ///
+    /// # Security considerations
+    /// A process with `CAP_SETGID` can use these to switch to any group, including more privileged
+    /// ones. Without it, the process can only switch between its current real, effective, and
+    /// saved group ids, so this is mostly useful for dropping privileges.
+    pub fn allow_set(mut self) -> YesReally<GroupId> {
+        self.allowed.extend([Sysno::setgid, Sysno::setregid, Sysno::setresgid, Sysno::setfsgid, Sysno::setgroups]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for GroupId {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "GroupId"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows getting and setting group ids"
+    }
+}
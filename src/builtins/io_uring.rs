@@ -0,0 +1,104 @@
+//! Contains a [`RuleSet`] for allowing asynchronous IO with `io_uring` and Linux AIO.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+use super::YesReally;
+
+/// A [`RuleSet`] for asynchronous IO with `io_uring`, and the older Linux AIO interface.
+///
+/// # Security considerations
+/// **Operations submitted through io_uring are not syscalls, so seccomp never sees them.** Once
+/// a ring is set up and `io_uring_enter` is allowed, the process can open, read, write, connect
+/// and more without making the corresponding syscalls, regardless of what the rest of the filter
+/// allows or which arguments it restricts. io_uring has also been the source of many kernel
+/// vulnerabilities, which is why e.g. Docker's and Android's default seccomp profiles deny it.
+///
+/// Linux AIO is much more limited, but `io_submit` still reads and writes file descriptors
+/// without going through `read` or `write`, so conditional rules on those syscalls, like
+/// [`SystemIO::allow_stdout`](super::SystemIO::allow_stdout), don't restrict it.
+///
+/// Landlock still applies to files opened through io_uring, so combining the two is much safer
+/// than using seccomp alone. Every function here returns a [`YesReally`] for these reasons, and
+/// there is no `everything()` constructor.
+#[must_use]
+pub struct IoUring {
+    allowed: HashSet<Sysno>,
+}
+
+impl IoUring {
+    /// Create a new [`IoUring`] ruleset with nothing allowed by default.
+    pub fn nothing() -> IoUring {
+        IoUring {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow creating io_uring instances with `io_uring_setup`.
+    ///
+    /// # Security considerations
+    /// See the [`IoUring`] documentation: the operations submitted to the ring are not visible to
+    /// seccomp.
+    pub fn allow_setup(mut self) -> YesReally<IoUring> {
+        self.allowed.extend([Sysno::io_uring_setup]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow submitting operations to and waiting for completions from io_uring instances with
+    /// `io_uring_enter`. This is also needed to use a ring created before the filter was applied.
+    ///
+    /// # Security considerations
+    /// See the [`IoUring`] documentation: the operations submitted to the ring are not visible to
+    /// seccomp.
+    pub fn allow_enter(mut self) -> YesReally<IoUring> {
+        self.allowed.extend([Sysno::io_uring_enter]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow registering files, buffers and other resources with io_uring instances with
+    /// `io_uring_register`.
+    ///
+    /// # Security considerations
+    /// See the [`IoUring`] documentation. `IORING_REGISTER_RESTRICTIONS` can limit the operations
+    /// a ring accepts, but the same syscall also registers the resources those operations use.
+    pub fn allow_register(mut self) -> YesReally<IoUring> {
+        self.allowed.extend([Sysno::io_uring_register]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow Linux AIO with `io_setup`, `io_destroy`, `io_submit`, `io_cancel`, `io_getevents`
+    /// and `io_pgetevents`.
+    ///
+    /// # Security considerations
+    /// See the [`IoUring`] documentation: `io_submit` reads and writes without the `read` and
+    /// `write` syscalls, so their conditional rules don't apply.
+    pub fn allow_aio(mut self) -> YesReally<IoUring> {
+        self.allowed.extend([
+            Sysno::io_setup, Sysno::io_destroy,
+            Sysno::io_submit, Sysno::io_cancel,
+            Sysno::io_getevents, Sysno::io_pgetevents,
+        ]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for IoUring {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "IoUring"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows asynchronous IO with io_uring and Linux AIO, which bypasses seccomp"
+    }
+}
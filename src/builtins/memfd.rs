@@ -0,0 +1,75 @@
+//! Contains a [`RuleSet`] for allowing anonymous memory-backed files with `memfd_create` and
+//! `memfd_secret`.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+#[must_use]
+/// Enable syscalls for creating anonymous files that live only in memory, e.g. for sharing sealed
+/// buffers with other processes or keeping secrets out of the rest of the address space.
+///
+/// The returned file descriptors are used with the usual syscalls, so you will probably also want
+/// e.g. [`SystemIO::allow_write`](super::SystemIO::allow_write) and
+/// [`Memory`](super::Memory) to map them. Adding seals to a memfd requires `fcntl` with
+/// `F_ADD_SEALS`, which is allowed by [`SystemIO::allow_ioctl`](super::SystemIO::allow_ioctl).
+pub struct Memfd {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Memfd {
+    /// Create a new [`Memfd`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Memfd {
+        Memfd {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow both `memfd_create` and `memfd_secret`.
+    pub fn everything() -> Memfd {
+        Memfd::nothing()
+            .allow_memfd_create()
+            .allow_memfd_secret()
+    }
+
+    /// Allow `memfd_create`, which creates an anonymous file backed by memory. The file has no
+    /// path, so this doesn't give access to anything outside of the process.
+    pub fn allow_memfd_create(mut self) -> Memfd {
+        self.allowed.extend([Sysno::memfd_create]);
+
+        self
+    }
+
+    /// Allow `memfd_secret`, which creates an anonymous file whose memory is removed from the
+    /// kernel's direct map, so that it can't be read by other processes or, for the most part,
+    /// the kernel itself.
+    ///
+    /// # Security Notes
+    ///
+    /// This is safe to allow in the sense that it only creates private memory, but secret memory
+    /// is locked and can't be swapped out, so it counts against `RLIMIT_MEMLOCK`. It is only
+    /// available since Linux 5.14, and may be disabled at boot, in which case it fails with
+    /// `ENOSYS`.
+    pub fn allow_memfd_secret(mut self) -> Memfd {
+        self.allowed.extend([Sysno::memfd_secret]);
+
+        self
+    }
+}
+
+impl RuleSet for Memfd {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Memfd"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows creating anonymous memory-backed files with memfd_create and memfd_secret"
+    }
+}
@@ -0,0 +1,228 @@
+//! Contains a [`RuleSet`] for allowing memory management syscalls like `mmap` and `mprotect`.
+
+use std::collections::{HashMap, HashSet};
+
+use syscalls::Sysno;
+
+use crate::{SeccompRule, RuleSet};
+
+use super::YesReally;
+
+const PROT_WRITE: u64 = libc::PROT_WRITE as u64;
+const PROT_EXEC: u64 = libc::PROT_EXEC as u64;
+
+/// A [`RuleSet`] for fine-grained control over memory management syscalls.
+///
+/// Note that [`BasicCapabilities`](super::BasicCapabilities), which is always enabled when a
+/// `SafetyContext` is applied, allows these syscalls unconditionally. If you enable a restricted
+/// version of a syscall here (e.g. [`allow_mmap`](Self::allow_mmap), which does not allow
+/// executable mappings), the restriction takes precedence over `BasicCapabilities`.
+#[must_use]
+pub struct Memory {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+    /// Syscalls that are allowed with custom rules, e.g. only without `PROT_EXEC`
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl Memory {
+    /// Create a new [`Memory`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Memory {
+        Memory {
+            allowed: HashSet::new(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow the syscalls needed by a typical allocator: `brk`, `mmap` without `PROT_EXEC`, and
+    /// `munmap`.
+    pub fn basic_allocation() -> Memory {
+        Memory::nothing()
+            .allow_brk()
+            .allow_mmap()
+            .allow_munmap()
+    }
+
+    /// Add a rule allowing `syscall` only if its protection flags, which must be the third
+    /// argument, do not contain `PROT_EXEC`. If the syscall is already allowed unconditionally,
+    /// nothing is added.
+    fn restrict_no_exec(&mut self, syscall: Sysno) {
+        if self.allowed.contains(&syscall) {
+            return;
+        }
+
+        let rule = SeccompRule::new(syscall)
+            .and_condition(seccomp_arg_filter!(arg2 & PROT_EXEC == 0));
+        self.custom.entry(syscall)
+            .or_insert_with(Vec::new)
+            .push(rule);
+    }
+
+    /// Add rules allowing `syscall` only if its protection flags, which must be the third
+    /// argument, do not contain both `PROT_WRITE` and `PROT_EXEC`. If the syscall is already
+    /// allowed unconditionally, nothing is added.
+    fn restrict_no_wx(&mut self, syscall: Sysno) {
+        const WX: u64 = PROT_WRITE | PROT_EXEC;
+
+        if self.allowed.contains(&syscall) {
+            return;
+        }
+
+        // Seccomp can't express "not all of these bits are set" in a single comparison, so we
+        // instead allow each of the three remaining combinations of the two bits with a separate
+        // rule. Rules are or-ed together.
+        let rules = self.custom.entry(syscall)
+            .or_insert_with(Vec::new);
+        for allowed_bits in [0, PROT_WRITE, PROT_EXEC] {
+            let rule = SeccompRule::new(syscall)
+                .and_condition(seccomp_arg_filter!(arg2 & WX == allowed_bits));
+            rules.push(rule);
+        }
+    }
+
+    /// Allow `syscall` unconditionally, removing any conditional rules for it.
+    fn allow_unconditionally(&mut self, syscall: Sysno) {
+        let _removed = self.custom.remove(&syscall);
+        self.allowed.extend([syscall]);
+    }
+
+    /// Allow `brk`.
+    pub fn allow_brk(mut self) -> Memory {
+        self.allowed.extend([Sysno::brk]);
+
+        self
+    }
+
+    /// Allow `mmap`, but only if the mapping is not executable (i.e. without `PROT_EXEC`).
+    pub fn allow_mmap(mut self) -> Memory {
+        self.restrict_no_exec(Sysno::mmap);
+
+        self
+    }
+
+    /// Allow `mmap` with any protection flags, including `PROT_EXEC`.
+    ///
+    /// # Security considerations
+    ///
+    /// Creating executable mappings allows an attacker who can write to memory to run arbitrary
+    /// code. You should only need this if you are writing a JIT or loading shared libraries after
+    /// the `SafetyContext` is applied.
+    pub fn allow_mmap_exec(mut self) -> YesReally<Memory> {
+        self.allow_unconditionally(Sysno::mmap);
+
+        YesReally::new(self)
+    }
+
+    /// Allow `mmap` and `mprotect`, but reject any call whose protection flags contain both
+    /// `PROT_WRITE` and `PROT_EXEC`, i.e. enforce W^X: memory may be writable or executable, but
+    /// not both at once.
+    ///
+    /// The protection flags are the third argument (`arg2`) of both `mmap(addr, length, prot,
+    /// flags, fd, offset)` and `mprotect(addr, len, prot)`, so the same filter is used for both.
+    ///
+    /// Note that this still allows a page to be made writable and later made executable with a
+    /// second `mprotect` call.
+    pub fn allow_mmap_no_wx(mut self) -> Memory {
+        self.restrict_no_wx(Sysno::mmap);
+        self.restrict_no_wx(Sysno::mprotect);
+
+        self
+    }
+
+    /// Allow `munmap`.
+    pub fn allow_munmap(mut self) -> Memory {
+        self.allowed.extend([Sysno::munmap]);
+
+        self
+    }
+
+    /// Allow `mprotect`, but only if the new protection flags do not contain `PROT_EXEC`.
+    pub fn allow_mprotect(mut self) -> Memory {
+        self.restrict_no_exec(Sysno::mprotect);
+
+        self
+    }
+
+    /// Allow `mprotect` with any protection flags, including `PROT_EXEC`.
+    ///
+    /// # Security considerations
+    ///
+    /// Making pages executable allows an attacker who can write to memory to run arbitrary code.
+    /// You should only need this if you are writing a JIT.
+    pub fn allow_mprotect_exec(mut self) -> YesReally<Memory> {
+        self.allow_unconditionally(Sysno::mprotect);
+
+        YesReally::new(self)
+    }
+
+    /// Allow `madvise` with any advice.
+    pub fn allow_madvise(mut self) -> Memory {
+        self.allow_unconditionally(Sysno::madvise);
+
+        self
+    }
+
+    /// Allow `madvise`, but only with advice that is a pure hint about access patterns:
+    /// `MADV_NORMAL`, `MADV_RANDOM`, `MADV_SEQUENTIAL`, and `MADV_WILLNEED`. The advice is the
+    /// third argument (`arg2`) of `madvise(addr, length, advice)`.
+    ///
+    /// Other advice values change the contents or semantics of memory and are rejected. For
+    /// example, `MADV_DONTNEED` and `MADV_FREE` discard pages, so later reads return zeroes, and
+    /// `MADV_WIPEONFORK` and `MADV_DONTFORK` change what a forked child sees. These can be used to
+    /// corrupt memory that other code in the process relies on.
+    ///
+    /// Note that allocators, including glibc's `malloc`, use `MADV_DONTNEED` or `MADV_FREE` to
+    /// return freed memory to the kernel. With this rule those calls are rejected by the
+    /// `SafetyContext`'s default action. Allocators tolerate the `EPERM` returned by default,
+    /// though memory usage may be higher, but not e.g.
+    /// [`kill_process_on_violation`](crate::SafetyContext::kill_process_on_violation). If
+    /// `madvise` is already allowed unconditionally, nothing is added.
+    pub fn allow_madvise_safe(mut self) -> Memory {
+        const SAFE_ADVICE: [i32; 4] = [
+            libc::MADV_NORMAL,
+            libc::MADV_RANDOM,
+            libc::MADV_SEQUENTIAL,
+            libc::MADV_WILLNEED,
+        ];
+
+        if self.allowed.contains(&Sysno::madvise) {
+            return self;
+        }
+
+        let rules = self.custom.entry(Sysno::madvise)
+            .or_insert_with(Vec::new);
+        for advice in SAFE_ADVICE {
+            let advice = advice as u64;
+            let rule = SeccompRule::new(Sysno::madvise)
+                .and_condition(seccomp_arg_filter!(arg2 == advice));
+            rules.push(rule);
+        }
+
+        self
+    }
+
+    /// Allow `mremap`.
+    pub fn allow_mremap(mut self) -> Memory {
+        self.allowed.extend([Sysno::mremap]);
+
+        self
+    }
+}
+
+impl RuleSet for Memory {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "Memory"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows mapping, allocating, and protecting memory"
+    }
+}
@@ -25,6 +25,9 @@ impl<T> YesReally<T> {
 pub mod basic;
 pub use basic::BasicCapabilities;
 
+pub mod rust_runtime;
+pub use rust_runtime::RustRuntime;
+
 pub mod systemio;
 pub use systemio::SystemIO;
 
@@ -34,5 +37,199 @@ pub use network::Networking;
 pub mod time;
 pub use time::Time;
 
-pub mod danger_zone;
+pub mod memory;
+pub use memory::Memory;
+
+pub mod signals;
+pub use signals::Signals;
+
+pub mod epoll;
+pub use epoll::Epoll;
+
+pub mod polling;
+pub use polling::Polling;
+
+pub mod file_watch;
+pub use file_watch::FileWatch;
+
+pub mod randomness;
+pub use randomness::Randomness;
+
+pub mod futex;
+pub use futex::Futex;
+
+pub mod scheduling;
+pub use scheduling::Scheduling;
+
+pub mod xattr;
+pub use xattr::Xattr;
+
+pub mod terminal;
+pub use terminal::Terminal;
+
+pub mod process_info;
+pub use process_info::ProcessInfo;
+
+pub mod process_control;
+pub use process_control::ProcessControl;
+
+pub mod group_id;
+pub use group_id::GroupId;
+
+pub mod eventing;
+pub use eventing::Eventing;
+
+pub mod file_locking;
+pub use file_locking::FileLocking;
+
+pub mod fd;
+pub use fd::FdOps;
+
+pub mod shared_memory;
+pub use shared_memory::SharedMemory;
+
+pub mod memfd;
+pub use memfd::Memfd;
+
+pub mod io_uring;
+pub use io_uring::IoUring;
+
+pub mod prctl;
+pub use prctl::Prctl;
+
+pub mod rlimit;
+pub use rlimit::Rlimit;
+
+pub mod cwd;
+pub use cwd::Cwd;
+
+pub mod backtrace;
+pub use backtrace::Backtrace;
+
 pub mod pipes;
+pub use pipes::Pipes;
+
+pub mod danger_zone;
+
+/// The name of `ruleset` and every syscall it allows, with or without conditions, sorted.
+fn syscalls<R: crate::RuleSet>(ruleset: &R) -> (&'static str, Vec<syscalls::Sysno>) {
+    let mut syscalls = ruleset.simple_rules();
+    syscalls.extend(ruleset.conditional_rules().into_keys());
+    syscalls.sort();
+    syscalls.dedup();
+
+    (ruleset.name(), syscalls)
+}
+
+/// Returns the name of every builtin [`RuleSet`](crate::RuleSet), including the ones in the
+/// [`danger_zone`], along with the syscalls it allows with everything enabled, including the
+/// functions that return a [`YesReally`] and syscalls that are only allowed with conditions.
+///
+/// This can be used to generate coverage reports or documentation. Functions that take
+/// arguments, like [`SystemIO::allow_file_read`], don't allow any syscalls beyond the ones
+/// included here.
+#[must_use]
+pub fn all() -> Vec<(&'static str, Vec<syscalls::Sysno>)> {
+    vec![
+        syscalls(&BasicCapabilities),
+        syscalls(&RustRuntime),
+        syscalls(&SystemIO::everything()
+            .allow_rename()
+            .allow_directory_ops()
+            .allow_symlink_ops()
+            .allow_symlink_create()
+            .allow_filesystem_metadata()
+            .allow_ioctl()),
+        syscalls(&Networking::nothing()
+            .allow_running_tcp_servers()
+            .allow_start_tcp_servers().yes_really()
+            .allow_running_udp_sockets()
+            .allow_start_udp_servers().yes_really()
+            .allow_connect().yes_really()
+            .allow_start_tcp_clients()
+            .allow_running_tcp_clients()
+            .allow_start_unix_servers().yes_really()
+            .allow_running_unix_servers()
+            .allow_running_unix_clients()
+            .allow_unix_sockets()
+            .allow_fd_passing()),
+        syscalls(&Time::nothing()
+            .allow_gettime()
+            .allow_posix_timers()
+            .allow_set_non_realtime_clocks()),
+        syscalls(&Memory::nothing()
+            .allow_brk()
+            .allow_mmap_exec().yes_really()
+            .allow_munmap()
+            .allow_mprotect_exec().yes_really()
+            .allow_madvise()
+            .allow_mremap()),
+        syscalls(&Signals::handlers()
+            .allow_rt_sigpending()),
+        syscalls(&Epoll::everything()),
+        syscalls(&Polling::everything()),
+        syscalls(&FileWatch::nothing()
+            .allow_inotify()
+            .allow_fanotify().yes_really()),
+        syscalls(&Randomness::nothing()
+            .allow_getrandom()),
+        syscalls(&Futex::everything()
+            .allow_futex_pi().yes_really()),
+        syscalls(&Scheduling::nothing()
+            .allow_query()
+            .allow_modify().yes_really()),
+        syscalls(&Xattr::nothing()
+            .allow_read()
+            .allow_write().yes_really()),
+        syscalls(&Terminal::nothing()
+            .allow_raw_mode()
+            .allow_window_size()),
+        syscalls(&ProcessInfo::everything()),
+        syscalls(&ProcessControl::everything()),
+        syscalls(&GroupId::nothing()
+            .allow_get()
+            .allow_set().yes_really()),
+        syscalls(&Eventing::everything()),
+        syscalls(&FileLocking::advisory()),
+        syscalls(&FdOps::everything()),
+        syscalls(&SharedMemory::nothing()
+            .allow_sysv_shm().yes_really()
+            .allow_posix_mq().yes_really()),
+        syscalls(&Memfd::everything()),
+        syscalls(&IoUring::nothing()
+            .allow_setup().yes_really()
+            .allow_enter().yes_really()
+            .allow_register().yes_really()
+            .allow_aio().yes_really()),
+        syscalls(&Prctl::nothing()
+            .allow_set_name()
+            .allow_get_name()
+            .allow_set_no_new_privs()
+            .allow_get_no_new_privs()
+            .allow_get_dumpable()
+            .allow_get_seccomp()
+            .allow_set_dumpable().yes_really()
+            .allow_set_seccomp().yes_really()),
+        syscalls(&Rlimit::nothing()
+            .allow_get()
+            .allow_set().yes_really()),
+        syscalls(&Cwd::everything()),
+        syscalls(&Backtrace),
+        syscalls(&Pipes),
+        syscalls(&danger_zone::Threads::nothing()
+            .allow_create()
+            .allow_sleep().yes_really()),
+        syscalls(&danger_zone::ForkAndExec),
+        syscalls(&danger_zone::Fork::nothing()
+            .allow_fork().yes_really()
+            .allow_vfork().yes_really()),
+        syscalls(&danger_zone::Namespaces::nothing()
+            .allow_mount().yes_really()
+            .allow_namespace_ops().yes_really()
+            .allow_pivot_root().yes_really()),
+        syscalls(&danger_zone::Capabilities::nothing()
+            .allow_capget()
+            .allow_capset().yes_really()
+            .allow_drop_privileges()),
+    ]
+}
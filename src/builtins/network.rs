@@ -7,19 +7,28 @@ use syscalls::Sysno;
 use super::YesReally;
 use crate::{SeccompRule, RuleSet};
 
+#[cfg(feature = "landlock")]
+use crate::landlock::{access, LandlockNetRule};
+
 // TODO: make bind calls conditional on the DGRAM/UNIX/STREAM flag in each function
 
 // TODO: add io_uring
 const NET_IO_SYSCALLS: &[Sysno] = &[
-    Sysno::epoll_create, Sysno::epoll_create1,
-    Sysno::epoll_ctl, Sysno::epoll_wait, Sysno::epoll_pwait, Sysno::epoll_pwait2,
-    Sysno::select, Sysno::pselect6,
-    Sysno::poll, Sysno::ppoll,
+    // The legacy variants only exist on x86_64
+    #[cfg(target_arch = "x86_64")] Sysno::epoll_create,
+    Sysno::epoll_create1,
+    #[cfg(target_arch = "x86_64")] Sysno::epoll_wait,
+    Sysno::epoll_ctl, Sysno::epoll_pwait, Sysno::epoll_pwait2,
+    #[cfg(target_arch = "x86_64")] Sysno::select,
+    Sysno::pselect6,
+    #[cfg(target_arch = "x86_64")] Sysno::poll,
+    Sysno::ppoll,
 
     Sysno::accept, Sysno::accept4,
 
     // used in reqwest::blocking I guess to notify when blocking reads finish?
-    Sysno::eventfd, Sysno::eventfd2,
+    #[cfg(target_arch = "x86_64")] Sysno::eventfd,
+    Sysno::eventfd2,
 
     // Used to set tcp_nodelay
     Sysno::fcntl, Sysno::ioctl,
@@ -39,18 +48,48 @@ const NET_WRITE_SYSCALLS: &[Sysno] = &[Sysno::sendto, Sysno::sendmsg, Sysno::sen
                                        Sysno::sendfile,
                                        Sysno::write, Sysno::writev, Sysno::pwritev, Sysno::pwritev2];
 
+/// The socket type is in the lower bits of the second argument of `socket`, and may be combined
+/// with flags like `SOCK_CLOEXEC` or `SOCK_NONBLOCK` in the upper bits.
+const SOCK_TYPE_MASK: u64 = 0xf;
+
 // TODO: refactor Socket rule creation to reduce duplication in the allow_start_*_server functions
 
+/// An address family, i.e. the domain argument of the `socket` syscall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressFamily {
+    /// IPv4, `AF_INET`
+    Inet,
+    /// IPv6, `AF_INET6`
+    Inet6,
+    /// Unix domain sockets, `AF_UNIX`
+    Unix,
+    /// Netlink sockets for communicating with the kernel, `AF_NETLINK`
+    Netlink,
+}
+
+impl AddressFamily {
+    /// The value of the `AF_*` constant for this address family.
+    fn value(self) -> u64 {
+        let family = match self {
+            AddressFamily::Inet => libc::AF_INET,
+            AddressFamily::Inet6 => libc::AF_INET6,
+            AddressFamily::Unix => libc::AF_UNIX,
+            AddressFamily::Netlink => libc::AF_NETLINK,
+        };
+        family as u64
+    }
+}
+
 /// A [`RuleSet`] representing syscalls that perform network operations - accept/listen/bind/connect etc.
 ///
 /// # How to use
 ///
 /// 1. Select TCP or UDP (or both) with `enable_tcp()`, `enable_udp()`
-/// 2a. If you are a server of some sort, **strongly** consider first binding to your ports and
-///     then not allowing further binds by using `running_tcp_server()` or `running_udp_server()`.
-///     Otherwise,
-/// 2b. If you are a client, use `tcp_client()` and/or `udp_client()`, which does not allow
-///     `accept` or `listen` syscalls.
+/// 2. If you are a server of some sort, **strongly** consider first binding to your ports and
+///    then not allowing further binds by using `running_tcp_server()` or `running_udp_server()`.
+///    Otherwise, if you are a client, use `tcp_client()` and/or `udp_client()`, which does not
+///    allow `accept` or `listen` syscalls.
+///
 /// The most common use-case: select TCP or UDP (or both) with `.enable_tcp()` or `.enable_udp()`,
 /// and then decide if you're going to allow binding to new ports
 ///
@@ -68,6 +107,9 @@ pub struct Networking {
     allowed: HashSet<Sysno>,
     /// Syscalls that are allowed with custom rules, e.g. only allow to specific fds
     custom: HashMap<Sysno, Vec<SeccompRule>>,
+    #[cfg(feature = "landlock")]
+    /// Landlock network rules
+    landlock_net_rules: Vec<LandlockNetRule>,
 }
 
 impl Networking {
@@ -76,6 +118,8 @@ impl Networking {
         Networking {
             allowed: HashSet::new(),
             custom: HashMap::new(),
+            #[cfg(feature = "landlock")]
+            landlock_net_rules: Vec::new(),
         }
     }
 
@@ -271,6 +315,130 @@ impl Networking {
         self
     }
 
+    /// Allow creating, binding, and connecting Unix domain sockets of any type, but no internet
+    /// sockets.
+    ///
+    /// The `socket` syscall is only allowed with the `AF_UNIX` domain, so this composes with the
+    /// TCP and UDP functions above: enabling both allows creating either kind of socket.
+    ///
+    /// # Security Notes
+    ///
+    /// `connect` and `bind` are allowed unconditionally, since their address argument is a
+    /// struct we can't look into due to seccomp restrictions. If the process already holds an
+    /// internet socket, it can still be used with them.
+    pub fn allow_unix_sockets(mut self) -> Networking {
+        const AF_UNIX: u64 = libc::AF_UNIX as u64;
+
+        let rule = SeccompRule::new(Sysno::socket)
+            .and_condition(seccomp_arg_filter!(arg0 == AF_UNIX));
+        self.custom.entry(Sysno::socket)
+            .or_insert_with(Vec::new)
+            .push(rule);
+
+        self.allowed.extend(&[Sysno::bind, Sysno::connect]);
+        self.allowed.extend(NET_IO_SYSCALLS);
+        self.allowed.extend(NET_READ_SYSCALLS);
+        self.allowed.extend(NET_WRITE_SYSCALLS);
+
+        self
+    }
+
+    /// Allow logging to the local syslog daemon over its unix datagram socket, e.g. `/dev/log`,
+    /// as glibc's `syslog` function does.
+    ///
+    /// This allows `socket` only for unix datagram sockets, as well as `connect` and `sendto`,
+    /// which is enough for e.g. `UnixDatagram::send_to`. Rust's `UnixDatagram::send` writes to
+    /// the connected socket with `write` instead, so callers using it need to allow `write` on the
+    /// socket separately, e.g. with [`SystemIO::allow_write_fds`](super::SystemIO::allow_write_fds)
+    /// or [`SystemIO::allow_write`](super::SystemIO::allow_write).
+    ///
+    /// Note that on systems using systemd-journald, `/dev/log` is a symlink to
+    /// `/run/systemd/journal/dev-log`, so with landlock both paths need to be accessible.
+    /// journald's native protocol uses `/run/systemd/journal/socket` and `sendmsg` instead, which
+    /// is not allowed here.
+    ///
+    /// # Security Notes
+    ///
+    /// `connect` is allowed unconditionally, since its address argument is a struct we can't look
+    /// into due to seccomp restrictions.
+    pub fn allow_syslog(mut self) -> Networking {
+        const AF_UNIX: u64 = libc::AF_UNIX as u64;
+        const SOCK_DGRAM: u64 = libc::SOCK_DGRAM as u64;
+
+        let rule = SeccompRule::new(Sysno::socket)
+            .and_condition(seccomp_arg_filter!(arg0 == AF_UNIX))
+            .and_condition(seccomp_arg_filter!(arg1 & SOCK_TYPE_MASK == SOCK_DGRAM));
+        self.custom.entry(Sysno::socket)
+            .or_insert_with(Vec::new)
+            .push(rule);
+
+        self.allowed.extend(&[Sysno::connect, Sysno::sendto]);
+
+        self
+    }
+
+    /// Allow creating sockets of any type, but only in the given address families.
+    ///
+    /// This adds one rule on `socket` per family, each requiring the first argument to equal that
+    /// family. Conditions within a single rule must all match, while separate rules are or-ed
+    /// together, so a socket may be created if its family is any of the given ones. Rules from
+    /// other functions like [`allow_start_tcp_clients`](Self::allow_start_tcp_clients) are or-ed
+    /// in the same way, so this can widen but never narrow what they allow.
+    ///
+    /// This only allows `socket`; to use the sockets you need to allow e.g. `bind` or `connect`
+    /// with the other functions here.
+    pub fn allow_address_families(mut self, families: &[AddressFamily]) -> Networking {
+        if families.is_empty() {
+            return self;
+        }
+
+        let rules = self.custom.entry(Sysno::socket)
+            .or_insert_with(Vec::new);
+        for family in families {
+            let rule = SeccompRule::new(Sysno::socket)
+                .and_condition(seccomp_arg_filter!(arg0 == family.value()));
+            rules.push(rule);
+        }
+
+        self
+    }
+
+    /// Allow creating stream sockets, e.g. TCP or unix stream sockets, in any address family.
+    ///
+    /// This adds a rule on `socket` requiring the type in the second argument to be exactly
+    /// `SOCK_STREAM`, ignoring the `SOCK_CLOEXEC` and `SOCK_NONBLOCK` flags. As with
+    /// [`allow_address_families`](Self::allow_address_families), the rule is or-ed with other
+    /// rules on `socket`, and only `socket` is allowed.
+    pub fn allow_stream_sockets_only(self) -> Networking {
+        self.allow_socket_type(libc::SOCK_STREAM)
+    }
+
+    /// Allow creating datagram sockets, e.g. UDP or unix datagram sockets, in any address family.
+    ///
+    /// This adds a rule on `socket` requiring the type in the second argument to be exactly
+    /// `SOCK_DGRAM`, ignoring the `SOCK_CLOEXEC` and `SOCK_NONBLOCK` flags. As with
+    /// [`allow_address_families`](Self::allow_address_families), the rule is or-ed with other
+    /// rules on `socket`, and only `socket` is allowed.
+    pub fn allow_dgram_sockets_only(self) -> Networking {
+        self.allow_socket_type(libc::SOCK_DGRAM)
+    }
+
+    /// Add a rule allowing `socket` with the given type and any flags, unless it already exists.
+    fn allow_socket_type(mut self, socket_type: libc::c_int) -> Networking {
+        #[allow(clippy::cast_sign_loss)]
+        let socket_type = socket_type as u64;
+        let rule = SeccompRule::new(Sysno::socket)
+            .and_condition(seccomp_arg_filter!(arg1 & SOCK_TYPE_MASK == socket_type));
+
+        let rules = self.custom.entry(Sysno::socket)
+            .or_insert_with(Vec::new);
+        if !rules.contains(&rule) {
+            rules.push(rule);
+        }
+
+        self
+    }
+
     /// Allow a running Unix socket client to continue running. Does not allow `socket` or `connect`,
     /// preventing new sockets from being created.
     ///
@@ -283,6 +451,54 @@ impl Networking {
 
         self
     }
+
+    /// Allow sending messages on existing sockets with `sendmsg` and `sendmmsg`, e.g. for
+    /// scatter-gather IO or sending to multiple addresses at once.
+    ///
+    /// # Security considerations
+    /// On unix sockets, `sendmsg` can pass this process's file descriptors to the peer with
+    /// `SCM_RIGHTS`. This only shares access the process already has, but seccomp can't inspect
+    /// the control messages, so it can't be prevented either.
+    pub fn allow_sendmsg(mut self) -> Networking {
+        self.allowed.extend([Sysno::sendmsg, Sysno::sendmmsg]);
+
+        self
+    }
+
+    /// Allow receiving messages on existing sockets with `recvmsg` and `recvmmsg`.
+    ///
+    /// # Security considerations
+    /// The control messages are passed in memory, which seccomp can't inspect, so on a unix
+    /// socket this can't prevent receiving file descriptors with `SCM_RIGHTS` if the peer sends
+    /// them and the buffer has room for them. The same is true for every method that allows
+    /// `recvmsg`, including e.g. [`allow_running_unix_servers`](Self::allow_running_unix_servers).
+    /// Only use these on unix sockets whose peer is trusted not to send fds, and use
+    /// [`allow_fd_passing`](Self::allow_fd_passing) to make it explicit when receiving fds is
+    /// intended.
+    pub fn allow_recvmsg(mut self) -> Networking {
+        self.allowed.extend([Sysno::recvmsg, Sysno::recvmmsg]);
+
+        self
+    }
+
+    /// Allow passing file descriptors over unix sockets with `SCM_RIGHTS`, i.e. both
+    /// [`allow_sendmsg`](Self::allow_sendmsg) and [`allow_recvmsg`](Self::allow_recvmsg), for
+    /// privilege-separated programs where one process opens files or sockets on behalf of another.
+    ///
+    /// # Security considerations
+    /// A received file descriptor carries the access rights of whoever opened it, regardless of
+    /// this process's seccomp filter and landlock rules: a sandboxed process that can't open any
+    /// files can still read and write a file whose fd it receives, or use a socket bound to a
+    /// port it couldn't bind itself. Only receive fds from a peer you trust to send the right
+    /// ones.
+    ///
+    /// Seccomp can't distinguish fd passing from other messages, so this allows the same syscalls
+    /// as the two functions above, and does not restrict anything compared to them. It only
+    /// exists so that the intent is visible in the code.
+    pub fn allow_fd_passing(self) -> Networking {
+        self.allow_sendmsg()
+            .allow_recvmsg()
+    }
 }
 
 impl RuleSet for Networking {
@@ -294,7 +510,53 @@ impl RuleSet for Networking {
         self.custom.clone()
     }
 
+    #[cfg(feature = "landlock")]
+    fn landlock_net_rules(&self) -> Vec<LandlockNetRule> {
+        self.landlock_net_rules.clone()
+    }
+
     fn name(&self) -> &'static str {
         "Networking"
     }
+
+    fn description(&self) -> &'static str {
+        "allows TCP, UDP, and Unix domain sockets"
+    }
+}
+
+// landlock impls for Networking
+
+#[cfg(feature = "landlock")]
+impl Networking {
+    /// Use Landlock to allow binding TCP sockets only to the given port. If this function is
+    /// called multiple times, all ports passed will be allowed.
+    ///
+    /// This also allows creating new TCP sockets and running servers on them, as with
+    /// [`allow_start_tcp_servers`](Self::allow_start_tcp_servers), but Landlock prevents binding
+    /// to any other port.
+    ///
+    /// Landlock network rules require Linux 6.7 or newer. Applying the `SafetyContext` will fail
+    /// on older kernels rather than silently allowing all ports.
+    pub fn allow_bind_tcp(mut self, port: u16) -> Networking {
+        self.landlock_net_rules.push(LandlockNetRule::new(port, access::bind_tcp()));
+
+        // allow relevant syscalls as well
+        self.allow_start_tcp_servers().yes_really()
+    }
+
+    /// Use Landlock to allow connecting TCP sockets only to the given port. If this function is
+    /// called multiple times, all ports passed will be allowed.
+    ///
+    /// This also allows creating new TCP sockets, as with
+    /// [`allow_start_tcp_clients`](Self::allow_start_tcp_clients), but Landlock prevents connecting
+    /// to any other port.
+    ///
+    /// Landlock network rules require Linux 6.7 or newer. Applying the `SafetyContext` will fail
+    /// on older kernels rather than silently allowing all ports.
+    pub fn allow_connect_tcp(mut self, port: u16) -> Networking {
+        self.landlock_net_rules.push(LandlockNetRule::new(port, access::connect_tcp()));
+
+        // allow relevant syscalls as well
+        self.allow_start_tcp_clients()
+    }
 }
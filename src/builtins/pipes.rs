@@ -4,14 +4,18 @@ use syscalls::Sysno;
 use crate::RuleSet;
 
 /// [`Pipes`] allows you to create anonymous pipes for inter-process communication via the `pipe`
-/// syscalls.
+/// syscalls. `pipe` only exists on x86_64, elsewhere only `pipe2` is allowed.
 pub struct Pipes;
 impl RuleSet for Pipes {
     fn simple_rules(&self) -> Vec<Sysno> {
-        vec![Sysno::pipe, Sysno::pipe2]
+        vec![#[cfg(target_arch = "x86_64")] Sysno::pipe, Sysno::pipe2]
     }
 
     fn name(&self) -> &'static str {
         "Pipes"
     }
+
+    fn description(&self) -> &'static str {
+        "allows creating pipes"
+    }
 }
@@ -0,0 +1,73 @@
+//! Contains a [`RuleSet`] for allowing the classic `poll` and `select` syscalls, used by older
+//! event loops and many libraries.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+/// A [`RuleSet`] for waiting on multiple fds with `poll`, `ppoll`, `select`, and `pselect6`. See
+/// [`Epoll`](super::Epoll) for the epoll syscalls.
+///
+/// Note that [`Networking`](super::Networking) already allows these when you allow running
+/// servers or clients.
+#[must_use]
+pub struct Polling {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Polling {
+    /// Create a new [`Polling`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Polling {
+        Polling {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow all `poll` and `select` syscalls.
+    pub fn everything() -> Polling {
+        Polling::nothing()
+            .allow_poll()
+            .allow_select()
+    }
+
+    /// Allow `poll` and `ppoll`, as well as `ppoll_time64` on 32 bit systems. `poll` only exists
+    /// on x86_64; libc uses `ppoll` elsewhere.
+    pub fn allow_poll(mut self) -> Polling {
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend([Sysno::poll]);
+        self.allowed.extend([Sysno::ppoll]);
+        #[cfg(target_pointer_width = "32")]
+        self.allowed.extend([Sysno::ppoll_time64]);
+
+        self
+    }
+
+    /// Allow `select` and `pselect6`, as well as `pselect6_time64` on 32 bit systems. `select`
+    /// only exists on x86_64; libc uses `pselect6` elsewhere.
+    pub fn allow_select(mut self) -> Polling {
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend([Sysno::select]);
+        self.allowed.extend([Sysno::pselect6]);
+        #[cfg(target_pointer_width = "32")]
+        self.allowed.extend([Sysno::pselect6_time64]);
+
+        self
+    }
+}
+
+impl RuleSet for Polling {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Polling"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows waiting on file descriptors with poll and select"
+    }
+}
@@ -0,0 +1,117 @@
+//! Contains a [`RuleSet`] for allowing individual `prctl` options.
+
+use std::collections::HashMap;
+
+use syscalls::Sysno;
+
+use crate::{SeccompArgumentFilter, SeccompilerComparator, SeccompRule, RuleSet};
+
+use super::YesReally;
+
+/// A [`RuleSet`] for `prctl`, which multiplexes many unrelated operations on its first argument.
+/// Each function allows a single option, so that e.g. a thread can set its name without being
+/// able to change its seccomp filters or dumpable flag.
+///
+/// Because these are conditional rules on `prctl`, this can't be enabled at the same time as
+/// other rulesets that allow `prctl` unconditionally. It can be combined with
+/// [`Capabilities::allow_drop_privileges`](super::danger_zone::Capabilities::allow_drop_privileges).
+#[must_use]
+pub struct Prctl {
+    /// Syscalls that are allowed with custom rules, i.e. only specific `prctl` options
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl Prctl {
+    /// Create a new [`Prctl`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Prctl {
+        Prctl {
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow setting the name of the current thread with `PR_SET_NAME`.
+    pub fn allow_set_name(self) -> Prctl {
+        self.allow_option(libc::PR_SET_NAME)
+    }
+
+    /// Allow getting the name of the current thread with `PR_GET_NAME`.
+    pub fn allow_get_name(self) -> Prctl {
+        self.allow_option(libc::PR_GET_NAME)
+    }
+
+    /// Allow setting `no_new_privs` with `PR_SET_NO_NEW_PRIVS`. The flag can't be unset once set.
+    pub fn allow_set_no_new_privs(self) -> Prctl {
+        self.allow_option(libc::PR_SET_NO_NEW_PRIVS)
+    }
+
+    /// Allow getting the `no_new_privs` flag with `PR_GET_NO_NEW_PRIVS`.
+    pub fn allow_get_no_new_privs(self) -> Prctl {
+        self.allow_option(libc::PR_GET_NO_NEW_PRIVS)
+    }
+
+    /// Allow getting the dumpable flag with `PR_GET_DUMPABLE`.
+    pub fn allow_get_dumpable(self) -> Prctl {
+        self.allow_option(libc::PR_GET_DUMPABLE)
+    }
+
+    /// Allow getting the seccomp mode with `PR_GET_SECCOMP`, which is used by
+    /// [`is_seccomp_active`](crate::is_seccomp_active).
+    pub fn allow_get_seccomp(self) -> Prctl {
+        self.allow_option(libc::PR_GET_SECCOMP)
+    }
+
+    /// Allow setting the dumpable flag with `PR_SET_DUMPABLE`.
+    ///
+    /// # Security considerations
+    /// A dumpable process can be attached to with `ptrace` and have its memory read through
+    /// `/proc/<pid>/mem` by other processes running as the same user.
+    pub fn allow_set_dumpable(self) -> YesReally<Prctl> {
+        YesReally::new(self.allow_option(libc::PR_SET_DUMPABLE))
+    }
+
+    /// Allow installing seccomp filters or enabling strict mode with `PR_SET_SECCOMP`.
+    ///
+    /// # Security considerations
+    /// New filters can only restrict syscalls further, but a filter returning
+    /// `SECCOMP_RET_TRACE` or `SECCOMP_RET_USER_NOTIF` hands control over syscalls to another
+    /// process, and a filter that denies syscalls can make other code take unexpected fallback
+    /// paths.
+    pub fn allow_set_seccomp(self) -> YesReally<Prctl> {
+        YesReally::new(self.allow_option(libc::PR_SET_SECCOMP))
+    }
+
+    /// Allow `prctl` when its first argument is `option`, unless it is already allowed.
+    fn allow_option(mut self, option: libc::c_int) -> Prctl {
+        // The option is an int, so only compare the lower 32 bits.
+        #[allow(clippy::cast_sign_loss)]
+        let option = option as u32;
+        let rule = SeccompRule::new(Sysno::prctl)
+            .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Eq, option));
+
+        let rules = self.custom.entry(Sysno::prctl)
+            .or_insert_with(Vec::new);
+        if !rules.contains(&rule) {
+            rules.push(rule);
+        }
+
+        self
+    }
+}
+
+impl RuleSet for Prctl {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "Prctl"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows individual prctl options"
+    }
+}
@@ -0,0 +1,72 @@
+//! Contains a [`RuleSet`] for allowing supervisors to reap child processes and exit.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+/// A [`RuleSet`] for waiting on child processes and exiting, e.g. for supervisors that reap
+/// their children.
+///
+/// Note that `exit` and `exit_group` are part of
+/// [`BasicCapabilities`](super::BasicCapabilities), which is always enabled when a
+/// `SafetyContext` is applied, so they are allowed even without
+/// [`allow_exit`](Self::allow_exit) unless they have been denied with
+/// [`SafetyContext::deny`](crate::SafetyContext::deny). The wait syscalls are also allowed by
+/// [`ForkAndExec`](super::danger_zone::ForkAndExec).
+#[must_use]
+pub struct ProcessControl {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl ProcessControl {
+    /// Create a new [`ProcessControl`] ruleset with nothing allowed by default.
+    pub fn nothing() -> ProcessControl {
+        ProcessControl {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow waiting for child processes to exit.
+    pub fn reaping() -> ProcessControl {
+        ProcessControl::nothing()
+            .allow_wait()
+    }
+
+    /// Allow waiting for child processes and exiting.
+    pub fn everything() -> ProcessControl {
+        ProcessControl::nothing()
+            .allow_wait()
+            .allow_exit()
+    }
+
+    /// Allow waiting for child processes to change state with `wait4` and `waitid`.
+    pub fn allow_wait(mut self) -> ProcessControl {
+        self.allowed.extend([Sysno::wait4, Sysno::waitid]);
+
+        self
+    }
+
+    /// Allow exiting the current thread or process with `exit` and `exit_group`.
+    pub fn allow_exit(mut self) -> ProcessControl {
+        self.allowed.extend([Sysno::exit, Sysno::exit_group]);
+
+        self
+    }
+}
+
+impl RuleSet for ProcessControl {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "ProcessControl"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows waiting for child processes and exiting"
+    }
+}
@@ -0,0 +1,65 @@
+//! Contains a [`RuleSet`] for allowing read-only process information syscalls.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+#[must_use]
+/// Enable read-only syscalls that return information about the current process and system, like
+/// `getppid` and `getrusage`.
+///
+/// Note that `getpid` and `gettid` are currently also allowed by
+/// [`BasicCapabilities`](super::BasicCapabilities).
+pub struct ProcessInfo {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl ProcessInfo {
+    /// Create a new ProcessInfo [`RuleSet`] with nothing allowed by default.
+    pub fn nothing() -> ProcessInfo {
+        ProcessInfo {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow all process information syscalls: `getpid`, `getppid`, `gettid`, `getrusage`,
+    /// `sysinfo`, `getcpu`, and `times`.
+    pub fn everything() -> ProcessInfo {
+        ProcessInfo::nothing()
+            .allow_ids()
+            .allow_usage()
+    }
+
+    /// Allow getting the ids of the current process, its parent, and the current thread: `getpid`,
+    /// `getppid`, and `gettid`.
+    pub fn allow_ids(mut self) -> ProcessInfo {
+        self.allowed.extend([Sysno::getpid, Sysno::getppid, Sysno::gettid]);
+
+        self
+    }
+
+    /// Allow getting resource usage and system statistics: `getrusage`, `sysinfo`, `getcpu`, and
+    /// `times`.
+    pub fn allow_usage(mut self) -> ProcessInfo {
+        self.allowed.extend([Sysno::getrusage, Sysno::sysinfo, Sysno::getcpu, Sysno::times]);
+
+        self
+    }
+}
+
+impl RuleSet for ProcessInfo {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "ProcessInfo"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows reading process ids and resource usage"
+    }
+}
@@ -0,0 +1,52 @@
+//! Contains a [`RuleSet`] for allowing the `getrandom` syscall.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+#[must_use]
+/// Enable syscalls for getting random bytes from the kernel.
+///
+/// `getrandom` is currently also allowed by [`BasicCapabilities`](super::BasicCapabilities), so
+/// enabling this is not strictly necessary, but it makes it explicit that your program depends on
+/// it, e.g. via `rand` or a TLS library.
+///
+/// Reading from `/dev/urandom` instead requires opening and reading the file, e.g. with
+/// [`SystemIO::allow_open_readonly`](super::SystemIO::allow_open_readonly) and
+/// [`SystemIO::allow_read`](super::SystemIO::allow_read).
+pub struct Randomness {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Randomness {
+    /// Create a new Randomness [`RuleSet`] with nothing allowed by default.
+    pub fn nothing() -> Randomness {
+        Randomness {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow the `getrandom` syscall.
+    pub fn allow_getrandom(mut self) -> Randomness {
+        self.allowed.extend([Sysno::getrandom]);
+
+        self
+    }
+}
+
+impl RuleSet for Randomness {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Randomness"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows reading random numbers from the kernel"
+    }
+}
@@ -0,0 +1,88 @@
+//! Contains a [`RuleSet`] for allowing getting and setting resource limits.
+
+use std::collections::{HashMap, HashSet};
+
+use syscalls::Sysno;
+
+use crate::{SeccompArgumentFilter, SeccompilerComparator, SeccompRule, RuleSet};
+
+use super::YesReally;
+
+/// A [`RuleSet`] for getting and setting the process's resource limits, e.g. the maximum number
+/// of open files.
+///
+/// libc implements both `getrlimit` and `setrlimit` with `prlimit64`, which takes both the new
+/// and the old limit, so [`allow_get`](Self::allow_get) only allows `prlimit64` with a null new
+/// limit. Because of this conditional rule, it can't be enabled at the same time as other
+/// rulesets that allow `prlimit64` unconditionally.
+#[must_use]
+pub struct Rlimit {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+    /// Syscalls that are allowed with custom rules, i.e. `prlimit64` only for queries
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl Rlimit {
+    /// Create a new [`Rlimit`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Rlimit {
+        Rlimit {
+            allowed: HashSet::new(),
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow reading the resource limits of the current process with `getrlimit`, and with
+    /// `prlimit64` if the pid is 0, i.e. the current process, and the new limit is null.
+    ///
+    /// Only the pid 0 is allowed, so querying the current process by its actual pid fails, as
+    /// do queries of other processes. If [`allow_set`](Self::allow_set) was already called, this
+    /// does nothing.
+    pub fn allow_get(mut self) -> Rlimit {
+        if self.allowed.contains(&Sysno::prlimit64) {
+            return self;
+        }
+
+        self.allowed.extend([Sysno::getrlimit]);
+
+        let rule = SeccompRule::new(Sysno::prlimit64)
+            .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Eq, 0))
+            .and_condition(seccomp_arg_filter!(arg2 == 0));
+        let _old = self.custom.insert(Sysno::prlimit64, vec![rule]);
+
+        self
+    }
+
+    /// Allow getting and setting resource limits with `getrlimit`, `setrlimit` and `prlimit64`.
+    /// This replaces the conditional rule added by [`allow_get`](Self::allow_get).
+    ///
+    /// # Security considerations
+    /// Raising limits, e.g. on memory or the number of processes, can let the process use more
+    /// of the system's resources than intended, up to the hard limit or without bound with
+    /// `CAP_SYS_RESOURCE`. `prlimit64` is allowed unconditionally, so it can also change the
+    /// limits of other processes running as the same user.
+    pub fn allow_set(mut self) -> YesReally<Rlimit> {
+        let _removed = self.custom.remove(&Sysno::prlimit64);
+        self.allowed.extend([Sysno::getrlimit, Sysno::setrlimit, Sysno::prlimit64]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for Rlimit {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "Rlimit"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows getting and setting resource limits"
+    }
+}
@@ -0,0 +1,80 @@
+//! Contains a [`RuleSet`] for allowing the syscalls the Rust standard library needs to keep
+//! running.
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+/// A [`RuleSet`] allowing the syscalls that the Rust runtime, i.e. the standard library and the
+/// system allocator, uses behind your back. Enable this plus whatever your application needs.
+///
+/// The syscalls included are:
+/// - `brk`, `mmap`, `munmap`, `mremap`, and `madvise` for the allocator. glibc's `malloc` uses
+///   `mremap` to grow large allocations, and `madvise` to return freed memory to the kernel.
+/// - `mprotect`, which glibc uses to set up guard pages and grow heaps in other threads.
+/// - `futex` for `Mutex`, `Condvar`, `Once`, and parking threads, as well as `sched_yield` for
+///   [`std::thread::yield_now`] and spin loops.
+/// - `sigaltstack`, `rt_sigaction`, `rt_sigprocmask`, and `rt_sigreturn`, which Rust uses to
+///   install a handler that reports stack overflows instead of segfaulting.
+/// - `getrandom`, which seeds the hashers of `HashMap` and `HashSet`.
+/// - `rseq`, which glibc 2.35 and newer uses to register restartable sequences for each thread
+///   during thread setup. If it is denied with an errno, glibc silently continues without them,
+///   but with [`kill_process_on_violation`](crate::SafetyContext::kill_process_on_violation),
+///   starting a thread would kill the process.
+/// - `exit` and `exit_group` for exiting threads and the process.
+///
+/// Most of these are also in [`BasicCapabilities`](super::BasicCapabilities), which is always
+/// enabled, but listing them here makes the dependency explicit and keeps it working if a syscall
+/// is removed from `BasicCapabilities` or disabled with
+/// [`SafetyContext::deny`](crate::SafetyContext::deny).
+///
+/// Writing panic messages to stderr is not included, since it would conflict with rulesets that
+/// allow `write` unconditionally. Enable [`SystemIO::allow_stderr`](super::SystemIO::allow_stderr)
+/// if you want to see them. Creating threads is not included either; see
+/// [`Threads`](super::danger_zone::Threads).
+///
+/// Since all of these are simple rules, enabling this together with a ruleset that restricts one
+/// of them, e.g. [`Memory::allow_mmap`](super::Memory::allow_mmap), is an error. Use
+/// [`SafetyContext::enable_with_precedence`](crate::SafetyContext::enable_with_precedence) with
+/// [`Precedence::Conditional`](crate::Precedence::Conditional) to keep the restriction.
+pub struct RustRuntime;
+impl RuleSet for RustRuntime {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        vec![
+            // Allocator
+            Sysno::brk,
+            Sysno::mmap,
+            Sysno::munmap,
+            Sysno::mremap,
+            Sysno::madvise,
+            Sysno::mprotect,
+
+            // Synchronization and parking
+            Sysno::futex,
+            Sysno::sched_yield,
+
+            // Stack overflow handler
+            Sysno::sigaltstack,
+            Sysno::rt_sigaction,
+            Sysno::rt_sigprocmask,
+            Sysno::rt_sigreturn,
+
+            // HashMap seeds
+            Sysno::getrandom,
+
+            // Thread setup in newer glibc
+            Sysno::rseq,
+
+            Sysno::exit,
+            Sysno::exit_group,
+        ]
+    }
+
+    fn name(&self) -> &'static str {
+        "RustRuntime"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows the syscalls the Rust standard library and allocator need at runtime"
+    }
+}
@@ -0,0 +1,73 @@
+//! Contains a [`RuleSet`] for allowing scheduling-related syscalls.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+use super::YesReally;
+
+#[must_use]
+/// Enable syscalls related to process and thread scheduling.
+///
+/// Note that `sched_getaffinity`, `sched_setaffinity`, and `sched_yield` are currently also allowed
+/// by [`BasicCapabilities`](super::BasicCapabilities).
+pub struct Scheduling {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Scheduling {
+    /// Create a new Scheduling [`RuleSet`] with nothing allowed by default.
+    pub fn nothing() -> Scheduling {
+        Scheduling {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow querying scheduling parameters and yielding: `sched_getparam`, `sched_getscheduler`,
+    /// `sched_get_priority_max`, `sched_get_priority_min`, `sched_getaffinity`, and `sched_yield`.
+    pub fn allow_query(mut self) -> Scheduling {
+        self.allowed.extend([
+            Sysno::sched_getparam, Sysno::sched_getscheduler,
+            Sysno::sched_get_priority_max, Sysno::sched_get_priority_min,
+            Sysno::sched_getaffinity,
+            Sysno::sched_yield,
+        ]);
+
+        self
+    }
+
+    /// Allow changing scheduling parameters: `sched_setscheduler`, `sched_setparam`, and
+    /// `sched_setaffinity`.
+    ///
+    /// # Security considerations
+    ///
+    /// With `CAP_SYS_NICE` or a sufficient `RLIMIT_RTPRIO`, a process can set a realtime
+    /// scheduling policy for itself, which can starve other processes on the system of CPU time.
+    /// These syscalls also apply to any process you have permission to change, not just the
+    /// current one.
+    pub fn allow_modify(mut self) -> YesReally<Scheduling> {
+        self.allowed.extend([
+            Sysno::sched_setscheduler, Sysno::sched_setparam,
+            Sysno::sched_setaffinity,
+        ]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for Scheduling {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Scheduling"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows querying and modifying scheduler settings"
+    }
+}
@@ -0,0 +1,87 @@
+//! Contains a [`RuleSet`] for allowing System V shared memory and POSIX message queues.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+use super::YesReally;
+
+/// Syscalls for using POSIX message queues that are already open.
+const MQ_IO_SYSCALLS: &[Sysno] = &[Sysno::mq_timedsend, Sysno::mq_timedreceive, Sysno::mq_getsetattr, Sysno::mq_notify];
+
+/// Enable syscalls for System V shared memory and POSIX message queues, which are used to share
+/// memory and pass messages between processes.
+#[must_use]
+pub struct SharedMemory {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl SharedMemory {
+    /// Create a new [`SharedMemory`] ruleset with nothing allowed by default.
+    pub fn nothing() -> SharedMemory {
+        SharedMemory {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow detaching System V shared memory segments that were attached before the
+    /// `SafetyContext` was applied, with `shmdt`.
+    pub fn allow_sysv_shm_detach(mut self) -> SharedMemory {
+        self.allowed.extend([Sysno::shmdt]);
+
+        self
+    }
+
+    /// Allow creating, attaching, detaching, and controlling System V shared memory segments with
+    /// `shmget`, `shmat`, `shmdt`, and `shmctl`.
+    ///
+    /// # Security considerations
+    /// System V shared memory segments are identified by global keys, so any segment that the
+    /// process has permissions for can be attached, including ones created by other processes.
+    /// This can be used to cross isolation boundaries between processes, and `shmctl` can remove
+    /// segments used by other processes.
+    pub fn allow_sysv_shm(mut self) -> YesReally<SharedMemory> {
+        self.allowed.extend([Sysno::shmget, Sysno::shmat, Sysno::shmdt, Sysno::shmctl]);
+
+        YesReally::new(self)
+    }
+
+    /// Allow sending and receiving messages on POSIX message queues that were opened before the
+    /// `SafetyContext` was applied, and querying or changing their attributes, with
+    /// `mq_timedsend`, `mq_timedreceive`, `mq_getsetattr`, and `mq_notify`.
+    pub fn allow_posix_mq_io(mut self) -> SharedMemory {
+        self.allowed.extend(MQ_IO_SYSCALLS);
+
+        self
+    }
+
+    /// Allow opening and removing POSIX message queues with `mq_open` and `mq_unlink`, as well as
+    /// the syscalls in [`allow_posix_mq_io`](Self::allow_posix_mq_io).
+    ///
+    /// # Security considerations
+    /// Message queues are identified by global names, so any queue that the process has
+    /// permissions for can be opened, including ones created by other processes. This can be
+    /// used to cross isolation boundaries between processes.
+    pub fn allow_posix_mq(mut self) -> YesReally<SharedMemory> {
+        self.allowed.extend([Sysno::mq_open, Sysno::mq_unlink]);
+
+        YesReally::new(self.allow_posix_mq_io())
+    }
+}
+
+impl RuleSet for SharedMemory {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "SharedMemory"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows System V shared memory and POSIX message queues"
+    }
+}
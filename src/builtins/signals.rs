@@ -0,0 +1,90 @@
+//! Contains a [`RuleSet`] for allowing signal handling syscalls.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+/// A [`RuleSet`] for installing and managing signal handlers, e.g. `rt_sigaction` and
+/// `sigaltstack`.
+///
+/// Note that this only covers *handling* signals. Sending signals to other threads or processes
+/// is not included.
+///
+/// [`BasicCapabilities`](super::BasicCapabilities) already allows most of these because the Rust
+/// runtime uses them to install its stack overflow handler, so you mostly need this ruleset if
+/// you are writing your own rulesets from scratch, or for `rt_sigpending`.
+#[must_use]
+pub struct Signals {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Signals {
+    /// Create a new [`Signals`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Signals {
+        Signals {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow the syscalls needed to install and restore signal handlers: `rt_sigaction`,
+    /// `rt_sigprocmask`, `rt_sigreturn`, and `sigaltstack`.
+    pub fn handlers() -> Signals {
+        Signals::nothing()
+            .allow_sigaction()
+            .allow_sigprocmask()
+            .allow_sigreturn()
+            .allow_sigaltstack()
+    }
+
+    /// Allow `rt_sigaction`, which installs signal handlers.
+    pub fn allow_sigaction(mut self) -> Signals {
+        self.allowed.extend([Sysno::rt_sigaction]);
+
+        self
+    }
+
+    /// Allow `rt_sigprocmask`, which blocks and unblocks signals.
+    pub fn allow_sigprocmask(mut self) -> Signals {
+        self.allowed.extend([Sysno::rt_sigprocmask]);
+
+        self
+    }
+
+    /// Allow `sigaltstack`, which sets an alternate stack for signal handlers to run on.
+    pub fn allow_sigaltstack(mut self) -> Signals {
+        self.allowed.extend([Sysno::sigaltstack]);
+
+        self
+    }
+
+    /// Allow `rt_sigreturn`, which is called when returning from a signal handler.
+    pub fn allow_sigreturn(mut self) -> Signals {
+        self.allowed.extend([Sysno::rt_sigreturn]);
+
+        self
+    }
+
+    /// Allow `rt_sigpending`, which checks which signals are pending.
+    pub fn allow_rt_sigpending(mut self) -> Signals {
+        self.allowed.extend([Sysno::rt_sigpending]);
+
+        self
+    }
+}
+
+impl RuleSet for Signals {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Signals"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows installing signal handlers and masking signals"
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::collections::{HashSet, HashMap};
 use std::fs::File;
+use std::ops::RangeInclusive;
 use std::os::unix::io::AsRawFd;
 
 #[cfg(feature = "landlock")]
@@ -14,21 +15,44 @@ use crate::LandlockRule;
 #[cfg(feature = "landlock")]
 use crate::landlock::{access, AccessFs, BitFlags};
 
-use crate::{RuleSet, SeccompRule};
+use crate::{RuleSet, SeccompArgumentFilter, SeccompilerComparator, SeccompRule};
 use super::YesReally;
 
 pub(crate) const IO_READ_SYSCALLS: &[Sysno] = &[Sysno::read, Sysno::readv, Sysno::preadv, Sysno::preadv2, Sysno::pread64, Sysno::lseek];
 pub(crate) const IO_WRITE_SYSCALLS: &[Sysno] = &[Sysno::write, Sysno::writev, Sysno::pwritev, Sysno::pwritev2, Sysno::pwrite64,
                                       Sysno::fsync, Sysno::fdatasync, Sysno::lseek];
-pub(crate) const IO_OPEN_SYSCALLS: &[Sysno] = &[Sysno::open, Sysno::openat, Sysno::openat2];
+// The legacy syscalls without an `at` suffix, like `open` and `stat`, only exist on x86_64. Other
+// architectures like aarch64 only have the `*at` variants, which libc uses instead.
+pub(crate) const IO_OPEN_SYSCALLS: &[Sysno] = &[
+    #[cfg(target_arch = "x86_64")] Sysno::open,
+    Sysno::openat, Sysno::openat2];
 pub(crate) const IO_IOCTL_SYSCALLS: &[Sysno] = &[Sysno::ioctl, Sysno::fcntl];
 // TODO: may want to separate fd-based and filename-based?
-pub(crate) const IO_METADATA_SYSCALLS: &[Sysno] = &[Sysno::stat, Sysno::fstat, Sysno::newfstatat,
-                                         Sysno::lstat, Sysno::statx,
-                                         Sysno::getdents, Sysno::getdents64,
-                                         Sysno::getcwd];
+pub(crate) const IO_METADATA_SYSCALLS: &[Sysno] = &[
+    #[cfg(target_arch = "x86_64")] Sysno::stat,
+    #[cfg(target_arch = "x86_64")] Sysno::lstat,
+    #[cfg(target_arch = "x86_64")] Sysno::newfstatat,
+    #[cfg(target_arch = "aarch64")] Sysno::fstatat,
+    Sysno::fstat, Sysno::statx,
+    #[cfg(target_arch = "x86_64")] Sysno::getdents,
+    Sysno::getdents64,
+    Sysno::getcwd];
+pub(crate) const IO_FS_METADATA_SYSCALLS: &[Sysno] = &[Sysno::statfs, Sysno::fstatfs];
 pub(crate) const IO_CLOSE_SYSCALLS: &[Sysno] = &[Sysno::close, Sysno::close_range];
-pub(crate) const IO_UNLINK_SYSCALLS: &[Sysno] = &[Sysno::unlink, Sysno::unlinkat];
+pub(crate) const IO_UNLINK_SYSCALLS: &[Sysno] = &[#[cfg(target_arch = "x86_64")] Sysno::unlink, Sysno::unlinkat];
+pub(crate) const IO_MKDIR_SYSCALLS: &[Sysno] = &[
+    #[cfg(target_arch = "x86_64")] Sysno::mkdir,
+    Sysno::mkdirat,
+    #[cfg(target_arch = "x86_64")] Sysno::rmdir];
+pub(crate) const IO_RENAME_SYSCALLS: &[Sysno] = &[
+    #[cfg(target_arch = "x86_64")] Sysno::rename,
+    Sysno::renameat, Sysno::renameat2];
+pub(crate) const IO_READLINK_SYSCALLS: &[Sysno] = &[
+    #[cfg(target_arch = "x86_64")] Sysno::readlink,
+    Sysno::readlinkat];
+pub(crate) const IO_SYMLINK_SYSCALLS: &[Sysno] = &[
+    #[cfg(target_arch = "x86_64")] Sysno::symlink,
+    Sysno::symlinkat];
 
 // TODO: split into SystemIO, SystemIOLandlock, SystemIOSeccompRestricted so that you can't call a
 // landlock function after using a seccomp argument filter function (or vice versa). You can still
@@ -71,14 +95,37 @@ impl SystemIO {
             .allow_close()
     }
 
-    /// Allow `read` syscalls.
+    /// Allow reading from stdin and writing to stdout and stderr, and nothing else. Like
+    /// [`allow_stdin`](Self::allow_stdin), [`allow_stdout`](Self::allow_stdout) and
+    /// [`allow_stderr`](Self::allow_stderr), these are conditional rules on the fd, so `read` is
+    /// only allowed on fd 0 and `write` only on fds 1 and 2.
+    pub fn stdio() -> SystemIO {
+        SystemIO::nothing()
+            .allow_stdin()
+            .allow_stdout()
+            .allow_stderr()
+    }
+
+    /// Allow `read` syscalls, including the vectored and positional variants `readv`, `pread64`,
+    /// `preadv` and `preadv2`, and `lseek`.
+    ///
+    /// Seeking is allowed by both this and [`allow_write`](Self::allow_write), so `Seek` on a
+    /// `File` works with either. There is no `_llseek` on the supported 64-bit architectures.
+    ///
+    /// `preadv2` takes `RWF_*` flags, e.g. `RWF_NOWAIT`, which could be filtered on, but they are
+    /// allowed unconditionally here since they don't give access to anything else.
     pub fn allow_read(mut self) -> SystemIO {
         self.allowed.extend(IO_READ_SYSCALLS);
 
         self
     }
 
-    /// Allow `write` syscalls.
+    /// Allow `write` syscalls, including the vectored and positional variants `writev`,
+    /// `pwrite64`, `pwritev` and `pwritev2`, as well as `fsync`, `fdatasync` and `lseek`.
+    ///
+    /// As with `preadv2` in [`allow_read`](Self::allow_read), the `RWF_*` flags of `pwritev2`
+    /// are not filtered. Note that `RWF_APPEND` lets a write append to a file that was not
+    /// opened with `O_APPEND`.
     pub fn allow_write(mut self) -> SystemIO {
         self.allowed.extend(IO_WRITE_SYSCALLS);
 
@@ -92,6 +139,52 @@ impl SystemIO {
         self
     }
 
+    /// Allow `rename` syscalls.
+    pub fn allow_rename(mut self) -> SystemIO {
+        self.allowed.extend(IO_RENAME_SYSCALLS);
+
+        self
+    }
+
+    /// Allow creating and removing directories with `mkdir` and `rmdir`, as well as removing and
+    /// renaming files with the [`allow_unlink`](Self::allow_unlink) and
+    /// [`allow_rename`](Self::allow_rename) syscalls. Use those directly if you only need to
+    /// remove or rename files.
+    ///
+    /// When the `landlock` feature is used to restrict filesystem access, the corresponding
+    /// landlock rights are also needed on the affected directories: `MakeDir`, `RemoveDir`,
+    /// `RemoveFile`, and `Refer` to rename files between directories.
+    pub fn allow_directory_ops(mut self) -> SystemIO {
+        self.allowed.extend(IO_MKDIR_SYSCALLS);
+
+        self.allow_unlink()
+            .allow_rename()
+    }
+
+    /// Allow reading the targets of symlinks with `readlink` and `readlinkat`.
+    ///
+    /// These are also part of [`BasicCapabilities`](super::BasicCapabilities), since e.g. the
+    /// standard library reads `/proc/self/exe`, so this is mostly useful to document that a
+    /// program relies on them. Landlock does not restrict reading symlinks.
+    pub fn allow_symlink_ops(mut self) -> SystemIO {
+        self.allowed.extend(IO_READLINK_SYSCALLS);
+
+        self
+    }
+
+    /// Allow creating symlinks with `symlink` and `symlinkat`. This modifies the filesystem, so it
+    /// is separate from [`allow_symlink_ops`](Self::allow_symlink_ops).
+    ///
+    /// When the `landlock` feature is used to restrict filesystem access, the `MakeSym` landlock
+    /// right is also needed on the directory the symlink is created in. The target of a symlink
+    /// is not checked when it is created, but landlock checks the resolved path when the symlink
+    /// is followed, so a symlink can't be used to access files outside of the allowed paths.
+    pub fn allow_symlink_create(mut self) -> SystemIO {
+        self.allowed.extend(IO_SYMLINK_SYSCALLS);
+
+        self
+    }
+
     /// Allow `open` syscalls.
     ///
     /// # Security
@@ -107,6 +200,10 @@ impl SystemIO {
 
     /// Allow `open` syscalls but not with write flags.
     ///
+    /// This is enforced by seccomp on the flags argument, so it does not require landlock: opening
+    /// with `O_WRONLY`, `O_RDWR`, `O_APPEND`, `O_CREAT`, `O_EXCL`, or `O_TRUNC` fails. `O_TRUNC` is
+    /// included because Linux truncates the file even if it is opened with `O_RDONLY`.
+    ///
     /// Note that the `openat2` syscall (which is not exposed by glibc anyway according to the
     /// syscall manpage, and so probably isn't very common) is not supported here because it has a
     /// separate configuration struct instead of a flag bitset.
@@ -116,20 +213,24 @@ impl SystemIO {
         const O_APPEND: u64 = libc::O_APPEND as u64;
         const O_CREAT: u64 = libc::O_CREAT as u64;
         const O_EXCL: u64 = libc::O_EXCL as u64;
+        const O_TRUNC: u64 = libc::O_TRUNC as u64;
         // TMPFILE causes problems because it's defined as __O_TMPFILE | O_DIRECTORY
         // i.e. just the tmpfile bit or the o_directory bit. O_DIRECTORY by itself is fine because
         // it just causse the open to fail if it's a directory. however the manpage states that
         // WRONLY or RDWR is required for O_TMPFILE so we're fine to leave it out anyway.
         // const O_TMPFILE: u64 = libc::O_TMPFILE as u64;
 
-        const WRITECREATE: u64 = O_WRONLY | O_RDWR | O_APPEND | O_CREAT | O_EXCL;// | O_TMPFILE;
+        const WRITECREATE: u64 = O_WRONLY | O_RDWR | O_APPEND | O_CREAT | O_EXCL | O_TRUNC;// | O_TMPFILE;
 
         // flags are the second argument for open but the third for openat
-        let rule = SeccompRule::new(Sysno::open)
-            .and_condition(seccomp_arg_filter!(arg1 & WRITECREATE == 0));
-        self.custom.entry(Sysno::open)
-            .or_insert_with(Vec::new)
-            .push(rule);
+        #[cfg(target_arch = "x86_64")]
+        {
+            let rule = SeccompRule::new(Sysno::open)
+                .and_condition(seccomp_arg_filter!(arg1 & WRITECREATE == 0));
+            self.custom.entry(Sysno::open)
+                .or_insert_with(Vec::new)
+                .push(rule);
+        }
 
         let rule = SeccompRule::new(Sysno::openat)
             .and_condition(seccomp_arg_filter!(arg2 & WRITECREATE == 0));
@@ -141,19 +242,86 @@ impl SystemIO {
     }
 
     /// Allow `stat` syscalls.
+    ///
+    /// These return metadata about individual files, like their size and permissions. See
+    /// [`allow_filesystem_metadata`](Self::allow_filesystem_metadata) for metadata about the
+    /// filesystem a file is on. This includes `statx`, see
+    /// [`without_statx`](Self::without_statx) to allow the others without it.
     pub fn allow_metadata(mut self) -> SystemIO {
         self.allowed.extend(IO_METADATA_SYSCALLS);
 
         self
     }
 
-    /// Allow `ioctl` and `fcntl` syscalls.
+    /// Allow only `statx`, which returns extended metadata about a file, either by fd or by path.
+    /// Recent versions of glibc use it to implement `stat` and `fstat`.
+    pub fn allow_statx(mut self) -> SystemIO {
+        self.allowed.extend([Sysno::statx]);
+
+        self
+    }
+
+    /// Remove `statx` from the allowed syscalls, e.g. after [`allow_metadata`](Self::allow_metadata),
+    /// so that it takes the `SafetyContext`'s default action.
+    ///
+    /// Some programs behave better when `statx` fails with `ENOSYS`, in which case glibc and the
+    /// Rust standard library fall back to `fstat` and `newfstatat`. To get that, also use
+    /// [`SafetyContext::return_errno_for`](crate::SafetyContext::return_errno_for) with
+    /// `(Sysno::statx, libc::ENOSYS)`.
+    pub fn without_statx(mut self) -> SystemIO {
+        let _removed = self.allowed.remove(&Sysno::statx);
+
+        self
+    }
+
+    /// Allow `statfs` and `fstatfs`, which return metadata about a mounted filesystem, like its
+    /// type, free space, and mount flags, e.g. for checking free disk space.
+    ///
+    /// This does not allow per-file metadata like `stat`; use
+    /// [`allow_metadata`](Self::allow_metadata) for that, which also includes `statx`. Programs
+    /// that only stat files don't need this.
+    pub fn allow_filesystem_metadata(mut self) -> SystemIO {
+        self.allowed.extend(IO_FS_METADATA_SYSCALLS);
+
+        self
+    }
+
+    /// Allow `ioctl` and `fcntl` syscalls. This replaces any rules added with
+    /// [`allow_ioctl_requests`](Self::allow_ioctl_requests).
     pub fn allow_ioctl(mut self) -> SystemIO {
+        let _removed = self.custom.remove(&Sysno::ioctl);
         self.allowed.extend(IO_IOCTL_SYSCALLS);
 
         self
     }
 
+    /// Allow `ioctl` only with the given request numbers, e.g. `FIONREAD` or `FIONBIO`, on any fd.
+    ///
+    /// This adds one conditional rule on the second argument (`arg1`) per distinct request. Only
+    /// the lower 32 bits of each request are compared, since that's all the kernel uses. If
+    /// `ioctl` is already allowed unconditionally with [`allow_ioctl`](Self::allow_ioctl), nothing
+    /// is added, and like other conditional rules, this can't be enabled at the same time as
+    /// rulesets that allow `ioctl` unconditionally.
+    pub fn allow_ioctl_requests(mut self, requests: &[u64]) -> SystemIO {
+        if self.allowed.contains(&Sysno::ioctl) {
+            return self;
+        }
+
+        let rules = self.custom.entry(Sysno::ioctl)
+            .or_insert_with(Vec::new);
+        for &request in requests {
+            #[allow(clippy::cast_possible_truncation)]
+            let request = request as u32;
+            let rule = SeccompRule::new(Sysno::ioctl)
+                .and_condition(SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, request));
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+
+        self
+    }
+
     /// Allow `close` syscalls.
     pub fn allow_close(mut self) -> SystemIO {
         self.allowed.extend(IO_CLOSE_SYSCALLS);
@@ -240,6 +408,64 @@ impl SystemIO {
 
         self
     }
+
+    /// Allow reading from every fd in `fds`, e.g. `3..=5` for fds passed by systemd socket
+    /// activation, with the same syscalls as [`allow_file_read`](Self::allow_file_read) except
+    /// for the metadata syscalls. If the range is empty, nothing is allowed.
+    ///
+    /// # Security considerations
+    ///
+    /// Any file or socket that gets an fd in the range is allowed, including ones opened after the
+    /// original fds are closed.
+    ///
+    /// # Panics
+    /// Panics if the start of the range is negative.
+    pub fn allow_read_fds(mut self, fds: RangeInclusive<i32>) -> SystemIO {
+        for &syscall in IO_READ_SYSCALLS {
+            self.allow_fd_range(syscall, &fds);
+        }
+
+        self
+    }
+
+    /// Allow writing to every fd in `fds` with `write`, as with
+    /// [`allow_file_write`](Self::allow_file_write). If the range is empty, nothing is allowed.
+    ///
+    /// # Security considerations
+    ///
+    /// Any file or socket that gets an fd in the range is allowed, including ones opened after the
+    /// original fds are closed.
+    ///
+    /// # Panics
+    /// Panics if the start of the range is negative.
+    pub fn allow_write_fds(mut self, fds: RangeInclusive<i32>) -> SystemIO {
+        self.allow_fd_range(Sysno::write, &fds);
+
+        self
+    }
+
+    /// Add a rule allowing `syscall` if its first argument is in `fds`. A range with a single fd
+    /// is compared with `Eq`, and a larger one with `Ge` and `Le`.
+    fn allow_fd_range(&mut self, syscall: Sysno, fds: &RangeInclusive<i32>) {
+        if fds.is_empty() {
+            return;
+        }
+        let start = u32::try_from(*fds.start()).expect("provided fd was negative");
+        // The end is at least the start, so it is not negative either.
+        let end = u32::try_from(*fds.end()).expect("provided fd was negative");
+
+        let rule = if start == end {
+            SeccompRule::new(syscall)
+                .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Eq, start))
+        } else {
+            SeccompRule::new(syscall)
+                .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Ge, start))
+                .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Le, end))
+        };
+        self.custom.entry(syscall)
+            .or_insert_with(Vec::new)
+            .push(rule);
+    }
 }
 
 impl RuleSet for SystemIO {
@@ -259,6 +485,10 @@ impl RuleSet for SystemIO {
     fn name(&self) -> &'static str {
         "SystemIO"
     }
+
+    fn description(&self) -> &'static str {
+        "allows opening, reading, writing, and inspecting files"
+    }
 }
 
 // landlock impls for SystemIO
@@ -274,7 +504,8 @@ impl SystemIO {
 
     /// Use Landlock to allow only files within the specified directory, or the specific file, to
     /// be read. If this function is called multiple times, all directories and files passed will
-    /// be allowed.
+    /// be allowed. This does not allow executing the files, see
+    /// [`allow_execute_in_dir`](Self::allow_execute_in_dir).
     ///
     /// Note that if this is used with [`allow_open_readonly`] or other syscall-argument restricting
     /// methods, applying the `SafetyContext` will fail.
@@ -289,6 +520,29 @@ impl SystemIO {
             .allow_open().yes_really()
     }
 
+    /// Use Landlock to allow executing files within the specified directory, or the specific
+    /// file. If this function is called multiple times, all directories and files passed will be
+    /// allowed.
+    ///
+    /// Landlock checks the read right as well as the execute right when a file is executed, so
+    /// this also allows the files to be read. Conversely,
+    /// [`allow_read_path`](Self::allow_read_path) does not allow executing files.
+    ///
+    /// Note that this does not allow the `execve` syscall itself, which is in
+    /// [`ForkAndExec`](super::danger_zone::ForkAndExec). Dynamically linked programs also need
+    /// their interpreter (e.g. `/lib64/ld-linux-x86-64.so.2`) to be executable and their
+    /// libraries to be readable.
+    pub fn allow_execute_in_dir<P: AsRef<Path>>(mut self, path: P) -> SystemIO {
+        let new_flags = access::read_path() | access::execute();
+        self.insert_flags(path, new_flags);
+
+        // allow relevant syscalls as well
+        self.allow_close()
+            .allow_read()
+            .allow_metadata()
+            .allow_open().yes_really()
+    }
+
     /// Use Landlock to allow only the specified file to be written to. If this function is called
     /// multiple times, all files passed will be allowed.
     ///
@@ -317,6 +571,7 @@ impl SystemIO {
         self.insert_flags(path, new_flags);
 
         // allow relevant syscalls as well
+        #[cfg(target_arch = "x86_64")]
         self.allowed.extend(&[Sysno::creat]);
         self.allow_open().yes_really()
     }
@@ -341,7 +596,9 @@ impl SystemIO {
         self.insert_flags(path, new_flags);
 
         // allow relevant syscalls as well
-        self.allowed.extend(&[Sysno::mkdir, Sysno::mkdirat]);
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend(&[Sysno::mkdir]);
+        self.allowed.extend(&[Sysno::mkdirat]);
         self
     }
 
@@ -352,7 +609,9 @@ impl SystemIO {
         self.insert_flags(path, new_flags);
 
         // allow relevant syscalls as well
-        self.allowed.extend(&[Sysno::unlink, Sysno::unlinkat]);
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend(&[Sysno::unlink]);
+        self.allowed.extend(&[Sysno::unlinkat]);
         self
     }
 
@@ -371,7 +630,9 @@ impl SystemIO {
         // allow relevant syscalls as well
         // unlinkat may be be used to remove directories as well so we include it here, since files
         // will be protected by landlock anyway.
-        self.allowed.extend(&[Sysno::rmdir, Sysno::unlinkat]);
+        #[cfg(target_arch = "x86_64")]
+        self.allowed.extend(&[Sysno::rmdir]);
+        self.allowed.extend(&[Sysno::unlinkat]);
         self
     }
 }
@@ -403,6 +664,20 @@ impl SystemIO {
             .allow_open().yes_really()
     }
 
+    /// Use Landlock to allow access to SSL certificates like
+    /// [`allow_ssl_files`](Self::allow_ssl_files), and additionally to each of `extra_paths`,
+    /// e.g. a CA bundle in a location specific to your distribution. Directories can be listed
+    /// and the files within them read, as with the default paths.
+    pub fn allow_ssl_files_with<P: AsRef<Path>>(self, extra_paths: &[P]) -> SystemIO {
+        let mut ssl = self.allow_ssl_files();
+        let new_flags = access::read_path() | access::list_dir();
+        for path in extra_paths {
+            ssl.insert_flags(path, new_flags);
+        }
+
+        ssl
+    }
+
     /// Use Landlock to allow access to DNS files, like /etc/resolv.conf
     pub fn allow_dns_files(mut self) -> SystemIO {
         let new_flags = access::read_path();
@@ -416,4 +691,26 @@ impl SystemIO {
             .allow_metadata()
             .allow_open().yes_really()
     }
+
+    /// Use Landlock to allow reading the process's own files in `/proc`, e.g. `/proc/self/stat`
+    /// or `/proc/self/maps`, as profilers and allocators do, and allow opening and reading files.
+    ///
+    /// `/proc/self` is a symlink that is resolved when the `SafetyContext` is applied, so only
+    /// the `/proc/<pid>` directory of the process that applied it is allowed, including the
+    /// `task` directories of its threads. Other processes' directories, including those of
+    /// children forked afterwards, are not. Reading the `/proc/self/exe` symlink with
+    /// `readlink` is not restricted by Landlock and is always allowed by
+    /// [`BasicCapabilities`](super::BasicCapabilities).
+    ///
+    /// Without the `landlock` feature, this function is not available, and the files can only be
+    /// read by allowing [`allow_open_readonly`](Self::allow_open_readonly) and
+    /// [`allow_read`](Self::allow_read) for all paths.
+    pub fn allow_proc_self(mut self) -> SystemIO {
+        self.insert_flags("/proc/self", access::read_path() | access::list_dir());
+
+        self.allow_close()
+            .allow_read()
+            .allow_metadata()
+            .allow_open().yes_really()
+    }
 }
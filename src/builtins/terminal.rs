@@ -0,0 +1,92 @@
+//! Contains a [`RuleSet`] for allowing terminal control `ioctl`s, e.g. for putting a terminal into
+//! raw mode.
+
+use std::collections::HashMap;
+
+use syscalls::Sysno;
+
+use crate::{SeccompArgumentFilter, SeccompilerComparator, SeccompRule, RuleSet};
+
+/// `ioctl` requests for getting and setting terminal attributes, as used by `tcgetattr` and
+/// `tcsetattr`.
+const RAW_MODE_REQUESTS: &[libc::Ioctl] = &[libc::TCGETS, libc::TCSETS, libc::TCSETSW, libc::TCSETSF];
+
+/// `ioctl` requests for getting the terminal window size.
+const WINDOW_SIZE_REQUESTS: &[libc::Ioctl] = &[libc::TIOCGWINSZ];
+
+/// A [`RuleSet`] for controlling terminals with specific `ioctl` requests, rather than allowing
+/// `ioctl` entirely with [`SystemIO::allow_ioctl`](super::SystemIO::allow_ioctl), which it
+/// therefore can't be combined with (see [`SafetyContext::enable`](crate::SafetyContext::enable)).
+#[must_use]
+pub struct Terminal {
+    /// Syscalls that are allowed with custom rules, i.e. only specific `ioctl` requests
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
+}
+
+impl Terminal {
+    /// Create a new [`Terminal`] ruleset with nothing allowed by default.
+    pub fn nothing() -> Terminal {
+        Terminal {
+            custom: HashMap::new(),
+        }
+    }
+
+    /// Allow getting and setting terminal attributes, e.g. to put the terminal into raw mode.
+    pub fn raw_mode() -> Terminal {
+        Terminal::nothing()
+            .allow_raw_mode()
+    }
+
+    /// Allow getting the terminal window size.
+    pub fn window_size() -> Terminal {
+        Terminal::nothing()
+            .allow_window_size()
+    }
+
+    /// Add a rule allowing `ioctl` with each of the given requests, on any fd.
+    fn allow_requests(&mut self, requests: &[libc::Ioctl]) {
+        let rules = self.custom.entry(Sysno::ioctl)
+            .or_insert_with(Vec::new);
+        for &request in requests {
+            // The kernel only uses the lower 32 bits of the request.
+            #[allow(clippy::cast_possible_truncation)]
+            let request = request as u32;
+            let rule = SeccompRule::new(Sysno::ioctl)
+                .and_condition(SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, request));
+            rules.push(rule);
+        }
+    }
+
+    /// Allow `ioctl` with `TCGETS`, `TCSETS`, `TCSETSW`, and `TCSETSF`, which are used by
+    /// `tcgetattr` and `tcsetattr` to get and set terminal attributes.
+    pub fn allow_raw_mode(mut self) -> Terminal {
+        self.allow_requests(RAW_MODE_REQUESTS);
+
+        self
+    }
+
+    /// Allow `ioctl` with `TIOCGWINSZ`, to get the terminal window size.
+    pub fn allow_window_size(mut self) -> Terminal {
+        self.allow_requests(WINDOW_SIZE_REQUESTS);
+
+        self
+    }
+}
+
+impl RuleSet for Terminal {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
+    fn name(&self) -> &'static str {
+        "Terminal"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows terminal control ioctls, e.g. for raw mode and window sizes"
+    }
+}
@@ -1,17 +1,23 @@
 //! Contains a [`RuleSet`] for allowing time-related syscalls, but check the comments for why you
 //! probably don't actually need to enable them.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use syscalls::Sysno;
 
-use crate::RuleSet;
+use crate::{RuleSet, SeccompArgumentFilter, SeccompilerComparator, SeccompRule};
+
+/// From `<linux/time.h>`. `clockid_t` is an `int`, so only the lower 32 bits are compared.
+#[allow(clippy::cast_sign_loss)]
+const CLOCK_REALTIME: u32 = libc::CLOCK_REALTIME as u32;
 
 #[must_use]
 /// Enable syscalls related to time.
 pub struct Time {
     /// Syscalls that are allowed
     allowed: HashSet<Sysno>,
+    /// Syscalls that are allowed with custom rules, i.e. setting clocks other than the system clock
+    custom: HashMap<Sysno, Vec<SeccompRule>>,
 }
 
 impl Time {
@@ -19,6 +25,7 @@ impl Time {
     pub fn nothing() -> Time {
         Time {
             allowed: HashSet::new(),
+            custom: HashMap::new(),
         }
     }
 
@@ -32,6 +39,44 @@ impl Time {
 
         self
     }
+
+    /// Allow creating and using POSIX interval timers with `timer_create`, `timer_settime`,
+    /// `timer_gettime`, `timer_getoverrun`, and `timer_delete`.
+    ///
+    /// By default, a timer delivers `SIGALRM` to the process when it expires, which terminates
+    /// the process unless the signal is handled or ignored. Installing a handler requires
+    /// [`Signals`](super::Signals) if it happens after the `SafetyContext` is applied. Timers
+    /// created with `SIGEV_THREAD` by glibc's `timer_create` also need to create a thread, which
+    /// requires [`Threads`](super::danger_zone::Threads).
+    pub fn allow_posix_timers(mut self) -> Time {
+        self.allowed
+            .extend([Sysno::timer_create, Sysno::timer_settime, Sysno::timer_gettime,
+                Sysno::timer_getoverrun, Sysno::timer_delete]);
+
+        self
+    }
+
+    /// Allow setting and adjusting clocks with `clock_settime` and `clock_adjtime`, except for
+    /// the system-wide clock `CLOCK_REALTIME` (0), by filtering on the clock id in the first
+    /// argument.
+    ///
+    /// Changing `CLOCK_REALTIME` affects every process on the system, e.g. certificate and token
+    /// expiry checks, log timestamps and `make`. The other static clocks, like
+    /// `CLOCK_MONOTONIC` (1), `CLOCK_PROCESS_CPUTIME_ID` (2), `CLOCK_THREAD_CPUTIME_ID` (3),
+    /// `CLOCK_BOOTTIME` (7) and `CLOCK_TAI` (11), can't be set at all and fail with `EINVAL`. In
+    /// practice, this allows setting and adjusting dynamic clocks, i.e. PTP hardware clocks
+    /// whose negative clock id is derived from an open `/dev/ptpN` fd, as done by `phc2sys`.
+    ///
+    /// `settimeofday` and `adjtimex` always operate on `CLOCK_REALTIME`, so they are not allowed.
+    pub fn allow_set_non_realtime_clocks(mut self) -> Time {
+        for syscall in [Sysno::clock_settime, Sysno::clock_adjtime] {
+            let rule = SeccompRule::new(syscall)
+                .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Ne, CLOCK_REALTIME));
+            let _old = self.custom.insert(syscall, vec![rule]);
+        }
+
+        self
+    }
 }
 
 impl RuleSet for Time {
@@ -39,7 +84,15 @@ impl RuleSet for Time {
         self.allowed.iter().copied().collect()
     }
 
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        self.custom.clone()
+    }
+
     fn name(&self) -> &'static str {
         "Time"
     }
+
+    fn description(&self) -> &'static str {
+        "allows reading clocks, using POSIX timers, and setting clocks other than the system clock"
+    }
 }
@@ -0,0 +1,71 @@
+//! Contains a [`RuleSet`] for allowing extended attribute syscalls.
+
+use std::collections::HashSet;
+
+use syscalls::Sysno;
+
+use crate::RuleSet;
+
+use super::YesReally;
+
+#[must_use]
+/// Enable syscalls for reading and writing extended file attributes.
+///
+/// Note that the path-based syscalls are not restricted by landlock, so the attributes of any file
+/// on the system can be read or written, subject to the usual file permissions.
+pub struct Xattr {
+    /// Syscalls that are allowed
+    allowed: HashSet<Sysno>,
+}
+
+impl Xattr {
+    /// Create a new Xattr [`RuleSet`] with nothing allowed by default.
+    pub fn nothing() -> Xattr {
+        Xattr {
+            allowed: HashSet::new(),
+        }
+    }
+
+    /// Allow reading and listing extended attributes: `getxattr`, `lgetxattr`, `fgetxattr`,
+    /// `listxattr`, `llistxattr`, and `flistxattr`.
+    pub fn allow_read(mut self) -> Xattr {
+        self.allowed.extend([
+            Sysno::getxattr, Sysno::lgetxattr, Sysno::fgetxattr,
+            Sysno::listxattr, Sysno::llistxattr, Sysno::flistxattr,
+        ]);
+
+        self
+    }
+
+    /// Allow setting and removing extended attributes: `setxattr`, `lsetxattr`, `fsetxattr`,
+    /// `removexattr`, `lremovexattr`, and `fremovexattr`.
+    ///
+    /// # Security considerations
+    ///
+    /// Attributes in the `security.*` and `trusted.*` namespaces are used by the kernel, e.g. file
+    /// capabilities are stored in `security.capability` and SELinux labels in `security.selinux`.
+    /// A process with the right privileges could use these to grant capabilities to an executable
+    /// or change how security modules treat a file.
+    pub fn allow_write(mut self) -> YesReally<Xattr> {
+        self.allowed.extend([
+            Sysno::setxattr, Sysno::lsetxattr, Sysno::fsetxattr,
+            Sysno::removexattr, Sysno::lremovexattr, Sysno::fremovexattr,
+        ]);
+
+        YesReally::new(self)
+    }
+}
+
+impl RuleSet for Xattr {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        self.allowed.iter().copied().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Xattr"
+    }
+
+    fn description(&self) -> &'static str {
+        "allows reading and writing extended file attributes"
+    }
+}
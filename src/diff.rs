@@ -0,0 +1,137 @@
+//! Comparing the rules of two [`SafetyContext`]s, e.g. to review how a policy changed between
+//! versions.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use syscalls::Sysno;
+
+use crate::{SafetyContext, SeccompRule};
+
+/// The seccomp rules of a [`SafetyContext`], with the conditional rules grouped by the name of
+/// the RuleSet they came from.
+type RulesByKind = (BTreeSet<Sysno>, BTreeMap<&'static str, Vec<SeccompRule>>);
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// The differences between the seccomp rules of two [`SafetyContext`]s. Returned by
+/// [`SafetyContext::diff`].
+///
+/// The `Display` implementation prints one line per difference, prefixed with `+` for additions
+/// and `-` for removals, which can be used e.g. to report why a CI check failed.
+pub struct PolicyDiff {
+    /// Syscalls that are allowed unconditionally by the other context but not by this one.
+    pub added_simple: BTreeSet<Sysno>,
+    /// Syscalls that are allowed unconditionally by this context but not by the other one.
+    pub removed_simple: BTreeSet<Sysno>,
+    /// Conditional rules that are only in the other context, keyed by the name of the RuleSet
+    /// they came from.
+    pub added_conditional: BTreeMap<&'static str, Vec<SeccompRule>>,
+    /// Conditional rules that are only in this context, keyed by the name of the RuleSet they
+    /// came from.
+    pub removed_conditional: BTreeMap<&'static str, Vec<SeccompRule>>,
+}
+
+impl PolicyDiff {
+    #[must_use]
+    /// Returns true if both contexts have the same seccomp rules.
+    pub fn is_empty(&self) -> bool {
+        self.added_simple.is_empty() && self.removed_simple.is_empty()
+            && self.added_conditional.is_empty() && self.removed_conditional.is_empty()
+    }
+
+    #[must_use]
+    /// Returns true if the other context allows anything this one doesn't, i.e. if there are any
+    /// added rules. This is conservative: a conditional rule that was added to replace a broader
+    /// one also counts.
+    pub fn widens(&self) -> bool {
+        !self.added_simple.is_empty() || !self.added_conditional.is_empty()
+    }
+}
+
+impl fmt::Display for PolicyDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for syscall in &self.added_simple {
+            writeln!(f, "+ {}", syscall)?;
+        }
+        for syscall in &self.removed_simple {
+            writeln!(f, "- {}", syscall)?;
+        }
+        for (sign, rules) in [('+', &self.added_conditional), ('-', &self.removed_conditional)] {
+            for (ruleset, rules) in rules {
+                for rule in rules {
+                    let conditions: Vec<String> = rule.argument_filters.iter()
+                        .map(ToString::to_string)
+                        .collect();
+                    writeln!(f, "{} {} if {} (from RuleSet `{}`)", sign, rule.syscall, conditions.join(" && "), ruleset)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SafetyContext {
+    #[must_use]
+    /// Compare the seccomp rules enabled so far with the ones enabled in `other`, without applying
+    /// anything. Added rules are the ones only in `other`, and removed rules are the ones only in
+    /// `self`, so `old.diff(&new)` shows how a policy changed.
+    ///
+    /// Syscalls allowed unconditionally are compared regardless of which RuleSet allowed them,
+    /// while conditional rules are compared per RuleSet. If a syscall changes from being allowed
+    /// unconditionally to being allowed with conditions or vice versa, it shows up as both an
+    /// added and a removed rule. Landlock rules and settings like the default action are not
+    /// compared.
+    pub fn diff(&self, other: &SafetyContext) -> PolicyDiff {
+        let (simple, conditional) = self.rules_by_kind();
+        let (other_simple, other_conditional) = other.rules_by_kind();
+
+        PolicyDiff {
+            added_simple: other_simple.difference(&simple).copied().collect(),
+            removed_simple: simple.difference(&other_simple).copied().collect(),
+            added_conditional: difference(&other_conditional, &conditional),
+            removed_conditional: difference(&conditional, &other_conditional),
+        }
+    }
+
+    /// The syscalls that are allowed unconditionally, and the conditional rules sorted by syscall
+    /// and grouped by RuleSet.
+    fn rules_by_kind(&self) -> RulesByKind {
+        let mut syscalls: Vec<&Sysno> = self.seccomp_rules.keys().collect();
+        syscalls.sort();
+
+        let mut simple = BTreeSet::new();
+        let mut conditional: BTreeMap<&'static str, Vec<SeccompRule>> = BTreeMap::new();
+        for syscall in syscalls {
+            for labeled_rule in &self.seccomp_rules[syscall] {
+                if labeled_rule.1.argument_filters.is_empty() {
+                    let _new = simple.insert(*syscall);
+                } else {
+                    conditional.entry(labeled_rule.0)
+                        .or_insert_with(Vec::new)
+                        .push(labeled_rule.1.clone());
+                }
+            }
+        }
+
+        (simple, conditional)
+    }
+}
+
+/// The rules in `rules` that are not in `other` for the same RuleSet.
+fn difference(rules: &BTreeMap<&'static str, Vec<SeccompRule>>, other: &BTreeMap<&'static str, Vec<SeccompRule>>)
+    -> BTreeMap<&'static str, Vec<SeccompRule>> {
+    let mut difference = BTreeMap::new();
+    for (&ruleset, rules) in rules {
+        let other_rules = other.get(ruleset).map_or(&[][..], Vec::as_slice);
+        let only_here: Vec<SeccompRule> = rules.iter()
+            .filter(|rule| !other_rules.contains(rule))
+            .cloned()
+            .collect();
+        if !only_here.is_empty() {
+            let _none = difference.insert(ruleset, only_here);
+        }
+    }
+
+    difference
+}
@@ -7,6 +7,8 @@ use std::path::PathBuf;
 
 use seccompiler::Error as SeccompilerError;
 
+use crate::SeccompRule;
+
 #[cfg(feature = "landlock")]
 use landlock::RulesetError as LandlockError;
 #[cfg(feature = "landlock")]
@@ -16,12 +18,41 @@ use landlock::PathFdError;
 /// The error type produced by [`crate::SafetyContext`]
 pub enum ExtraSafeError {
     /// Error created when a simple Seccomp rule would override a conditional rule, or when trying to add a
-    /// conditional rule when there's already a simple rule with the same syscall.
-    ConditionalNoEffectError(syscalls::Sysno, &'static str, &'static str),
+    /// conditional rule when there's already a simple rule with the same syscall. Contains the
+    /// syscall, the name of the RuleSet with the conditional rule, the name of the RuleSet with
+    /// the simple rule, and the conditional rule that would be overridden.
+    ConditionalNoEffectError(syscalls::Sysno, &'static str, &'static str, SeccompRule),
     /// An error from the underlying seccomp library.
     SeccompError(SeccompilerError),
     /// No rules were enabled in the SafetyContext.
     NoRulesEnabled,
+    /// The errno provided to the SafetyContext does not fit in the seccomp return value.
+    InvalidErrno(u32),
+    /// The errno passed to [`return_errno_for`](crate::SafetyContext::return_errno_for) for the
+    /// contained syscall is not between 1 and 4095. Contains the syscall and the errno.
+    ErrnoOutOfRange(syscalls::Sysno, i32),
+    /// Syscalls were marked with [`notify_on`](crate::SafetyContext::notify_on), but the filter
+    /// was applied in a way that does not return the listener fd.
+    NotifyListenerRequired,
+    /// The filter was applied with [`apply_with_listener`](crate::SafetyContext::apply_with_listener),
+    /// but no syscalls were marked with [`notify_on`](crate::SafetyContext::notify_on), so the
+    /// listener would never receive a notification.
+    NoNotifySyscalls,
+    /// The filter could not be applied to all threads, because the thread with the contained id
+    /// already has a filter that is not an ancestor of the current thread's filter, e.g. because
+    /// it applied another filter only to itself.
+    ThreadSyncFailed(libc::pid_t),
+    /// A syscall name passed to [`allow_syscall_names`](crate::SafetyContext::allow_syscall_names)
+    /// is not a syscall on the current architecture.
+    UnknownSyscall(String),
+    /// Landlock was required with [`require_landlock`](crate::SafetyContext::require_landlock),
+    /// but no landlock rules would be enforced, because the `landlock` feature is disabled, the
+    /// running kernel does not support Landlock, or no landlock rules were enabled. Also returned
+    /// when applying the filter if the landlock rules were not fully enforced.
+    LandlockRequired,
+    #[cfg(feature = "config")]
+    /// A policy file could not be parsed or contained unknown rulesets or options.
+    InvalidPolicy(String),
     #[cfg(feature = "landlock")]
     /// Two landlock rules with the same path were added.
     DuplicatePath(PathBuf, &'static str, &'static str),
@@ -36,6 +67,14 @@ pub enum ExtraSafeError {
     /// Landlock does not support being applied to all threads.
     LandlockNoThreadSync,
     #[cfg(feature = "landlock")]
+    /// Landlock rules can't be applied to a child process spawned with
+    /// [`spawn_sandboxed`](crate::SafetyContext::spawn_sandboxed).
+    LandlockNoSpawn,
+    #[cfg(feature = "landlock")]
+    /// The running kernel does not support the minimum Landlock ABI version that was required.
+    /// Contains the required version and the version supported by the kernel, if any.
+    LandlockAbiUnsupported(landlock::ABI, Option<landlock::ABI>),
+    #[cfg(feature = "landlock")]
     /// An error from the underlying landlock library.
     LandlockError(LandlockError),
 }
@@ -43,14 +82,27 @@ pub enum ExtraSafeError {
 impl fmt::Display for ExtraSafeError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            &Self::ConditionalNoEffectError(sysno, a, b) => write!(
-                f,
-                "A conditional rule on syscall `{}` from RuleSet `{}` would be overridden \
-                by a simple rule from RuleSet `{}`.",
-                sysno, a, b,
-            ),
+            Self::ConditionalNoEffectError(sysno, a, b, rule) => {
+                write!(f, "A conditional rule on syscall `{}` (", sysno)?;
+                for (i, filter) in rule.argument_filters.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " && ")?;
+                    }
+                    write!(f, "{}", filter)?;
+                }
+                write!(f, ") from RuleSet `{}` would be overridden by a simple rule from RuleSet `{}`.", a, b)
+            }
             Self::SeccompError(err) => write!(f, "A seccomp error occured {:?}", err),
             Self::NoRulesEnabled => write!(f, "No rules were enabled in the SafetyContext"),
+            Self::InvalidErrno(errno) => write!(f, "The errno {} is too large to be returned by a seccomp filter", errno),
+            Self::ErrnoOutOfRange(sysno, errno) => write!(f, "The errno {} for syscall `{}` is not between 1 and 4095", errno, sysno),
+            Self::NotifyListenerRequired => write!(f, "Syscalls were marked with notify_on, so the filter must be applied with apply_with_listener"),
+            Self::NoNotifySyscalls => write!(f, "The filter was applied with apply_with_listener, but no syscalls were marked with notify_on"),
+            Self::LandlockRequired => write!(f, "Landlock is required but no landlock rules would be enforced"),
+            Self::UnknownSyscall(name) => write!(f, "`{}` is not the name of a syscall on this architecture", name),
+            Self::ThreadSyncFailed(tid) => write!(f, "The filter could not be applied to all threads because thread {} has an incompatible seccomp filter", tid),
+            #[cfg(feature = "config")]
+            Self::InvalidPolicy(msg) => write!(f, "Invalid policy: {}", msg),
             #[cfg(feature = "landlock")]
             Self::DuplicatePath(path, a, b) => write!(f, "The same path ({:?}) was used in two different landlock rules. Rulesets '{}' and '{}'", path, a, b),
             #[cfg(feature = "landlock")]
@@ -61,6 +113,12 @@ impl fmt::Display for ExtraSafeError {
             Self::LandlockError(err) => write!(f, "A Landlock error occurred: {:?}", err),
             #[cfg(feature = "landlock")]
             Self::LandlockNoThreadSync => write!(f, "Landlock does not support syncing to all threads"),
+            #[cfg(feature = "landlock")]
+            Self::LandlockNoSpawn => write!(f, "Landlock rules can't be applied to a sandboxed child process"),
+            #[cfg(feature = "landlock")]
+            Self::LandlockAbiUnsupported(required, Some(available)) => write!(f, "Landlock ABI {} is required but the kernel only supports ABI {}", required, available),
+            #[cfg(feature = "landlock")]
+            Self::LandlockAbiUnsupported(required, None) => write!(f, "Landlock ABI {} is required but the kernel does not support Landlock", required),
         }
     }
 }
@@ -82,6 +140,15 @@ impl std::error::Error for ExtraSafeError {
         match self {
             Self::ConditionalNoEffectError(..) => None,
             Self::NoRulesEnabled => None,
+            Self::InvalidErrno(_) => None,
+            Self::ErrnoOutOfRange(..) => None,
+            Self::NotifyListenerRequired => None,
+            Self::NoNotifySyscalls => None,
+            Self::ThreadSyncFailed(_) => None,
+            Self::UnknownSyscall(_) => None,
+            Self::LandlockRequired => None,
+            #[cfg(feature = "config")]
+            Self::InvalidPolicy(_) => None,
             Self::SeccompError(err) => Some(err),
             #[cfg(feature = "landlock")]
             Self::DuplicatePath(_, _, _) => None,
@@ -93,6 +160,10 @@ impl std::error::Error for ExtraSafeError {
             Self::LandlockError(err) => Some(err),
             #[cfg(feature = "landlock")]
             Self::LandlockNoThreadSync => None,
+            #[cfg(feature = "landlock")]
+            Self::LandlockNoSpawn => None,
+            #[cfg(feature = "landlock")]
+            Self::LandlockAbiUnsupported(_, _) => None,
         }
     }
 }
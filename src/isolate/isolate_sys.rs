@@ -5,10 +5,10 @@
 //! Control flow works as follows:
 //! - We want to call some function `func` inside our namespace
 //! - After re-executing self via Command, `Isolate::main_hook` eventually calls
-//! `clone_into_namespace` with a bunch of configuration data, including which function we
-//! eventually want to call
+//!   `clone_into_namespace` with a bunch of configuration data, including which function we
+//!   eventually want to call
 //! - `clone_into_namespace` sets up the clone syscall with the namespace parameters and the config
-//! data, and calls clone with the `run_isolate` function
+//!   data, and calls clone with the `run_isolate` function
 //! - `run_isolate` unpacks the config data and uses it to set up a new tmpfs and bindmounts inside it, then does `pivot_root` into the tmpfs.
 //! - Finally, `run_isolate` calls `func` and then exits when it's done.
 #![allow(unsafe_code)]
@@ -142,7 +142,7 @@ pub fn make_tempdir(isolate_name: &str) -> PathBuf {
     let template_str = format!("/tmp/{}.XXXXXX\0", isolate_name);
     let mut dir_buf: Vec<u8> = template_str.clone().into_bytes();
 
-    let dir_ptr: *mut i8 = dir_buf.as_mut_ptr().cast::<i8>();
+    let dir_ptr: *mut libc::c_char = dir_buf.as_mut_ptr().cast::<libc::c_char>();
     let ret = unsafe { libc::mkdtemp(dir_ptr) };
     fail_null!(ret, "failed to create temporary directory after clone");
 
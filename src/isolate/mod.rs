@@ -4,13 +4,13 @@
 //!
 //! Specifically, you can isolate:
 //! - The filesystem. The Isolate creates a temporary directory and mounts a tmpfs onto it, and
-//! then makes that tmpfs the new root. The original root filesystem becomes unaccessible.
-//! Specific directories and files may be mounted into the tmpfs if desired.
+//!   then makes that tmpfs the new root. The original root filesystem becomes unaccessible.
+//!   Specific directories and files may be mounted into the tmpfs if desired.
 //! - The network. A new network namespace may also be created. In the context of extrasafe, this
-//! is mostly useful to disable the network and make it simpler to use seccomp.
+//!   is mostly useful to disable the network and make it simpler to use seccomp.
 //! - The program itself. In addition to running in a different memory space, so the original
-//! program's data is unaffected by the subprocess, the Isolate is executed via an in-memory
-//! copy of the program so that the program binary itself cannot be modified.
+//!   program's data is unaffected by the subprocess, the Isolate is executed via an in-memory
+//!   copy of the program so that the program binary itself cannot be modified.
 
 // options:
 // - keep network
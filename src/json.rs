@@ -0,0 +1,111 @@
+#![cfg(feature = "serde")]
+
+//! Serialization of a [`SafetyContext`]'s rules to JSON, for inspecting and diffing policies.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::{SafetyContext, SeccompArgumentFilter, SeccompilerComparator};
+
+/// The rules enabled by a single [`RuleSet`](crate::RuleSet), keyed by syscall name.
+#[derive(Serialize, Default)]
+struct RuleSetJson {
+    /// Syscalls allowed unconditionally
+    simple: BTreeSet<&'static str>,
+    /// Syscalls allowed if any of their rules match. Each rule is a list of argument filters
+    /// which must all match.
+    conditional: BTreeMap<&'static str, Vec<Vec<ArgumentFilterJson>>>,
+}
+
+/// A single [`SeccompArgumentFilter`].
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+struct ArgumentFilterJson {
+    /// The index of the syscall argument being filtered
+    arg: u8,
+    /// The comparison operation, e.g. `eq` or `masked_eq`
+    op: &'static str,
+    /// The mask applied to the argument before comparing, if the operation is `masked_eq`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mask: Option<u64>,
+    /// The value the argument is compared against
+    value: u64,
+    /// Whether all 64 bits of the argument are compared, rather than just the lower 32
+    is_64bit: bool,
+}
+
+impl From<&SeccompArgumentFilter> for ArgumentFilterJson {
+    fn from(filter: &SeccompArgumentFilter) -> ArgumentFilterJson {
+        let (op, mask) = match filter.comparator {
+            SeccompilerComparator::Eq => ("eq", None),
+            SeccompilerComparator::Ne => ("ne", None),
+            SeccompilerComparator::Lt => ("lt", None),
+            SeccompilerComparator::Le => ("le", None),
+            SeccompilerComparator::Gt => ("gt", None),
+            SeccompilerComparator::Ge => ("ge", None),
+            SeccompilerComparator::MaskedEq(mask) => ("masked_eq", Some(mask)),
+        };
+
+        ArgumentFilterJson {
+            arg: filter.arg_idx,
+            op,
+            mask,
+            value: filter.value,
+            is_64bit: filter.is_64bit,
+        }
+    }
+}
+
+impl SafetyContext {
+    #[must_use]
+    /// Serialize the seccomp rules enabled so far to JSON, grouped by the name of the
+    /// [`RuleSet`](crate::RuleSet) they came from.
+    ///
+    /// The output is deterministic: rulesets, syscalls, and rules are all sorted, so it can be
+    /// compared across versions of your program or of extrasafe to catch unexpected changes in
+    /// what is allowed. As with [`enabled_syscalls`](Self::enabled_syscalls), the syscalls from
+    /// [`BasicCapabilities`](crate::builtins::BasicCapabilities) are not included.
+    ///
+    /// ```json
+    /// {
+    ///   "Memory": {
+    ///     "simple": ["brk", "munmap"],
+    ///     "conditional": {
+    ///       "mmap": [[{ "arg": 2, "op": "masked_eq", "mask": 4, "value": 0, "is_64bit": true }]]
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut rulesets: BTreeMap<&'static str, RuleSetJson> = BTreeMap::new();
+
+        for labeled_rule in self.seccomp_rules.values().flatten() {
+            let ruleset = rulesets.entry(labeled_rule.0).or_default();
+            let rule = &labeled_rule.1;
+            let syscall = rule.syscall.name();
+
+            if rule.argument_filters.is_empty() {
+                let _new = ruleset.simple.insert(syscall);
+            }
+            else {
+                let filters = rule.argument_filters.iter()
+                    .map(ArgumentFilterJson::from)
+                    .collect();
+                ruleset.conditional.entry(syscall)
+                    .or_insert_with(Vec::new)
+                    .push(filters);
+            }
+        }
+
+        for ruleset in rulesets.values_mut() {
+            for rules in ruleset.conditional.values_mut() {
+                rules.sort();
+            }
+        }
+
+        // Serializing can only fail for maps with non-string keys or types with failing Serialize
+        // impls, neither of which are used here.
+        serde_json::to_string_pretty(&rulesets)
+            .expect("serializing rules to JSON cannot fail")
+    }
+}
@@ -5,7 +5,10 @@
 use std::path::{Path, PathBuf};
 
 pub use landlock::RulesetError as LandlockError;
-pub use landlock::{ABI, Access, AccessFs, BitFlags, Compatible, CompatLevel, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreatedAttr};
+pub use landlock::{ABI, Access, AccessFs, AccessNet, BitFlags, Compatible, CompatLevel, NetPort, PathBeneath, PathFd, Ruleset, RulesetAttr, RulesetCreatedAttr};
+use landlock::RulesetStatus;
+
+use crate::ExtraSafeError;
 
 /// A Landlock rule. It consists of a path and a collection of access rights which determine what
 /// actions can be performed on that path.
@@ -28,10 +31,107 @@ impl LandlockRule {
     }
 }
 
+/// A Landlock network rule. It consists of a TCP port and a collection of access rights which
+/// determine whether binding to and/or connecting to that port is allowed.
+///
+/// Network rules require Landlock ABI v4 (Linux 6.7) or newer. On older kernels, applying a
+/// `SafetyContext` with network rules will fail.
+#[derive(Clone, Debug)]
+pub struct LandlockNetRule {
+    /// The TCP port to apply the access rules to.
+    pub port: u16,
+    /// The access rules, i.e. bind and/or connect, to allow on the port.
+    pub access_rules: BitFlags<AccessNet>,
+}
+
+impl LandlockNetRule {
+    /// Create a new Landlock network rule.
+    pub fn new(port: u16, access_rules: BitFlags<AccessNet>) -> LandlockNetRule {
+        LandlockNetRule {
+            port,
+            access_rules,
+        }
+    }
+}
+
+/// Query the Landlock ABI version supported by the running kernel, or `None` if Landlock is not
+/// supported or not enabled. Versions newer than those known to the landlock crate are reported
+/// as the newest known version.
+#[allow(unsafe_code)]
+pub(crate) fn current_abi() -> Option<ABI> {
+    /// Flag to `landlock_create_ruleset` to return the ABI version instead of creating a ruleset.
+    const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1 << 0;
+
+    // SAFETY: with the version flag, the attribute pointer must be null and the size 0, and no
+    // file descriptor is created.
+    let version = unsafe {
+        libc::syscall(libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<libc::c_void>(), 0usize, LANDLOCK_CREATE_RULESET_VERSION)
+    };
+    if version <= 0 {
+        return None;
+    }
+
+    Some(ABI::from(i32::try_from(version).unwrap_or(i32::MAX)))
+}
+
 /// A [`LandlockRule`] labeled with the name of the [`RuleSet`] it originated from. Internal-only.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct LabeledLandlockRule(pub &'static str, pub LandlockRule);
 
+/// All of the landlock rules to be applied by a `CompiledFilter`. Internal-only.
+#[derive(Clone, Debug)]
+pub(crate) struct LandlockRules {
+    /// Filesystem rules
+    pub paths: Vec<LandlockRule>,
+    /// Network rules
+    pub ports: Vec<LandlockNetRule>,
+    /// Whether landlock was required with `require_landlock` or `require_landlock_abi`
+    pub required: bool,
+}
+
+impl LandlockRules {
+    /// Apply the rules to the current thread.
+    ///
+    /// Filesystem access is restricted if there are any filesystem rules, or if there are no
+    /// network rules either (i.e. with `landlock_only()`), so that a `SafetyContext` that only
+    /// restricts ports does not prevent all filesystem access. TCP bind and connect are only
+    /// restricted if there are network rules. `PR_SET_NO_NEW_PRIVS` is only set if
+    /// `no_new_privs` is true.
+    ///
+    /// If the rules are `required`, fails with [`ExtraSafeError::LandlockRequired`] unless they
+    /// are fully enforced.
+    pub(crate) fn restrict_self(&self, no_new_privs: bool) -> Result<(), ExtraSafeError> {
+        let mut ruleset = Ruleset::default()
+            .set_compatibility(CompatLevel::HardRequirement);
+        if !self.paths.is_empty() || self.ports.is_empty() {
+            ruleset = ruleset.handle_access(AccessFs::from_all(ABI::V2))?;
+        }
+        if !self.ports.is_empty() {
+            ruleset = ruleset.handle_access(AccessNet::from_all(ABI::V4))?;
+        }
+        let mut ruleset = ruleset.create()?
+            .no_new_privs(no_new_privs);
+
+        for rule in &self.paths {
+            // If path does not exist or is not accessible, just ignore it
+            if let Ok(fd) = PathFd::new(rule.path.clone()) {
+                let path_beneath = PathBeneath::new(fd, rule.access_rules);
+                ruleset = ruleset.add_rule(path_beneath)?;
+            }
+        }
+        for rule in &self.ports {
+            ruleset = ruleset.add_rule(NetPort::new(rule.port, rule.access_rules))?;
+        }
+
+        let status = ruleset.restrict_self()?;
+        if self.required && status.ruleset != RulesetStatus::FullyEnforced {
+            return Err(ExtraSafeError::LandlockRequired);
+        }
+        Ok(())
+    }
+}
+
 /// Helper functions for Landlock access rights
 pub mod access {
     use super::*;
@@ -83,4 +183,16 @@ pub mod access {
     pub fn execute() -> BitFlags<AccessFs> {
         Fs::Execute.into()
     }
+
+    /// Convenience function for landlock bind TCP port access right
+    #[must_use]
+    pub fn bind_tcp() -> BitFlags<AccessNet> {
+        AccessNet::BindTcp.into()
+    }
+
+    /// Convenience function for landlock connect TCP port access right
+    #[must_use]
+    pub fn connect_tcp() -> BitFlags<AccessNet> {
+        AccessNet::ConnectTcp.into()
+    }
 }
@@ -12,6 +12,20 @@
 //!
 //! See the [`SafetyContext`] struct's documentation and the tests/ and examples/ directories for
 //! more information on how to use it.
+//!
+//! # Compatibility ABIs
+//!
+//! Filters are compiled for the native architecture only. Syscalls made through another ABI can't
+//! be used to bypass them: the filter checks the architecture of each syscall and kills the
+//! process if it doesn't match, e.g. for i386 syscalls made with `int 0x80` on x86_64. x32
+//! syscalls use the x86_64 architecture but have bit 30 set in the syscall number, so they never
+//! match an allowed syscall and get the default action.
+//!
+//! # Tracing
+//!
+//! With the `tracing` feature, extrasafe emits a `DEBUG` event with the ruleset's name and
+//! number of rules each time a [`RuleSet`] is enabled, and an `INFO` event with the size of the
+//! filter just before a filter is applied, so that the subscriber can still do its IO.
 
 
 // Filter is the entire, top-level seccomp filter chain. All SeccompilerRules are or-ed together.
@@ -25,6 +39,8 @@ pub use seccompiler::SeccompRule as SeccompilerRule;
 pub use seccompiler::SeccompCondition as SeccompilerArgumentFilter;
 pub use seccompiler::Error as SeccompilerError;
 pub use seccompiler::SeccompCmpOp as SeccompilerComparator;
+pub use seccompiler::TargetArch as SeccompilerTargetArch;
+pub use seccompiler::sock_filter;
 
 use seccompiler::SeccompAction;
 
@@ -46,9 +62,29 @@ pub use landlock::*;
 #[cfg(feature = "isolate")]
 pub mod isolate;
 
+#[cfg(feature = "serde")]
+mod json;
+
+#[cfg(feature = "config")]
+pub mod policy;
+
+pub mod trap;
+
+mod lint;
+pub use lint::Lint;
+
+mod diff;
+pub use diff::PolicyDiff;
+
+mod budget;
+pub use budget::BudgetReport;
+
+mod seccomp;
+pub use seccomp::{is_seccomp_active, seccomp_filter_count};
+
 #[cfg(feature = "landlock")]
 use std::path::PathBuf;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 #[derive(Debug, Clone, PartialEq)]
 /// A restriction on the arguments of a syscall. May be combined with other
@@ -131,7 +167,25 @@ impl SeccompArgumentFilter {
     }
 }
 
-#[derive(Debug, Clone)]
+impl std::fmt::Display for SeccompArgumentFilter {
+    /// Format the filter like the [`seccomp_arg_filter!`] macro, e.g. `arg0 & 0x1 == 0x0`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self.comparator {
+            SeccompilerComparator::Eq => "==",
+            SeccompilerComparator::Ne => "!=",
+            SeccompilerComparator::Lt => "<",
+            SeccompilerComparator::Le => "<=",
+            SeccompilerComparator::Gt => ">",
+            SeccompilerComparator::Ge => ">=",
+            SeccompilerComparator::MaskedEq(mask) => {
+                return write!(f, "arg{} & {:#x} == {:#x}", self.arg_idx, mask, self.value);
+            }
+        };
+        write!(f, "arg{} {} {:#x}", self.arg_idx, op, self.value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 #[must_use]
 /// A seccomp rule.
 pub struct SeccompRule {
@@ -174,6 +228,13 @@ impl SeccompRule {
     }
 }
 
+/// The mask of the data portion of a seccomp filter's return value, which contains e.g. the errno
+/// to return.
+const SECCOMP_RET_DATA: u32 = 0x0000_ffff;
+
+/// The largest errno the kernel returns from a syscall, `MAX_ERRNO` in `<linux/err.h>`.
+const MAX_ERRNO: i32 = 4095;
+
 #[derive(Debug, Clone)]
 /// A [`SeccompRule`] labeled with the name of the [`RuleSet`] it originated from. Internal-only.
 struct LabeledSeccompRule(pub &'static str, pub SeccompRule);
@@ -193,12 +254,25 @@ pub trait RuleSet {
     /// The name of the profile.
     fn name(&self) -> &'static str;
 
+    /// A short human-readable description of what the profile allows, e.g. for rendering policy
+    /// reports. Empty by default.
+    fn description(&self) -> &'static str {
+        ""
+    }
+
     #[cfg(feature = "landlock")]
     /// A landlock rule is a pair of an access control (e.g. read/write access, directory creation
     /// access) and a directory or path.
     fn landlock_rules(&self) -> Vec<LandlockRule> {
         Vec::new()
     }
+
+    #[cfg(feature = "landlock")]
+    /// A landlock network rule is a pair of an access control (i.e. bind or connect) and a TCP
+    /// port.
+    fn landlock_net_rules(&self) -> Vec<LandlockNetRule> {
+        Vec::new()
+    }
 }
 
 impl<T: ?Sized + RuleSet> RuleSet for &T {
@@ -217,11 +291,56 @@ impl<T: ?Sized + RuleSet> RuleSet for &T {
         T::name(self)
     }
 
+    #[inline]
+    fn description(&self) -> &'static str {
+        T::description(self)
+    }
+
+    #[cfg(feature = "landlock")]
+    #[inline]
+    fn landlock_rules(&self) -> Vec<LandlockRule> {
+        T::landlock_rules(self)
+    }
+
+    #[cfg(feature = "landlock")]
+    #[inline]
+    fn landlock_net_rules(&self) -> Vec<LandlockNetRule> {
+        T::landlock_net_rules(self)
+    }
+}
+
+impl<T: ?Sized + RuleSet> RuleSet for Box<T> {
+    #[inline]
+    fn simple_rules(&self) -> Vec<syscalls::Sysno> {
+        T::simple_rules(self)
+    }
+
+    #[inline]
+    fn conditional_rules(&self) -> HashMap<syscalls::Sysno, Vec<SeccompRule>> {
+        T::conditional_rules(self)
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        T::name(self)
+    }
+
+    #[inline]
+    fn description(&self) -> &'static str {
+        T::description(self)
+    }
+
     #[cfg(feature = "landlock")]
     #[inline]
     fn landlock_rules(&self) -> Vec<LandlockRule> {
         T::landlock_rules(self)
     }
+
+    #[cfg(feature = "landlock")]
+    #[inline]
+    fn landlock_net_rules(&self) -> Vec<LandlockNetRule> {
+        T::landlock_net_rules(self)
+    }
 }
 
 impl RuleSet for syscalls::Sysno {
@@ -238,6 +357,16 @@ impl RuleSet for syscalls::Sysno {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Which rule to keep when a simple rule and a conditional rule are enabled for the same syscall.
+/// See [`SafetyContext::enable_with_precedence`].
+pub enum Precedence {
+    /// Keep the simple rule, allowing the syscall unconditionally.
+    Simple,
+    /// Keep the conditional rules, so the syscall stays restricted.
+    Conditional,
+}
+
 #[must_use]
 /// A struct representing a set of rules to be loaded into a seccomp filter and applied to the
 /// current thread, or all threads in the current process.
@@ -245,7 +374,7 @@ impl RuleSet for syscalls::Sysno {
 /// Create with [`new()`](Self::new). Add [`RuleSet`]s with [`enable()`](Self::enable), and then use [`apply_to_current_thread()`](Self::apply_to_current_thread)
 /// to apply the filters to the current thread, or [`apply_to_all_threads()`](Self::apply_to_all_threads) to apply the filter to
 /// all threads in the process.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SafetyContext {
     /// A mapping from a syscall to either be a single simple rule or multiple conditional rules, but not both.
     seccomp_rules: HashMap<syscalls::Sysno, Vec<LabeledSeccompRule>>,
@@ -253,14 +382,34 @@ pub struct SafetyContext {
     /// A mapping from filesystem paths to [`LandlockRule`]s specifying files and directories with
     /// the operations that can be performed on them.
     landlock_rules: HashMap<PathBuf, LabeledLandlockRule>,
-    /// The errno returned when a syscall does not match one of the seccomp rules. Defaults to 1.
-    errno: u32,
-    /// Flag to apply seccomp to all threads rather than just the current thread. Defaults to
-    /// false (but the public apply functions always set it directly anyway)
-    all_threads: bool,
+    #[cfg(feature = "landlock")]
+    /// [`LandlockNetRule`]s specifying TCP ports and whether they can be bound or connected to.
+    landlock_net_rules: Vec<LandlockNetRule>,
+    /// The action taken when a syscall does not match one of the seccomp rules. Defaults to
+    /// returning errno 1 (`EPERM`).
+    default_action: SeccompAction,
     #[cfg(feature = "landlock")]
     /// Flag to only use landlock filters and not enable seccomp filters at all. Defaults to false.
     only_landlock: bool,
+    #[cfg(feature = "landlock")]
+    /// The minimum Landlock ABI the running kernel must support. Defaults to `None`.
+    required_landlock_abi: Option<ABI>,
+    /// Whether applying should fail if no landlock rules would be enforced. Defaults to false.
+    require_landlock: bool,
+    /// Whether to keep speculative store bypass mitigations disabled when the seccomp filter is
+    /// installed. Defaults to false.
+    allow_speculation: bool,
+    /// Whether to log syscalls that are denied or killed by the seccomp filter to the audit log.
+    /// Defaults to false.
+    log_violations: bool,
+    /// Whether to set `PR_SET_NO_NEW_PRIVS` before installing the filters. Defaults to true.
+    no_new_privs: bool,
+    /// Syscalls that are never allowed, even if a ruleset enables them.
+    denied: HashSet<syscalls::Sysno>,
+    /// Syscalls that notify a supervisor via the listener fd instead of being allowed or denied.
+    notify: BTreeSet<syscalls::Sysno>,
+    /// Syscalls that return a specific errno instead of being allowed or denied.
+    errnos: BTreeMap<syscalls::Sysno, i32>,
 }
 
 impl SafetyContext {
@@ -272,17 +421,169 @@ impl SafetyContext {
             seccomp_rules: HashMap::new(),
             #[cfg(feature = "landlock")]
             landlock_rules: HashMap::new(),
-            errno: 1,
-            all_threads: false,
+            #[cfg(feature = "landlock")]
+            landlock_net_rules: Vec::new(),
+            default_action: SeccompAction::Errno(1),
             #[cfg(feature = "landlock")]
             only_landlock: false,
+            #[cfg(feature = "landlock")]
+            required_landlock_abi: None,
+            require_landlock: false,
+            allow_speculation: false,
+            log_violations: false,
+            no_new_privs: true,
+            denied: HashSet::new(),
+            notify: BTreeSet::new(),
+            errnos: BTreeMap::new(),
         }
     }
 
     /// Set the errno to the provided value when a syscall does not match one of the seccomp rules
-    /// in this `SafetyContext`.
+    /// in this `SafetyContext`. By default, `EPERM` is returned.
+    ///
+    /// For example, returning `ENOSYS` instead will usually make libc and other libraries fall back
+    /// to alternative syscalls, as if the syscall did not exist on the running kernel.
+    ///
+    /// The errno must fit in the 16 bit data field of the seccomp return value, otherwise applying
+    /// the `SafetyContext` will fail with [`ExtraSafeError::InvalidErrno`]. Note that the kernel
+    /// further limits the errno to at most 4095.
     pub fn with_errno(mut self, errno: u32) -> SafetyContext {
-        self.errno = errno;
+        self.default_action = SeccompAction::Errno(errno);
+        self
+    }
+
+    /// Set whether speculative store bypass (SSB) should stay allowed when the seccomp filter is
+    /// installed, by passing `SECCOMP_FILTER_FLAG_SPEC_ALLOW` to the kernel. Defaults to `false`.
+    ///
+    /// # Security considerations
+    /// Depending on the kernel's `spec_store_bypass_disable` setting, installing a seccomp filter
+    /// also enables the mitigation for Spectre variant 4 (speculative store bypass) for the thread,
+    /// which can noticeably slow down some workloads. Passing `true` keeps the mitigation
+    /// disabled, which is only reasonable if the sandboxed code is trusted not to mount
+    /// speculative execution attacks, e.g. when the filter is defense in depth against bugs
+    /// rather than a boundary around untrusted code.
+    pub fn allow_speculation(mut self, allow: bool) -> SafetyContext {
+        self.allow_speculation = allow;
+        self
+    }
+
+    /// Allow syscalls that do not match one of the seccomp rules in this `SafetyContext`, but log
+    /// them to the kernel's audit log.
+    ///
+    /// This does not restrict anything, and is meant to be used to discover which syscalls your
+    /// program needs before enforcing a policy. Note that the logs will only appear if the kernel
+    /// is configured to record them: `log` must be listed in
+    /// `/proc/sys/kernel/seccomp/actions_logged`, and they will show up either in the audit log or
+    /// in `dmesg` depending on whether auditd is running.
+    pub fn log_only(mut self) -> SafetyContext {
+        self.default_action = SeccompAction::Log;
+        self
+    }
+
+    /// Log syscalls that do not match one of the seccomp rules to the kernel's audit log, in
+    /// addition to taking the default action, e.g. returning `EPERM` or killing the process.
+    ///
+    /// Unlike [`log_only`](Self::log_only), this does not change what is allowed: it installs the
+    /// filter with `SECCOMP_FILTER_FLAG_LOG`, which requires Linux 4.14 or newer, so that
+    /// violations can be investigated afterwards. As with `log_only`, whether the logs appear is
+    /// governed by the kernel's configuration: the action, e.g. `errno` or `kill_process`, must be
+    /// listed in `/proc/sys/kernel/seccomp/actions_logged`, and the logs show up either in the
+    /// audit log or in `dmesg` depending on whether auditd is running.
+    pub fn log_violations(mut self) -> SafetyContext {
+        self.log_violations = true;
+        self
+    }
+
+    /// Set whether `PR_SET_NO_NEW_PRIVS` is set on the thread before the seccomp filter and
+    /// landlock rules are installed. Defaults to `true`.
+    ///
+    /// # Security considerations
+    /// Without `no_new_privs`, setuid and setgid binaries and file capabilities keep working
+    /// after the filter is applied, so the sandboxed process can `exec` a program that runs with
+    /// more privileges than it has, e.g. `sudo` or `su`. The filter is inherited by that program,
+    /// which is why the kernel only allows installing a seccomp filter or landlock ruleset
+    /// without `no_new_privs` if the thread has `CAP_SYS_ADMIN` in its user namespace.
+    /// Unprivileged threads will get an `EACCES` error from the kernel when the filter is applied.
+    ///
+    /// Only disable this if the process is privileged and needs to run setuid programs after
+    /// sandboxing. Note that `no_new_privs` can't be unset once it is set, so this has no effect
+    /// if it was already set, e.g. by a previous filter or by the parent process.
+    pub fn set_no_new_privs(mut self, set: bool) -> SafetyContext {
+        self.no_new_privs = set;
+        self
+    }
+
+    /// Kill the whole process when a syscall does not match one of the seccomp rules, instead of
+    /// returning an error (`EPERM` by default).
+    ///
+    /// This uses `SECCOMP_RET_KILL_PROCESS`, which requires Linux 4.14 or newer. Older kernels
+    /// treat it as `SECCOMP_RET_KILL_THREAD` instead and only kill the offending thread. The
+    /// process is terminated as if by an uncaught `SIGSYS`, so a core dump may be produced.
+    pub fn kill_process_on_violation(mut self) -> SafetyContext {
+        self.default_action = SeccompAction::KillProcess;
+        self
+    }
+
+    /// Raise `SIGSYS` in the offending thread when a syscall does not match one of the seccomp
+    /// rules, instead of returning an error.
+    ///
+    /// Without a `SIGSYS` handler this kills the process, like an unhandled segfault. Use
+    /// [`trap::install_logging_handler`] to log blocked syscalls and make them return `EPERM`
+    /// instead, which is useful for finding out which syscalls are missing from a policy without
+    /// killing the process.
+    ///
+    /// Since the handler writes to stderr, writing to stderr is allowed in addition to the
+    /// enabled rules.
+    pub fn trap_on_violation(mut self) -> SafetyContext {
+        self.default_action = SeccompAction::Trap;
+        self
+    }
+
+    /// Let a supervisor decide whether each call to one of `syscalls` is allowed, via seccomp user
+    /// notifications (`SECCOMP_RET_USER_NOTIF`), instead of allowing or denying them in the filter.
+    ///
+    /// The filter must then be applied with [`apply_with_listener`](Self::apply_with_listener),
+    /// which returns the listener fd; the other `apply_*` functions return
+    /// [`ExtraSafeError::NotifyListenerRequired`]. The syscalls are allowed by the main filter,
+    /// and a second filter that sends them to the listener is installed alongside it. Syscalls
+    /// denied with [`deny`](Self::deny) stay denied.
+    ///
+    /// # Lifecycle of the listener fd
+    ///
+    /// When the sandboxed thread makes one of the syscalls, it blocks until the supervisor reads
+    /// the notification with the `SECCOMP_IOCTL_NOTIF_RECV` ioctl and responds with
+    /// `SECCOMP_IOCTL_NOTIF_SEND`, either with an error or return value, or with
+    /// `SECCOMP_USER_NOTIF_FLAG_CONTINUE` to let the kernel run the syscall. The supervisor must
+    /// run on another thread or in another process, e.g. after sending the fd over a unix socket,
+    /// since the sandboxed thread can't answer its own notifications. Once every copy of the fd
+    /// is closed, the notified syscalls fail with `ENOSYS`. The fd is not closed on exec.
+    ///
+    /// # Security Notes
+    ///
+    /// Pointer arguments can be changed by other threads after the supervisor has inspected
+    /// them, so the supervisor should not use `SECCOMP_USER_NOTIF_FLAG_CONTINUE` to allow a
+    /// syscall based on memory the sandboxed process can modify. Requires Linux 5.5 or later.
+    pub fn notify_on(mut self, syscalls: &[syscalls::Sysno]) -> SafetyContext {
+        self.notify.extend(syscalls);
+        self
+    }
+
+    /// Make each of the syscalls fail with the given errno, instead of being allowed or taking the
+    /// default action. Other syscalls are not affected.
+    ///
+    /// This is useful for getting libc to fall back to another syscall, e.g. returning `ENOSYS`
+    /// for `statx` so that glibc uses `fstat` instead, while keeping `EPERM` or
+    /// [`kill_process_on_violation`](Self::kill_process_on_violation) for everything else. The
+    /// syscalls fail even if a [`RuleSet`] enables them, but syscalls denied with
+    /// [`deny`](Self::deny) still take the default action. If a syscall is given more than once,
+    /// the last errno is used. Each errno must be between 1 and 4095, otherwise applying the
+    /// `SafetyContext` fails with [`ExtraSafeError::ErrnoOutOfRange`].
+    ///
+    /// Like [`notify_on`](Self::notify_on), this is implemented with a second seccomp filter
+    /// that is installed before the main filter, so it is not part of
+    /// [`compile_bpf`](Self::compile_bpf).
+    pub fn return_errno_for(mut self, errnos: &[(syscalls::Sysno, i32)]) -> SafetyContext {
+        self.errnos.extend(errnos.iter().copied());
         self
     }
 
@@ -304,87 +605,364 @@ impl SafetyContext {
 
     /// Enable the simple and conditional rules provided by the [`RuleSet`].
     ///
+    /// The same `RuleSet` may be enabled multiple times, e.g. with different subsets of its
+    /// functionality from different modules of your program, and the union of the rules is used.
+    /// Identical rules are only added once, and landlock rules for the same path from rulesets
+    /// with the same [`name`](RuleSet::name) are combined rather than raising an error.
+    ///
+    /// A syscall can't be allowed both conditionally and unconditionally, since the unconditional
+    /// rule would make the conditions meaningless. For example, the conditional `ioctl` and
+    /// `fcntl` rules of [`Terminal`](builtins::Terminal), [`FileLocking`](builtins::FileLocking)
+    /// and [`FdOps`](builtins::FdOps) conflict with
+    /// [`SystemIO::allow_ioctl`](builtins::SystemIO::allow_ioctl) and
+    /// [`Networking`](builtins::Networking), which allow both syscalls unconditionally.
+    ///
     /// # Errors
     /// Will return [`ExtraSafeError::ConditionalNoEffectError`] if a conditional rule is enabled at
     /// the same time as a simple rule for a syscall, which would override the conditional rule.
+    /// Use [`enable_with_precedence`](Self::enable_with_precedence) to resolve the conflict
+    /// instead.
     pub fn enable<R: RuleSet>(mut self, policy: R) -> Result<SafetyContext, ExtraSafeError> {
         #[cfg(feature = "landlock")]
         self.enable_landlock_rules(&policy)?;
 
-        self.enable_seccomp_rules(policy)?;
+        self.enable_seccomp_rules(policy, None)?;
 
         Ok(self)
     }
 
+    /// Enable each of the given [`RuleSet`]s in order, as with [`enable`](Self::enable).
+    ///
+    /// This is useful for enabling a collection of different rulesets, e.g. a
+    /// `Vec<Box<dyn RuleSet>>` built up at runtime.
+    ///
+    /// # Errors
+    /// Will return the first error returned by [`enable`](Self::enable).
+    pub fn enable_all<I>(self, policies: I) -> Result<SafetyContext, ExtraSafeError>
+    where I: IntoIterator, I::Item: RuleSet {
+        policies.into_iter()
+            .try_fold(self, SafetyContext::enable)
+    }
+
+    /// Enable the [`RuleSet`] as with [`enable`](Self::enable), but only if extrasafe is compiled
+    /// for `arch`, and do nothing otherwise. This is meant for rulesets that are only needed on
+    /// one architecture, e.g. for legacy syscalls that only exist on x86_64, so that the same
+    /// code sets up the `SafetyContext` everywhere.
+    ///
+    /// Filters are always compiled for the architecture extrasafe is compiled for, so the rules
+    /// are either added here or never. Note that the ruleset itself must still compile on every
+    /// architecture, i.e. syscalls that don't exist everywhere need to be behind `#[cfg]`s or
+    /// looked up by name.
+    ///
+    /// # Errors
+    /// Will return the same errors as [`enable`](Self::enable) if `arch` is the current
+    /// architecture.
+    pub fn enable_on_arch<R: RuleSet>(self, policy: R, arch: SeccompilerTargetArch)
+        -> Result<SafetyContext, ExtraSafeError> {
+        let native = SeccompilerTargetArch::try_from(std::env::consts::ARCH)
+            .expect("extrasafe is only supported on x86_64 and aarch64");
+        if arch != native {
+            return Ok(self);
+        }
+
+        self.enable(policy)
+    }
+
+    /// Allow the syscalls with the given names unconditionally, e.g. `["read", "write"]`, as if
+    /// they were enabled by a [`RuleSet`] named `SyscallNames`. This is meant for policies built
+    /// from external data, e.g. a list of syscalls recorded with `strace`; prefer the builtin
+    /// rulesets otherwise.
+    ///
+    /// # Errors
+    /// Will return [`ExtraSafeError::UnknownSyscall`] if any of the names is not a syscall on the
+    /// current architecture, in which case none of the syscalls are added. Will return
+    /// [`ExtraSafeError::ConditionalNoEffectError`] if one of the syscalls already has
+    /// conditional rules, as with [`enable`](Self::enable).
+    pub fn allow_syscall_names(mut self, names: &[&str]) -> Result<SafetyContext, ExtraSafeError> {
+        let syscalls = names.iter()
+            .map(|name| name.parse::<syscalls::Sysno>()
+                .map_err(|()| ExtraSafeError::UnknownSyscall((*name).to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rules = syscalls.into_iter()
+            .map(|syscall| LabeledSeccompRule("SyscallNames", SeccompRule::new(syscall)));
+        self.add_seccomp_rules(rules, None)?;
+
+        Ok(self)
+    }
+
+    /// Enable the simple and conditional rules provided by the [`RuleSet`], resolving any conflict
+    /// between a simple and a conditional rule for the same syscall according to `precedence`
+    /// rather than returning an error.
+    ///
+    /// With [`Precedence::Simple`], the syscall is allowed unconditionally and the conditional
+    /// rules are dropped, widening what is allowed. With [`Precedence::Conditional`], the simple
+    /// rule is dropped and the syscall stays restricted by the conditional rules. This applies
+    /// whether the conflicting rule comes from `policy` or from a previously enabled `RuleSet`.
+    ///
+    /// Neither is done implicitly: [`enable`](Self::enable) returns an error on such a conflict so
+    /// that widening a restricted syscall is always an explicit choice.
+    ///
+    /// # Errors
+    /// Will return an [`ExtraSafeError`] if the landlock rules conflict, as with
+    /// [`enable`](Self::enable).
+    pub fn enable_with_precedence<R: RuleSet>(mut self, policy: R, precedence: Precedence)
+        -> Result<SafetyContext, ExtraSafeError> {
+        #[cfg(feature = "landlock")]
+        self.enable_landlock_rules(&policy)?;
+
+        self.enable_seccomp_rules(policy, Some(precedence))?;
+
+        Ok(self)
+    }
+
+    /// Add all of the rules enabled in `other` to this `SafetyContext`, as if each of the
+    /// [`RuleSet`]s enabled in `other` had been enabled here.
+    ///
+    /// This can be used to build a baseline `SafetyContext` in one place, e.g. a library or
+    /// framework, and let other code add to it. Only the rules, including syscalls denied with
+    /// [`deny`](Self::deny), are added: other settings of `other`, like
+    /// [`with_errno`](Self::with_errno) or `landlock_only`, are ignored in
+    /// favor of the ones on `self`.
+    ///
+    /// # Errors
+    /// Will return [`ExtraSafeError::ConditionalNoEffectError`] if a conditional rule in one
+    /// `SafetyContext` conflicts with a simple rule in the other, and other errors as with
+    /// [`enable`](Self::enable).
+    pub fn extend(mut self, other: SafetyContext) -> Result<SafetyContext, ExtraSafeError> {
+        #[cfg(feature = "landlock")]
+        {
+            self.add_landlock_rules(other.landlock_rules.into_values())?;
+            self.landlock_net_rules.extend(other.landlock_net_rules);
+        }
+
+        self.add_seccomp_rules(other.seccomp_rules.into_values().flatten(), None)?;
+        for syscall in other.denied {
+            self = self.deny(syscall);
+        }
+        self.notify.extend(other.notify);
+        for (syscall, errno) in other.errnos {
+            let _existing = self.errnos.entry(syscall).or_insert(errno);
+        }
+
+        Ok(self)
+    }
+
+    /// Deny `syscall` even if it is allowed by an enabled [`RuleSet`], so that it falls through to
+    /// the default action, e.g. returning `EPERM`.
+    ///
+    /// This removes both simple and conditional rules for the syscall, including the ones added
+    /// by rulesets enabled later and by the
+    /// [`BasicCapabilities`](builtins::BasicCapabilities) that are enabled when the filter is
+    /// applied, so you can enable a broad ruleset and carve out a single syscall from it.
+    pub fn deny(mut self, syscall: syscalls::Sysno) -> SafetyContext {
+        let _removed = self.seccomp_rules.remove(&syscall);
+        let _inserted = self.denied.insert(syscall);
+        self
+    }
+
+    /// Remove the rules of `policy` from the rules enabled so far, e.g. to enable
+    /// [`SystemIO::everything`](builtins::SystemIO::everything) and then disable
+    /// `SystemIO::nothing().allow_unlink()`.
+    ///
+    /// Every syscall in the simple rules of `policy` is removed entirely, no matter which or how
+    /// many rulesets enabled it. Each conditional rule of `policy` only removes identical
+    /// conditional rules, so disabling `allow_stdout` keeps `allow_stderr`, and a syscall that is
+    /// allowed unconditionally stays allowed, since the difference can't be expressed as rules.
+    ///
+    /// Unlike [`deny`](Self::deny), this only affects the rules enabled so far: rulesets enabled
+    /// later and the [`BasicCapabilities`](builtins::BasicCapabilities) that are enabled when the
+    /// filter is applied can allow the syscalls again. Landlock rules are not removed.
+    pub fn disable<R: RuleSet>(mut self, policy: R) -> SafetyContext {
+        for syscall in policy.simple_rules() {
+            let _removed = self.seccomp_rules.remove(&syscall);
+        }
+
+        for (syscall, disabled_rules) in policy.conditional_rules() {
+            let Some(rules) = self.seccomp_rules.get_mut(&syscall) else {
+                continue;
+            };
+            rules.retain(|LabeledSeccompRule(_, rule)| {
+                rule.argument_filters.is_empty() || !disabled_rules.contains(rule)
+            });
+            if rules.is_empty() {
+                let _removed = self.seccomp_rules.remove(&syscall);
+            }
+        }
+
+        self
+    }
+
+    #[must_use]
+    /// Check a recorded trace of syscalls, e.g. from `strace`, against the currently enabled
+    /// rules without applying anything, and return the syscalls in the trace that may be denied,
+    /// in the order they first occur.
+    ///
+    /// Syscalls that are only allowed by conditional rules are always reported, since the trace
+    /// does not contain their arguments: they need to be inspected manually. The
+    /// [`BasicCapabilities`](builtins::BasicCapabilities) that are enabled when the filter is
+    /// applied are taken into account.
+    ///
+    /// Syscalls passed to [`return_errno_for`](Self::return_errno_for) always fail, and the
+    /// result of syscalls passed to [`notify_on`](Self::notify_on) is decided by the listener at
+    /// runtime, so both are reported even if a RuleSet allows them.
+    pub fn check_trace(&self, syscalls: &[syscalls::Sysno]) -> Vec<syscalls::Sysno> {
+        #[cfg(feature = "landlock")]
+        if self.only_landlock {
+            return Vec::new();
+        }
+
+        let basic = builtins::BasicCapabilities.simple_rules();
+        let mut denied = Vec::new();
+        for &syscall in syscalls {
+            let allowed = match self.seccomp_rules.get(&syscall) {
+                _ if self.errnos.contains_key(&syscall) || self.notify.contains(&syscall) => false,
+                // The rules for a syscall are either all simple or all conditional.
+                Some(rules) if !rules.is_empty() => rules.iter()
+                    .all(|labeled_rule| labeled_rule.1.argument_filters.is_empty()),
+                _ => basic.contains(&syscall) && !self.denied.contains(&syscall),
+            };
+            if !allowed && !denied.contains(&syscall) {
+                denied.push(syscall);
+            }
+        }
+
+        denied
+    }
+
     #[cfg(feature = "landlock")]
     fn enable_landlock_rules<R: RuleSet>(&mut self, policy: &R) -> Result<(), ExtraSafeError> {
         let name = policy.name();
         let rules = policy.landlock_rules().into_iter()
-            .map(|rule| (rule.path.clone(), LabeledLandlockRule(name, rule)));
+            .map(|rule| LabeledLandlockRule(name, rule));
+        self.add_landlock_rules(rules)?;
+
+        // Unlike paths, rules for the same port from different rulesets are just combined.
+        self.landlock_net_rules.extend(policy.landlock_net_rules());
+        Ok(())
+    }
 
-        for (path, labeled_rule) in rules {
-            if let Some(existing_rule) = self.landlock_rules.get(&path) {
+    #[cfg(feature = "landlock")]
+    fn add_landlock_rules<I>(&mut self, rules: I) -> Result<(), ExtraSafeError>
+    where I: IntoIterator<Item = LabeledLandlockRule> {
+        for labeled_rule in rules {
+            let path = labeled_rule.1.path.clone();
+            if let Some(existing_rule) = self.landlock_rules.get_mut(&path) {
+                // If the same ruleset is enabled multiple times, combine the access rights.
+                if existing_rule.0 == labeled_rule.0 {
+                    existing_rule.1.access_rules.insert(labeled_rule.1.access_rules);
+                    continue;
+                }
                 return Err(ExtraSafeError::DuplicatePath(path.clone(), existing_rule.0, labeled_rule.0));
             }
             // value here is always none because we checked above that we're not inserting a path
             // that already exists
             let _always_none = self.landlock_rules.insert(path, labeled_rule);
         }
+
         Ok(())
     }
 
-    fn enable_seccomp_rules<R: RuleSet>(&mut self, policy: R) -> Result<(), ExtraSafeError> {
+    /// Add the rules from `policy`. If `precedence` is `None`, conflicts between simple and
+    /// conditional rules return an error, otherwise the kind of rule it specifies is kept.
+    fn enable_seccomp_rules<R: RuleSet>(&mut self, policy: R, precedence: Option<Precedence>)
+        -> Result<(), ExtraSafeError> {
         let policy_name = policy.name();
-        let new_rules = SafetyContext::gather_rules(policy)
-            .into_iter()
+        let new_rules = SafetyContext::gather_rules(policy);
+        trace_event!(DEBUG,
+            ruleset = policy_name,
+            simple = new_rules.iter().filter(|rule| rule.argument_filters.is_empty()).count(),
+            conditional = new_rules.iter().filter(|rule| !rule.argument_filters.is_empty()).count(),
+            "extrasafe: enabled ruleset");
+        let new_rules = new_rules.into_iter()
             .map(|rule| LabeledSeccompRule(policy_name, rule));
 
+        self.add_seccomp_rules(new_rules, precedence)
+    }
+
+    /// Add already-labeled rules, checking for conflicts as described in
+    /// [`enable_seccomp_rules`](Self::enable_seccomp_rules).
+    fn add_seccomp_rules<I>(&mut self, new_rules: I, precedence: Option<Precedence>) -> Result<(), ExtraSafeError>
+    where I: IntoIterator<Item = LabeledSeccompRule> {
         for labeled_new_rule in new_rules {
             let new_rule = &labeled_new_rule.1;
             let syscall = &new_rule.syscall;
 
-            if let Some(existing_rules) = self.seccomp_rules.get(syscall) {
-                for labeled_existing_rule in existing_rules {
+            if self.denied.contains(syscall) {
+                continue;
+            }
+
+            if let Some(existing_rules) = self.seccomp_rules.get_mut(syscall) {
+                // The rules for a syscall must either be all simple (i.e. duplicates from
+                // different rulesets) or all conditional (e.g. multiple rules allowing read to be
+                // called on specific fds), so it is enough to check the first one.
+                if let Some(labeled_existing_rule) = existing_rules.first() {
                     let existing_rule = &labeled_existing_rule.1;
 
                     let new_is_simple = new_rule.argument_filters.is_empty();
                     let existing_is_simple = existing_rule.argument_filters.is_empty();
 
-                    // if one rule is conditional and the other is simple, let the user know there
-                    // would be a conflict and raise an error.
-                    if new_is_simple && !existing_is_simple {
-                        return Err(ExtraSafeError::ConditionalNoEffectError(
-                            new_rule.syscall,
-                            labeled_existing_rule.0,
-                            labeled_new_rule.0,
-                        ));
-                    }
-                    else if !new_is_simple && existing_is_simple {
-                        return Err(ExtraSafeError::ConditionalNoEffectError(
-                            new_rule.syscall,
-                            labeled_new_rule.0,
-                            labeled_existing_rule.0,
-                        ));
+                    // if one rule is conditional and the other is simple, either let the user
+                    // know there would be a conflict and raise an error, or keep whichever kind
+                    // of rule the user asked for.
+                    if new_is_simple != existing_is_simple {
+                        let keep_new = match precedence {
+                            None if new_is_simple => return Err(ExtraSafeError::ConditionalNoEffectError(
+                                new_rule.syscall,
+                                labeled_existing_rule.0,
+                                labeled_new_rule.0,
+                                existing_rule.clone(),
+                            )),
+                            None => return Err(ExtraSafeError::ConditionalNoEffectError(
+                                new_rule.syscall,
+                                labeled_new_rule.0,
+                                labeled_existing_rule.0,
+                                new_rule.clone(),
+                            )),
+                            Some(Precedence::Simple) => new_is_simple,
+                            Some(Precedence::Conditional) => !new_is_simple,
+                        };
+
+                        if !keep_new {
+                            continue;
+                        }
+                        existing_rules.clear();
                     }
-                    // otherwise, they're either both conditional rules or both simple rules,
-                    // in which case we continue to check the existing filters, and then add the
-                    // rules to our filter as normal if all checks pass.
-                    //
-                    // In the end, the rules for a syscall must either be all simple (i.e.
-                    // duplicates from different rulesets) or all conditional (e.g. multiple rules
-                    // allowing read to be called on specific fds)
                 }
             }
 
-            self.seccomp_rules
+            let existing_rules = self.seccomp_rules
                 .entry(*syscall)
-                .or_insert_with(Vec::new)
-                .push(labeled_new_rule);
+                .or_insert_with(Vec::new);
+
+            // If the same ruleset is enabled multiple times, don't add the same rule twice.
+            let is_duplicate = existing_rules.iter()
+                .any(|labeled_existing_rule| labeled_existing_rule.0 == labeled_new_rule.0
+                    && labeled_existing_rule.1 == labeled_new_rule.1);
+            if !is_duplicate {
+                existing_rules.push(labeled_new_rule);
+            }
         }
 
         Ok(())
     }
 
+    #[must_use]
+    /// Returns the set of syscalls that are allowed by the rules enabled so far, either
+    /// unconditionally or with argument filters.
+    ///
+    /// Note that this does not include the syscalls from
+    /// [`BasicCapabilities`](builtins::BasicCapabilities), which are only added when the
+    /// `SafetyContext` is applied, nor the syscalls passed to
+    /// [`return_errno_for`](Self::return_errno_for) or [`notify_on`](Self::notify_on), which
+    /// fail or are decided by the listener even if a RuleSet allows them.
+    pub fn enabled_syscalls(&self) -> BTreeSet<syscalls::Sysno> {
+        self.seccomp_rules.keys()
+            .filter(|syscall| !self.errnos.contains_key(syscall) && !self.notify.contains(syscall))
+            .copied()
+            .collect()
+    }
+
     #[cfg(feature = "landlock")]
     /// Do not use seccomp at all, and only enable landlock filters.
     pub fn landlock_only(mut self) -> SafetyContext {
@@ -392,6 +970,43 @@ impl SafetyContext {
         self
     }
 
+    #[cfg(feature = "landlock")]
+    #[must_use]
+    /// Returns the Landlock ABI version supported by the running kernel, or `None` if Landlock is
+    /// not supported or not enabled.
+    ///
+    /// Each ABI version adds new access rights, e.g. ABI v4 adds TCP bind and connect rules. The
+    /// filesystem rules used by extrasafe require ABI v2.
+    pub fn landlock_abi() -> Option<ABI> {
+        current_abi()
+    }
+
+    #[cfg(feature = "landlock")]
+    /// Require the running kernel to support at least the given Landlock ABI version, otherwise
+    /// applying the `SafetyContext` will fail with [`ExtraSafeError::LandlockAbiUnsupported`]
+    /// before any filters are applied.
+    ///
+    /// Use this if partial enforcement of your landlock rules is not acceptable, or to make sure
+    /// Landlock is available at all.
+    pub fn require_landlock_abi(mut self, abi: ABI) -> SafetyContext {
+        self.required_landlock_abi = Some(abi);
+        self
+    }
+
+    /// Fail closed if Landlock would not be enforced: applying the `SafetyContext` fails with
+    /// [`ExtraSafeError::LandlockRequired`] before any filters are applied if the `landlock`
+    /// feature is disabled, the running kernel does not support Landlock, or no landlock rules
+    /// were enabled. It also fails, before any seccomp filters are applied, if the landlock rules
+    /// are not fully enforced when they are applied.
+    ///
+    /// Unlike `require_landlock_abi`, this is available without the
+    /// `landlock` feature, so that code relying on landlock for its filesystem restrictions
+    /// doesn't silently run without them when built without it.
+    pub fn require_landlock(mut self) -> SafetyContext {
+        self.require_landlock = true;
+        self
+    }
+
     // TODO: unused, need to figure out a good way to do this without clasing with the existing
     // seccomp argument-filtered/not-filtered checks
     // #[cfg(feature = "landlock")]
@@ -441,9 +1056,56 @@ impl SafetyContext {
     /// If no rulesets are enabled, returns an `ExtraSafeError::NoRulesEnabled` error. If you
     /// really want to enable "nothing", try enabling the `builtins::BasicCapabilities` default
     /// ruleset manually, or create your own with e.g. just the `exit` syscall.
-    pub fn apply_to_current_thread(mut self) -> Result<(), ExtraSafeError> {
-        self.all_threads = false;
-        self.apply()
+    pub fn apply_to_current_thread(self) -> Result<(), ExtraSafeError> {
+        self.into_filter()?
+            .apply_to_current_thread()
+    }
+
+    /// Apply the [`SafetyContext`] to the current thread on top of any filters that are already
+    /// attached to it, e.g. a framework's filter followed by an application's filter.
+    ///
+    /// This applies the rules the same way as [`apply_to_current_thread`](Self::apply_to_current_thread),
+    /// which always stacks onto existing filters, and exists to make layered sandboxing explicit.
+    /// Seccomp runs every attached filter and the most restrictive action wins, so the resulting
+    /// enforcement is the intersection of all stacked filters: a syscall is only allowed if every
+    /// filter allows it. Since filters can't be removed, a later context can only narrow what is
+    /// allowed. The filters applied first must allow the `seccomp` syscall (and `prctl`, unless
+    /// [`set_no_new_privs`](Self::set_no_new_privs) is disabled), otherwise the later filters
+    /// can't be installed.
+    ///
+    /// Use [`seccomp_filter_count`] to check how many filters are attached.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`apply_to_current_thread`](Self::apply_to_current_thread).
+    pub fn apply_stacked_onto_current_thread(self) -> Result<(), ExtraSafeError> {
+        self.apply_to_current_thread()
+    }
+
+    /// Apply the [`SafetyContext`] to the current thread and then run `f`, returning its result.
+    ///
+    /// This is a shorthand for the common pattern of applying a filter and then running the code
+    /// that should be sandboxed. Note that seccomp filters can't be removed, so the filter stays
+    /// applied to the current thread after `f` returns, or if it panics. To sandbox only `f`, call
+    /// this on a new thread. If the filter can't be applied, `f` is not run.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`apply_to_current_thread`](Self::apply_to_current_thread).
+    pub fn run<F, R>(self, f: F) -> Result<R, ExtraSafeError>
+    where F: FnOnce() -> R {
+        self.apply_to_current_thread()?;
+        Ok(f())
+    }
+
+    /// Apply the [`SafetyContext`] to the current thread like
+    /// [`apply_to_current_thread`](Self::apply_to_current_thread), and return the listener fd for
+    /// the syscalls marked with [`notify_on`](Self::notify_on). See `notify_on` for how to use it.
+    ///
+    /// # Errors
+    /// Returns `ExtraSafeError::NoNotifySyscalls` if no syscalls were marked with `notify_on`, and
+    /// otherwise the same errors as [`apply_to_current_thread`](Self::apply_to_current_thread).
+    pub fn apply_with_listener(self) -> Result<std::os::fd::OwnedFd, ExtraSafeError> {
+        self.into_filter()?
+            .apply_with_listener()
     }
 
     /// Load the [`SafetyContext`]'s rules into a seccomp filter and apply the filter to all threads in
@@ -460,29 +1122,43 @@ impl SafetyContext {
     /// If no rulesets are enabled, returns an `ExtraSafeError::NoRulesEnabled` error. If you
     /// really want to enable "nothing", try enabling the `builtins::BasicCapabilities` default
     /// ruleset manually, or create your own with e.g. just the `exit` syscall.
-    pub fn apply_to_all_threads(mut self) -> Result<(), ExtraSafeError> {
+    ///
+    /// If another thread already applied a filter only to itself, the filter can't be synced to
+    /// it and an `ExtraSafeError::ThreadSyncFailed` error with that thread's id is returned. In
+    /// that case, the filter is not applied to any thread.
+    pub fn apply_to_all_threads(self) -> Result<(), ExtraSafeError> {
         #[cfg(feature = "landlock")]
-        if !self.landlock_rules.is_empty() {
+        if !self.landlock_rules.is_empty() || !self.landlock_net_rules.is_empty() {
             return Err(ExtraSafeError::LandlockNoThreadSync);
         }
-        self.all_threads = true;
-        self.apply()
+        self.into_filter()?
+            .apply_to_all_threads()
     }
 
-    /// Actually do the application of the rules. If `self.all_threads` is True, applies the rules to
-    /// all threads via seccomp tsync. If `self.only_landlock` is True, only applies landlock rules.
+    /// Compile the [`SafetyContext`]'s rules into a [`CompiledFilter`] without applying it, so that
+    /// it can be applied later or on a different thread.
     ///
-    /// If the landlock feature is enabled but no landlock rules are applied, landlock is not
-    /// enabled, unless `landlock_only()` is called. To enable landlock but allow no file access,
-    /// you can first apply a `landlock_only()` `SafetyContext`, and then apply a separate
-    /// `SafetyContext` with your seccomp rules.
+    /// A seccomp filter can only be installed by a thread on itself (or on all threads of the
+    /// process at once), so there is no way to apply a filter to another specific thread from the
+    /// outside. Instead, compile the filter in the controlling thread and send it to the worker
+    /// thread, which then calls [`CompiledFilter::apply_to_current_thread`] itself before running
+    /// any untrusted code. Any threads the worker spawns afterwards inherit the filter.
     ///
-    /// If no rulesets are enabled, returns an `ExtraSafeError::NoRulesEnabled` error. If you
-    /// really want to enable "nothing", try enabling the `builtins::BasicCapabilities` default
-    /// ruleset manually, or create your own with e.g. just the `exit` syscall.
-    fn apply(mut self) -> Result<(), ExtraSafeError> {
+    /// # Errors
+    /// Returns the same errors as [`apply_to_current_thread`](Self::apply_to_current_thread) that
+    /// occur before the filter is loaded, e.g. `ExtraSafeError::NoRulesEnabled`.
+    pub fn into_filter(self) -> Result<CompiledFilter, ExtraSafeError> {
+        let native = SeccompilerTargetArch::try_from(std::env::consts::ARCH)
+            .expect("extrasafe is only supported on x86_64 and aarch64");
+        self.into_filter_for_arch(native)
+    }
+
+    /// Compile the rules as with [`into_filter`](Self::into_filter), but with the main seccomp
+    /// filter built for `arch`. The syscall numbers are always those of the current architecture,
+    /// so a filter for another architecture is only useful to estimate its size.
+    fn into_filter_for_arch(mut self, arch: SeccompilerTargetArch) -> Result<CompiledFilter, ExtraSafeError> {
         #[cfg(feature = "landlock")]
-        if self.seccomp_rules.is_empty() && self.landlock_rules.is_empty() {
+        if self.seccomp_rules.is_empty() && self.landlock_rules.is_empty() && self.landlock_net_rules.is_empty() {
             return Err(ExtraSafeError::NoRulesEnabled);
         }
         #[cfg(not(feature = "landlock"))]
@@ -490,24 +1166,221 @@ impl SafetyContext {
             return Err(ExtraSafeError::NoRulesEnabled);
         }
 
-        self = self.enable(builtins::BasicCapabilities)?;
+        if let SeccompAction::Errno(errno) = self.default_action {
+            if errno > SECCOMP_RET_DATA {
+                return Err(ExtraSafeError::InvalidErrno(errno));
+            }
+        }
+        let errnos = self.errnos.iter()
+            .map(|(&syscall, &errno)| if (1..=MAX_ERRNO).contains(&errno) {
+                Ok((syscall, errno.unsigned_abs()))
+            } else {
+                Err(ExtraSafeError::ErrnoOutOfRange(syscall, errno))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         #[cfg(feature = "landlock")]
-        if self.only_landlock {
-            return self.apply_landlock_rules();
+        if let Some(required) = self.required_landlock_abi {
+            let available = current_abi();
+            if available.is_none_or(|available| available < required) {
+                return Err(ExtraSafeError::LandlockAbiUnsupported(required, available));
+            }
+        }
+
+        if self.require_landlock {
+            #[cfg(feature = "landlock")]
+            let enforced = current_abi().is_some()
+                && (!self.landlock_rules.is_empty() || !self.landlock_net_rules.is_empty());
+            #[cfg(not(feature = "landlock"))]
+            let enforced = false;
+
+            if !enforced {
+                return Err(ExtraSafeError::LandlockRequired);
+            }
         }
+
+        if self.default_action == SeccompAction::Trap {
+            // Allow the trap handler to log to stderr. If `write` is already allowed
+            // unconditionally, the conditional rule is not needed and is dropped.
+            let rule = SeccompRule::new(syscalls::Sysno::write)
+                .and_condition(seccomp_arg_filter!(arg0 == 2));
+            self.add_seccomp_rules([LabeledSeccompRule("TrapOnViolation", rule)], Some(Precedence::Simple))?;
+        }
+
+        let notify: Vec<_> = self.notify.iter().copied().collect();
+        let rules = notify.iter()
+            .map(|&syscall| LabeledSeccompRule("NotifyOn", SeccompRule::new(syscall)));
+        self.add_seccomp_rules(rules, Some(Precedence::Simple))?;
+        let notify = (!notify.is_empty()).then(|| seccomp::user_notif_program(&notify));
+
+        // The main filter allows the syscalls, and the errno filter makes them fail.
+        let rules = errnos.iter()
+            .map(|&(syscall, _)| LabeledSeccompRule("ReturnErrno", SeccompRule::new(syscall)));
+        self.add_seccomp_rules(rules, Some(Precedence::Simple))?;
+        let errno = (!errnos.is_empty()).then(|| seccomp::errno_program(&errnos));
+
+        self.enable_basic_capabilities();
+
         // If no landlock rules, do not try to apply them since it would prevent all filesystem
         // access.
-        else if self.landlock_rules.is_empty() {
-            return self.apply_seccomp_rules();
+        #[cfg(feature = "landlock")]
+        let landlock = (self.only_landlock || !self.landlock_rules.is_empty() || !self.landlock_net_rules.is_empty())
+            .then(|| LandlockRules {
+                paths: self.landlock_rules.values()
+                    .map(|LabeledLandlockRule(_policy_name, rule)| rule.clone())
+                    .collect(),
+                ports: self.landlock_net_rules.clone(),
+                required: self.require_landlock || self.required_landlock_abi.is_some(),
+            });
+
+        let mut flags = 0;
+        if self.allow_speculation {
+            flags |= libc::SECCOMP_FILTER_FLAG_SPEC_ALLOW;
+        }
+        if self.log_violations {
+            flags |= libc::SECCOMP_FILTER_FLAG_LOG;
         }
+        let no_new_privs = self.no_new_privs;
+        trace_event!(DEBUG, syscalls = self.seccomp_rules.len(), "extrasafe: compiling filter");
+
+        #[cfg(feature = "landlock")]
+        let seccomp = if self.only_landlock { None } else { Some(self.compile_seccomp_rules(arch)?) };
+        #[cfg(not(feature = "landlock"))]
+        let seccomp = Some(self.compile_seccomp_rules(arch)?);
+
+        Ok(CompiledFilter {
+            seccomp,
+            notify,
+            errno,
+            flags,
+            no_new_privs,
+            #[cfg(feature = "landlock")]
+            landlock,
+        })
+    }
 
+    /// Spawn `cmd` as a child process with the [`SafetyContext`]'s rules applied to it, without
+    /// applying them to the current process.
+    ///
+    /// The filter is compiled in the current process and installed in the child after forking,
+    /// just before `exec` is called, using only the `prctl` and `seccomp` syscalls so that nothing
+    /// is allocated in the child. Since seccomp filters are inherited across `exec`, the new
+    /// program runs under the filter from its first instruction. For the same reason, the
+    /// `execve` syscall is allowed in addition to the enabled rules, otherwise the child could not
+    /// start the program at all. The new program then needs e.g. [`SystemIO`](builtins::SystemIO)
+    /// rules to open its dynamic loader and libraries.
+    ///
+    /// # Errors
+    /// Returns an error if the filter could not be compiled, or if spawning the child failed,
+    /// including if the filter could not be applied in the child. Returns an
+    /// `ExtraSafeError::ConditionalNoEffectError` if `execve` is restricted by conditional rules,
+    /// `ExtraSafeError::NotifyListenerRequired` if syscalls were marked with
+    /// [`notify_on`](Self::notify_on), and `ExtraSafeError::LandlockNoSpawn` if there are
+    /// landlock rules, since applying them would allocate in the child. All of these are wrapped
+    /// in an `std::io::Error`.
+    ///
+    /// If `execve` fails in the child, e.g. because the program does not exist, the child reports
+    /// the error to the parent with `write` on a pipe, after the filter is applied. If the filter
+    /// does not allow `write` on that pipe, the report fails or the child is killed, depending on
+    /// the violation action, and this returns `Ok` for a child that never ran the program. It
+    /// then exits with a non-zero status or is killed by a signal.
+    #[allow(unsafe_code)]
+    pub fn spawn_sandboxed(mut self, mut cmd: std::process::Command) -> std::io::Result<std::process::Child> {
+        use std::os::unix::process::CommandExt;
+
+        let execve = LabeledSeccompRule("SpawnSandboxed", SeccompRule::new(syscalls::Sysno::execve));
+        self.add_seccomp_rules([execve], None)
+            .map_err(std::io::Error::other)?;
+        let filter = self.into_filter()
+            .map_err(std::io::Error::other)?;
+
+        if filter.notify.is_some() {
+            return Err(std::io::Error::other(ExtraSafeError::NotifyListenerRequired));
+        }
         #[cfg(feature = "landlock")]
-        self.apply_landlock_rules()?;
-        self.apply_seccomp_rules()
+        if filter.landlock.is_some() {
+            return Err(std::io::Error::other(ExtraSafeError::LandlockNoSpawn));
+        }
+
+        filter.trace_apply("spawn_sandboxed");
+        let CompiledFilter { seccomp, errno, flags, no_new_privs, .. } = filter;
+        let bpf_filters: Vec<_> = [errno, seccomp].into_iter().flatten().collect();
+
+        // SAFETY: the closure runs in the child after fork, where only async-signal-safe
+        // operations are allowed. The filters were built beforehand, and installing them only
+        // makes the prctl and seccomp syscalls without allocating.
+        unsafe {
+            let _cmd = cmd.pre_exec(move || {
+                seccomp::apply_filters_after_fork(&bpf_filters, flags, no_new_privs)
+            });
+        }
+
+        cmd.spawn()
+    }
+
+    /// Compile the seccomp filter the same way as [`into_filter`](Self::into_filter) and return
+    /// its length in BPF instructions, without applying it.
+    ///
+    /// The kernel rejects filters longer than 4096 instructions (`BPF_MAXINSNS`), so this can be
+    /// used to check in tests that a policy with many conditional rules stays well under the
+    /// limit.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`into_filter`](Self::into_filter).
+    pub fn filter_len(&self) -> Result<usize, ExtraSafeError> {
+        Ok(self.compile_bpf()?.len())
+    }
+
+    /// Compile the seccomp filter the same way as [`into_filter`](Self::into_filter) and return
+    /// the BPF program without applying it, e.g. to cache it or load it with `prctl` elsewhere.
+    ///
+    /// [`sock_filter`] has the same layout as the kernel's `struct sock_filter`. Note that
+    /// landlock rules and the flags set by [`allow_speculation`](Self::allow_speculation) are not
+    /// part of the program. Returns an empty program if only landlock rules would be applied.
+    ///
+    /// # Errors
+    /// Returns the same errors as [`into_filter`](Self::into_filter).
+    pub fn compile_bpf(&self) -> Result<Vec<sock_filter>, ExtraSafeError> {
+        let filter = self.clone().into_filter()?;
+        Ok(filter.seccomp.unwrap_or_default())
     }
 
-    fn apply_seccomp_rules(self) -> Result<(), ExtraSafeError> {
+    /// Enable the [`BasicCapabilities`](builtins::BasicCapabilities) ruleset, except for syscalls
+    /// that have already been restricted with conditional rules, so that e.g. a `mmap` restricted
+    /// by [`Memory`](builtins::Memory) is not overridden, and syscalls that have been denied.
+    fn enable_basic_capabilities(&mut self) {
+        let basic = builtins::BasicCapabilities;
+        let name = basic.name();
+        for syscall in basic.simple_rules() {
+            if self.denied.contains(&syscall) {
+                continue;
+            }
+
+            let rules = self.seccomp_rules.entry(syscall)
+                .or_insert_with(Vec::new);
+
+            // The rules for a syscall are either all simple or all conditional, so we only need to
+            // check the first one.
+            let is_restricted = rules.first()
+                .is_some_and(|labeled_rule| !labeled_rule.1.argument_filters.is_empty());
+            if !is_restricted {
+                rules.push(LabeledSeccompRule(name, SeccompRule::new(syscall)));
+            }
+        }
+    }
+
+    fn compile_seccomp_rules(self, arch: SeccompilerTargetArch) -> Result<seccompiler::BpfProgram, ExtraSafeError> {
+        // Without any conditional rules, the filter is just a list of syscall numbers, which we
+        // can build more compactly than seccompiler.
+        let is_simple_only = self.seccomp_rules.values()
+            .flatten()
+            .all(|labeled_rule| labeled_rule.1.argument_filters.is_empty());
+        if is_simple_only {
+            let mut syscalls: Vec<syscalls::Sysno> = self.seccomp_rules.into_keys().collect();
+            syscalls.sort();
+            return Ok(seccomp::allowlist_program(&syscalls, self.default_action.into(), arch));
+        }
+
         // Turn our internal HashMap into a BTreeMap for seccompiler, being careful to avoid
         // https://github.com/rust-vmm/seccompiler/issues/42 i.e. don't use BTreeMap's collect impl
         // because it will ignore duplicates.
@@ -517,8 +1390,12 @@ impl SafetyContext {
         for (syscall, labeled_rules) in self.seccomp_rules {
             let syscall = syscall.id().into();
 
+            let rules = labeled_rules.into_iter()
+                .map(|LabeledSeccompRule(_origin, rule)| rule)
+                .collect();
+
             let mut seccompiler_rules = Vec::new();
-            for LabeledSeccompRule(_origin, rule) in labeled_rules {
+            for rule in coalesce_rules(rules) {
                 // If there are conditional rules, insert them to the vec
                 if let Some(seccompiler_rule) = rule.into_seccompiler()? {
                     seccompiler_rules.push(seccompiler_rule);
@@ -530,44 +1407,202 @@ impl SafetyContext {
             assert!(result.is_none(), "extrasafe logic error: somehow inserted the same syscall's rules twice");
         }
 
-        #[cfg(not(all(target_arch = "x86_64", target_os = "linux")))]
-        compile_error!("extrasafe is currently only supported on linux x86_64");
+        #[cfg(not(all(any(target_arch = "x86_64", target_arch = "aarch64"), target_os = "linux")))]
+        compile_error!("extrasafe is currently only supported on linux x86_64 and aarch64");
 
         let seccompiler_filter = SeccompilerFilter::new(
             rules_map,
-            SeccompAction::Errno(self.errno),
+            self.default_action,
             SeccompAction::Allow,
-            std::env::consts::ARCH.try_into().expect("invalid arches are prevented above"),
+            arch,
         )?;
 
-        let bpf_filter: seccompiler::BpfProgram = seccompiler_filter.try_into()?;
+        Ok(seccompiler_filter.try_into()?)
+    }
+}
+
+/// Remove rules for a single syscall that can't change whether a call is allowed, so that they
+/// don't take up space in the compiled filter.
+///
+/// The rules are OR-ed together and each rule's conditions are AND-ed, so a rule whose conditions
+/// include all of another rule's conditions allows a subset of what the other rule allows and can
+/// be dropped, e.g. `arg0 == AF_UNIX && arg1 == SOCK_STREAM` is covered by `arg0 == AF_UNIX`. This
+/// also removes duplicate rules from different rulesets. The allowed arguments are exactly the
+/// same afterwards, and the order of the remaining rules is kept.
+fn coalesce_rules(rules: Vec<SeccompRule>) -> Vec<SeccompRule> {
+    let covers = |rule: &SeccompRule, other: &SeccompRule| rule.argument_filters.iter()
+        .all(|filter| other.argument_filters.contains(filter));
+
+    rules.iter().enumerate()
+        .filter(|&(i, rule)| {
+            !rules.iter().enumerate().any(|(j, other)| {
+                // Of two rules that cover each other, keep the first.
+                j != i && covers(other, rule) && (j < i || !covers(rule, other))
+            })
+        })
+        .map(|(_, rule)| rule.clone())
+        .collect()
+}
+
+#[must_use]
+#[derive(Debug, Clone)]
+/// The rules of a [`SafetyContext`], compiled and ready to be applied. Create with
+/// [`SafetyContext::into_filter`].
+///
+/// A `CompiledFilter` can be sent to another thread and applied there, and can be applied any
+/// number of times, e.g. once on each worker thread in a pool.
+pub struct CompiledFilter {
+    /// The compiled seccomp filter, or `None` if only landlock rules should be applied.
+    seccomp: Option<seccompiler::BpfProgram>,
+    /// The filter sending syscalls to the user notification listener, or `None` if there are none.
+    notify: Option<seccompiler::BpfProgram>,
+    /// The filter returning specific errnos for some syscalls, or `None` if there are none.
+    errno: Option<seccompiler::BpfProgram>,
+    /// The `SECCOMP_FILTER_FLAG_*` flags to install the seccomp filter with.
+    flags: libc::c_ulong,
+    /// Whether to set `PR_SET_NO_NEW_PRIVS` before installing the filters.
+    no_new_privs: bool,
+    #[cfg(feature = "landlock")]
+    /// The landlock rules to apply, or `None` if landlock should not be enabled.
+    landlock: Option<LandlockRules>,
+}
 
-        if self.all_threads {
-            seccompiler::apply_filter_all_threads(&bpf_filter)?;
+impl CompiledFilter {
+    #[must_use]
+    /// The `SECCOMP_FILTER_FLAG_*` flags the seccomp filter is installed with, e.g.
+    /// `SECCOMP_FILTER_FLAG_LOG` if [`SafetyContext::log_violations`] was used. Flags that depend
+    /// on how the filter is applied, like `SECCOMP_FILTER_FLAG_TSYNC`, are not included.
+    pub fn flags(&self) -> libc::c_ulong {
+        self.flags
+    }
+
+    /// Apply the filter to the current thread.
+    ///
+    /// # Errors
+    /// Returns `ExtraSafeError::NotifyListenerRequired` if syscalls were marked with
+    /// [`SafetyContext::notify_on`], and may return another [`ExtraSafeError`] if the filter could
+    /// not be loaded.
+    pub fn apply_to_current_thread(&self) -> Result<(), ExtraSafeError> {
+        if self.notify.is_some() {
+            return Err(ExtraSafeError::NotifyListenerRequired);
         }
-        else {
-            seccompiler::apply_filter(&bpf_filter)?;
+
+        #[cfg(feature = "landlock")]
+        if let Some(landlock) = &self.landlock {
+            landlock.restrict_self(self.no_new_privs)?;
+        }
+
+        self.trace_apply("current_thread");
+        if let Some(errno) = &self.errno {
+            seccomp::apply_filter_with_flags(errno, self.flags, self.no_new_privs)?;
+        }
+        if let Some(bpf_filter) = &self.seccomp {
+            seccomp::apply_filter_with_flags(bpf_filter, self.flags, self.no_new_privs)?;
         }
 
         Ok(())
     }
 
-    #[cfg(feature = "landlock")]
-    fn apply_landlock_rules(&self) -> Result<(), ExtraSafeError> {
-	let abi = ABI::V2;
-	let mut landlock_ruleset = Ruleset::default()
-            .set_compatibility(CompatLevel::HardRequirement)
-	    .handle_access(AccessFs::from_all(abi))?
-	    .create()?;
-
-        for LabeledLandlockRule(_policy_name, rule) in self.landlock_rules.values() {
-            // If path does not exist or is not accessible, just ignore it
-            if let Ok(fd) = PathFd::new(rule.path.clone()) {
-                let path_beneath = PathBeneath::new(fd, rule.access_rules);
-                landlock_ruleset = landlock_ruleset.add_rule(path_beneath)?;
+    /// Apply the filter to the current thread and return the listener fd for user notifications.
+    /// See [`SafetyContext::notify_on`].
+    ///
+    /// # Errors
+    /// Returns `ExtraSafeError::NoNotifySyscalls` if no syscalls were marked with
+    /// [`SafetyContext::notify_on`], and may return another [`ExtraSafeError`] if the filter could
+    /// not be loaded.
+    pub fn apply_with_listener(&self) -> Result<std::os::fd::OwnedFd, ExtraSafeError> {
+        let Some(notify) = &self.notify else {
+            return Err(ExtraSafeError::NoNotifySyscalls);
+        };
+
+        #[cfg(feature = "landlock")]
+        if let Some(landlock) = &self.landlock {
+            landlock.restrict_self(self.no_new_privs)?;
+        }
+
+        // The notification filter allows all other syscalls, including the ones needed to
+        // install the main filter afterwards.
+        self.trace_apply("with_listener");
+        let listener = seccomp::apply_filter_with_listener(notify, self.flags, self.no_new_privs)?;
+
+        if let Some(errno) = &self.errno {
+            seccomp::apply_filter_with_flags(errno, self.flags, self.no_new_privs)?;
+        }
+
+        if let Some(bpf_filter) = &self.seccomp {
+            seccomp::apply_filter_with_flags(bpf_filter, self.flags, self.no_new_privs)?;
+        }
+
+        Ok(listener)
+    }
+
+    /// Return a closure that applies the filter to the thread it is called on, for use as a
+    /// thread start hook, e.g. tokio's `runtime::Builder::on_thread_start`.
+    ///
+    /// This is a way to sandbox every thread of a thread pool or async runtime without the race
+    /// between [`apply_to_all_threads`](Self::apply_to_all_threads) and threads that are still
+    /// being started. The filter is only compiled once, and applied by each thread as it starts,
+    /// before it runs any tasks. Note that tokio also calls the hook on its blocking pool
+    /// threads, but not on the thread that calls `block_on`.
+    ///
+    /// # Panics
+    /// The closure panics if the filter can't be applied, since the hook can't return an error
+    /// and the thread must not continue unsandboxed.
+    pub fn thread_start_hook(self) -> impl Fn() + Send + Sync + 'static {
+        move || {
+            if let Err(err) = self.apply_to_current_thread() {
+                panic!("extrasafe: failed to apply filter in thread start hook: {}", err);
             }
         }
-	let _status = landlock_ruleset.restrict_self();
+    }
+
+    /// Apply the filter to all threads in this process.
+    ///
+    /// # Errors
+    /// Returns `ExtraSafeError::LandlockNoThreadSync` if there are landlock rules,
+    /// `ExtraSafeError::NotifyListenerRequired` if syscalls were marked with
+    /// [`SafetyContext::notify_on`], `ExtraSafeError::ThreadSyncFailed` with the id of the
+    /// offending thread if another thread has an incompatible filter, and may return another
+    /// [`ExtraSafeError`] if the filter could not be loaded.
+    pub fn apply_to_all_threads(&self) -> Result<(), ExtraSafeError> {
+        if self.notify.is_some() {
+            return Err(ExtraSafeError::NotifyListenerRequired);
+        }
+
+        #[cfg(feature = "landlock")]
+        if self.landlock.is_some() {
+            return Err(ExtraSafeError::LandlockNoThreadSync);
+        }
+
+        self.trace_apply("all_threads");
+        let thread_sync_error = |err| match err {
+            seccompiler::Error::ThreadSync(tid) => libc::pid_t::try_from(tid)
+                .map_or(ExtraSafeError::SeccompError(err), ExtraSafeError::ThreadSyncFailed),
+            err => ExtraSafeError::SeccompError(err),
+        };
+        for bpf_filter in [&self.errno, &self.seccomp].into_iter().flatten() {
+            seccomp::apply_filter_with_flags(bpf_filter, self.flags | libc::SECCOMP_FILTER_FLAG_TSYNC, self.no_new_privs)
+                .map_err(thread_sync_error)?;
+        }
+
         Ok(())
     }
+
+    /// Emit a `tracing` event describing the filter that is about to be applied, if the `tracing`
+    /// feature is enabled. This is done before loading the seccomp filter, so that the subscriber
+    /// is not restricted by it.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn trace_apply(&self, target: &'static str) {
+        #[cfg(feature = "landlock")]
+        let landlock = self.landlock.is_some();
+        #[cfg(not(feature = "landlock"))]
+        let landlock = false;
+
+        trace_event!(INFO,
+            target,
+            instructions = self.seccomp.as_ref().map_or(0, Vec::len),
+            notify = self.notify.is_some(),
+            landlock,
+            "extrasafe: applying filter");
+    }
 }
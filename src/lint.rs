@@ -0,0 +1,101 @@
+//! Checks for rules that are allowed by a [`SafetyContext`] but don't have the intended effect.
+
+use std::fmt;
+
+use syscalls::Sysno;
+
+use crate::{SafetyContext, SeccompRule};
+
+/// Syscalls that can be used interchangeably, so that restricting one of them with conditional
+/// rules has no effect if another one is allowed unconditionally.
+const EQUIVALENT_SYSCALLS: &[&[Sysno]] = &[
+    &[
+        #[cfg(target_arch = "x86_64")] Sysno::open,
+        #[cfg(target_arch = "x86_64")] Sysno::creat,
+        Sysno::openat, Sysno::openat2,
+    ],
+    &[Sysno::read, Sysno::readv, Sysno::pread64, Sysno::preadv, Sysno::preadv2],
+    &[Sysno::write, Sysno::writev, Sysno::pwrite64, Sysno::pwritev, Sysno::pwritev2],
+];
+
+#[derive(Debug, Clone, PartialEq)]
+/// A potential mistake in a [`SafetyContext`] that does not prevent it from being applied.
+/// Returned by [`SafetyContext::lint`].
+pub enum Lint {
+    /// A conditional rule never makes a difference, because another rule for the same syscall
+    /// has a subset of its conditions and therefore allows everything it allows. Contains the
+    /// syscall, the name of the RuleSet with the shadowed rule, the shadowed rule, and the name
+    /// of the RuleSet with the broader rule.
+    ShadowedRule(Sysno, &'static str, SeccompRule, &'static str),
+    /// A syscall is restricted with conditional rules, but a syscall that can be used instead is
+    /// allowed unconditionally, e.g. `openat` with read-only flags and `openat2`. Contains the
+    /// restricted syscall, the name of the RuleSet restricting it, the unrestricted syscall, and
+    /// the name of the RuleSet allowing it.
+    RestrictionBypassed(Sysno, &'static str, Sysno, &'static str),
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShadowedRule(sysno, shadowed, _, by) => write!(f, "A conditional rule on syscall `{}` from RuleSet `{}` has no effect because a broader rule from RuleSet `{}` already allows it", sysno, shadowed, by),
+            Self::RestrictionBypassed(restricted, a, bypass, b) => write!(f, "Syscall `{}` is restricted by RuleSet `{}`, but `{}` is allowed unconditionally by RuleSet `{}`", restricted, a, bypass, b),
+        }
+    }
+}
+
+impl SafetyContext {
+    #[must_use]
+    /// Check the rules enabled so far for combinations that are allowed but probably don't do
+    /// what was intended, without applying anything. Unlike conflicts between simple and
+    /// conditional rules, these are not errors, so it is up to the caller to decide whether to
+    /// report or reject them.
+    ///
+    /// The lints are sorted by syscall. See [`Lint`] for the kinds of problems that are found.
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut syscalls: Vec<&Sysno> = self.seccomp_rules.keys().collect();
+        syscalls.sort();
+
+        let mut lints = Vec::new();
+        for syscall in &syscalls {
+            let rules = &self.seccomp_rules[*syscall];
+
+            // The rules for a syscall are either all simple or all conditional.
+            let is_conditional = rules.first()
+                .is_some_and(|labeled_rule| !labeled_rule.1.argument_filters.is_empty());
+            if !is_conditional {
+                continue;
+            }
+
+            for (i, shadowed) in rules.iter().enumerate() {
+                let broader = rules.iter().enumerate()
+                    .find(|&(j, broader)| i != j && shadows(&broader.1, &shadowed.1)
+                        // Only report one of two identical rules.
+                        && (j < i || !shadows(&shadowed.1, &broader.1)));
+                if let Some((_, broader)) = broader {
+                    lints.push(Lint::ShadowedRule(**syscall, shadowed.0, shadowed.1.clone(), broader.0));
+                }
+            }
+
+            let equivalent = EQUIVALENT_SYSCALLS.iter()
+                .find(|group| group.contains(syscall))
+                .map_or(&[][..], |group| &group[..]);
+            for bypass in equivalent {
+                let Some(bypass_rules) = self.seccomp_rules.get(bypass) else {
+                    continue;
+                };
+                if let Some(simple) = bypass_rules.iter().find(|labeled_rule| labeled_rule.1.argument_filters.is_empty()) {
+                    lints.push(Lint::RestrictionBypassed(**syscall, rules[0].0, *bypass, simple.0));
+                }
+            }
+        }
+
+        lints
+    }
+}
+
+/// Whether every syscall allowed by `rule` is also allowed by `broader`, i.e. all of the
+/// conditions of `broader` are also conditions of `rule`.
+fn shadows(broader: &SeccompRule, rule: &SeccompRule) -> bool {
+    broader.argument_filters.iter()
+        .all(|filter| rule.argument_filters.contains(filter))
+}
@@ -19,29 +19,91 @@
 #[macro_export]
 macro_rules! seccomp_arg_filter {
     ($argno:ident <= $value:expr) => {
-        $crate::SeccompArgumentFilter::new(match_argno!($argno), $crate::SeccompilerComparator::Le, $value)
+        $crate::SeccompArgumentFilter::new($crate::match_argno!($argno), $crate::SeccompilerComparator::Le, $value)
     };
     ($argno:ident < $value:expr) => {
-        $crate::SeccompArgumentFilter::new(match_argno!($argno), $crate::SeccompilerComparator::Lt, $value)
+        $crate::SeccompArgumentFilter::new($crate::match_argno!($argno), $crate::SeccompilerComparator::Lt, $value)
     };
     ($argno:ident >= $value:expr) => {
-        $crate::SeccompArgumentFilter::new(match_argno!($argno), $crate::SeccompilerComparator::Ge, $value)
+        $crate::SeccompArgumentFilter::new($crate::match_argno!($argno), $crate::SeccompilerComparator::Ge, $value)
     };
     ($argno:ident > $value:expr) => {
-        $crate::SeccompArgumentFilter::new(match_argno!($argno), $crate::SeccompilerComparator::Gt, $value)
+        $crate::SeccompArgumentFilter::new($crate::match_argno!($argno), $crate::SeccompilerComparator::Gt, $value)
     };
     ($argno:ident == $value:expr) => {
-        $crate::SeccompArgumentFilter::new(match_argno!($argno), $crate::SeccompilerComparator::Eq, $value)
+        $crate::SeccompArgumentFilter::new($crate::match_argno!($argno), $crate::SeccompilerComparator::Eq, $value)
     };
     ($argno:ident != $value:expr) => {
-        $crate::SeccompArgumentFilter::new(match_argno!($argno), $crate::SeccompilerComparator::Ne, $value)
+        $crate::SeccompArgumentFilter::new($crate::match_argno!($argno), $crate::SeccompilerComparator::Ne, $value)
     };
     ($argno:ident & $mask:tt == $value:expr) => {
-        $crate::SeccompArgumentFilter::new(match_argno!($argno), $crate::SeccompilerComparator::MaskedEq($mask), $value)
+        $crate::SeccompArgumentFilter::new($crate::match_argno!($argno), $crate::SeccompilerComparator::MaskedEq($mask), $value)
     };
     ($_other:expr) => {compile_error!("usage: `arg[0-5] {<=, <, >=, >, ==, !=} <value>` or `arg[0-5] & <mask> == <value>`")};
 }
 
+/// A macro to write the [`conditional_rules`](crate::RuleSet::conditional_rules) of a
+/// [`RuleSet`](crate::RuleSet) concisely. Each rule is a syscall name followed by its conditions
+/// in the syntax of [`seccomp_arg_filter!`], each in parentheses and joined with `&&`. Rules for
+/// the same syscall may be repeated, and the syscall is allowed if any of them match.
+/// Usage:
+/// ```
+/// use std::collections::HashMap;
+/// use extrasafe::*;
+/// use extrasafe::syscalls::Sysno;
+///
+/// const AF_NETLINK: u64 = libc::AF_NETLINK as u64;
+/// const SOCK_RAW: u64 = libc::SOCK_RAW as u64;
+///
+/// struct NetlinkSockets;
+/// impl RuleSet for NetlinkSockets {
+///     fn simple_rules(&self) -> Vec<Sysno> {
+///         Vec::new()
+///     }
+///
+///     fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+///         conditional_rules! {
+///             socket: (arg0 == AF_NETLINK) && (arg1 & 0xf == SOCK_RAW);
+///             sendto: (arg4 == 0);
+///         }
+///     }
+///
+///     fn name(&self) -> &'static str {
+///         "NetlinkSockets"
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! conditional_rules {
+    ($($syscall:ident: $( ( $($cond:tt)* ) )&&+);* $(;)?) => {{
+        let mut rules: ::std::collections::HashMap<$crate::syscalls::Sysno, ::std::vec::Vec<$crate::SeccompRule>>
+            = ::std::collections::HashMap::new();
+        $(
+            rules.entry($crate::syscalls::Sysno::$syscall)
+                .or_insert_with(::std::vec::Vec::new)
+                .push($crate::SeccompRule::new($crate::syscalls::Sysno::$syscall)
+                    $(.and_condition($crate::seccomp_arg_filter!($($cond)*)))+);
+        )*
+        rules
+    }};
+}
+
+/// Internal macro to emit a `tracing` event at the given level if the `tracing` feature is
+/// enabled. Otherwise it expands to nothing and its arguments are not evaluated.
+#[cfg(feature = "tracing")]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => {
+        tracing::event!(tracing::Level::$level, $($arg)*)
+    };
+}
+
+/// Internal macro to emit a `tracing` event at the given level if the `tracing` feature is
+/// enabled. Otherwise it expands to nothing and its arguments are not evaluated.
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)*) => {};
+}
+
 #[doc(hidden)]
 #[macro_export]
 /// Internal macro for `seccomp_arg_filter!`
@@ -0,0 +1,206 @@
+#![cfg(feature = "config")]
+
+//! Build a [`SafetyContext`] from a declarative policy file, so that policies can be reviewed
+//! outside of the code.
+//!
+//! A policy is a TOML document with one table per builtin [`RuleSet`], each containing the names
+//! of the ruleset's `allow_*` functions without the `allow_` prefix:
+//!
+//! ```toml
+//! [system_io]
+//! stdout = true
+//! stderr = true
+//! open_readonly = true
+//!
+//! [time]
+//! gettime = true
+//! ```
+//!
+//! Options set to `false` are ignored. Only functions that take no arguments and don't return a
+//! [`YesReally`](crate::builtins::YesReally) are available, so that dangerous permissions still
+//! have to be explicitly enabled in code.
+
+use toml::{Table, Value};
+
+use crate::builtins::{Cwd, Epoll, Eventing, FdOps, FileLocking, FileWatch, Futex, GroupId, Memfd, Memory, Networking, Polling, Prctl, ProcessControl, ProcessInfo, Randomness, Rlimit, Scheduling, SharedMemory, Signals, SystemIO, Terminal, Time, Xattr};
+use crate::{ExtraSafeError, RuleSet, SafetyContext};
+
+/// The option names for a ruleset, and the function each one calls.
+type Options<R> = [(&'static str, fn(R) -> R)];
+
+/// Parse a policy from a TOML string and enable the rulesets it describes in a new
+/// [`SafetyContext`]. See the [module documentation](self) for the format.
+///
+/// # Errors
+/// Will return [`ExtraSafeError::InvalidPolicy`] if the document isn't valid TOML, or if it
+/// contains unknown rulesets or options, or options that aren't booleans. Will return any other
+/// [`ExtraSafeError`] returned by [`SafetyContext::enable`].
+pub fn from_toml(s: &str) -> Result<SafetyContext, ExtraSafeError> {
+    let document: Table = s.parse()
+        .map_err(|err: toml::de::Error| ExtraSafeError::InvalidPolicy(err.message().to_string()))?;
+
+    let mut ctx = SafetyContext::new();
+    for (name, section) in &document {
+        ctx = match name.as_str() {
+            "system_io" => ctx.enable(build(name, section, SystemIO::nothing(), &[
+                ("read", SystemIO::allow_read),
+                ("write", SystemIO::allow_write),
+                ("unlink", SystemIO::allow_unlink),
+                ("rename", SystemIO::allow_rename),
+                ("directory_ops", SystemIO::allow_directory_ops),
+                ("symlink_ops", SystemIO::allow_symlink_ops),
+                ("symlink_create", SystemIO::allow_symlink_create),
+                ("open_readonly", SystemIO::allow_open_readonly),
+                ("metadata", SystemIO::allow_metadata),
+                ("statx", SystemIO::allow_statx),
+                ("filesystem_metadata", SystemIO::allow_filesystem_metadata),
+                ("ioctl", SystemIO::allow_ioctl),
+                ("close", SystemIO::allow_close),
+                ("stdin", SystemIO::allow_stdin),
+                ("stdout", SystemIO::allow_stdout),
+                ("stderr", SystemIO::allow_stderr),
+            ])?)?,
+            "networking" => ctx.enable(build(name, section, Networking::nothing(), &[
+                ("running_tcp_servers", Networking::allow_running_tcp_servers),
+                ("running_udp_sockets", Networking::allow_running_udp_sockets),
+                ("start_tcp_clients", Networking::allow_start_tcp_clients),
+                ("running_tcp_clients", Networking::allow_running_tcp_clients),
+                ("unix_sockets", Networking::allow_unix_sockets),
+                ("syslog", Networking::allow_syslog),
+                ("running_unix_servers", Networking::allow_running_unix_servers),
+                ("running_unix_clients", Networking::allow_running_unix_clients),
+                ("stream_sockets_only", Networking::allow_stream_sockets_only),
+                ("dgram_sockets_only", Networking::allow_dgram_sockets_only),
+                ("sendmsg", Networking::allow_sendmsg),
+                ("recvmsg", Networking::allow_recvmsg),
+                ("fd_passing", Networking::allow_fd_passing),
+            ])?)?,
+            "time" => ctx.enable(build(name, section, Time::nothing(), &[
+                ("gettime", Time::allow_gettime),
+                ("posix_timers", Time::allow_posix_timers),
+                ("set_non_realtime_clocks", Time::allow_set_non_realtime_clocks),
+            ])?)?,
+            "memory" => ctx.enable(build(name, section, Memory::nothing(), &[
+                ("brk", Memory::allow_brk),
+                ("mmap", Memory::allow_mmap),
+                ("mmap_no_wx", Memory::allow_mmap_no_wx),
+                ("munmap", Memory::allow_munmap),
+                ("mprotect", Memory::allow_mprotect),
+                ("madvise", Memory::allow_madvise),
+                ("madvise_safe", Memory::allow_madvise_safe),
+                ("mremap", Memory::allow_mremap),
+            ])?)?,
+            "signals" => ctx.enable(build(name, section, Signals::nothing(), &[
+                ("sigaction", Signals::allow_sigaction),
+                ("sigprocmask", Signals::allow_sigprocmask),
+                ("sigaltstack", Signals::allow_sigaltstack),
+                ("sigreturn", Signals::allow_sigreturn),
+                ("rt_sigpending", Signals::allow_rt_sigpending),
+            ])?)?,
+            "epoll" => ctx.enable(build(name, section, Epoll::nothing(), &[
+                ("create", Epoll::allow_create),
+                ("legacy_create", Epoll::allow_legacy_create),
+                ("ctl", Epoll::allow_ctl),
+                ("wait", Epoll::allow_wait),
+            ])?)?,
+            "polling" => ctx.enable(build(name, section, Polling::nothing(), &[
+                ("poll", Polling::allow_poll),
+                ("select", Polling::allow_select),
+            ])?)?,
+            "file_watch" => ctx.enable(build(name, section, FileWatch::nothing(), &[
+                ("inotify", FileWatch::allow_inotify),
+            ])?)?,
+            "randomness" => ctx.enable(build(name, section, Randomness::nothing(), &[
+                ("getrandom", Randomness::allow_getrandom),
+            ])?)?,
+            "futex" => ctx.enable(build(name, section, Futex::nothing(), &[
+                ("futex", Futex::allow_futex),
+                ("futex_waitv", Futex::allow_futex_waitv),
+            ])?)?,
+            "scheduling" => ctx.enable(build(name, section, Scheduling::nothing(), &[
+                ("query", Scheduling::allow_query),
+            ])?)?,
+            "xattr" => ctx.enable(build(name, section, Xattr::nothing(), &[
+                ("read", Xattr::allow_read),
+            ])?)?,
+            "terminal" => ctx.enable(build(name, section, Terminal::nothing(), &[
+                ("raw_mode", Terminal::allow_raw_mode),
+                ("window_size", Terminal::allow_window_size),
+            ])?)?,
+            "process_info" => ctx.enable(build(name, section, ProcessInfo::nothing(), &[
+                ("ids", ProcessInfo::allow_ids),
+                ("usage", ProcessInfo::allow_usage),
+            ])?)?,
+            "process_control" => ctx.enable(build(name, section, ProcessControl::nothing(), &[
+                ("wait", ProcessControl::allow_wait),
+                ("exit", ProcessControl::allow_exit),
+            ])?)?,
+            "group_id" => ctx.enable(build(name, section, GroupId::nothing(), &[
+                ("get", GroupId::allow_get),
+            ])?)?,
+            "eventing" => ctx.enable(build(name, section, Eventing::nothing(), &[
+                ("timerfd", Eventing::allow_timerfd),
+                ("eventfd", Eventing::allow_eventfd),
+                ("signalfd", Eventing::allow_signalfd),
+            ])?)?,
+            "file_locking" => ctx.enable(build(name, section, FileLocking::nothing(), &[
+                ("flock", FileLocking::allow_flock),
+                ("fcntl_locks", FileLocking::allow_fcntl_locks),
+            ])?)?,
+            "fd_ops" => ctx.enable(build(name, section, FdOps::nothing(), &[
+                ("dup", FdOps::allow_dup),
+                ("close_range", FdOps::allow_close_range),
+                ("fcntl", FdOps::allow_fcntl),
+            ])?)?,
+            "shared_memory" => ctx.enable(build(name, section, SharedMemory::nothing(), &[
+                ("sysv_shm_detach", SharedMemory::allow_sysv_shm_detach),
+                ("posix_mq_io", SharedMemory::allow_posix_mq_io),
+            ])?)?,
+            "memfd" => ctx.enable(build(name, section, Memfd::nothing(), &[
+                ("memfd_create", Memfd::allow_memfd_create),
+                ("memfd_secret", Memfd::allow_memfd_secret),
+            ])?)?,
+            "prctl" => ctx.enable(build(name, section, Prctl::nothing(), &[
+                ("set_name", Prctl::allow_set_name),
+                ("get_name", Prctl::allow_get_name),
+                ("set_no_new_privs", Prctl::allow_set_no_new_privs),
+                ("get_no_new_privs", Prctl::allow_get_no_new_privs),
+                ("get_dumpable", Prctl::allow_get_dumpable),
+                ("get_seccomp", Prctl::allow_get_seccomp),
+            ])?)?,
+            "rlimit" => ctx.enable(build(name, section, Rlimit::nothing(), &[
+                ("get", Rlimit::allow_get),
+            ])?)?,
+            "cwd" => ctx.enable(build(name, section, Cwd::nothing(), &[
+                ("getcwd", Cwd::allow_getcwd),
+                ("chdir", Cwd::allow_chdir),
+            ])?)?,
+            _ => return Err(ExtraSafeError::InvalidPolicy(format!("unknown ruleset `{}`", name))),
+        };
+    }
+
+    Ok(ctx)
+}
+
+/// Call the function for each option in `section` that is set to `true` on `ruleset`.
+fn build<R: RuleSet>(name: &str, section: &Value, mut ruleset: R, options: &Options<R>)
+    -> Result<R, ExtraSafeError> {
+    let section = section.as_table()
+        .ok_or_else(|| ExtraSafeError::InvalidPolicy(format!("`{}` must be a table", name)))?;
+
+    for (key, value) in section {
+        let (_, allow) = options.iter()
+            .find(|(option, _)| option == key)
+            .ok_or_else(|| ExtraSafeError::InvalidPolicy(
+                format!("unknown option `{}` for ruleset `{}`", key, name)))?;
+        let enabled = value.as_bool()
+            .ok_or_else(|| ExtraSafeError::InvalidPolicy(
+                format!("option `{}` for ruleset `{}` must be a boolean", key, name)))?;
+
+        if enabled {
+            ruleset = allow(ruleset);
+        }
+    }
+
+    Ok(ruleset)
+}
@@ -0,0 +1,256 @@
+#![allow(unsafe_code)]
+
+//! Installing seccomp filters with flags that seccompiler does not expose, building the filter
+//! for user notifications, and checking whether a filter is active.
+
+use std::io;
+use std::os::fd::{FromRawFd, OwnedFd};
+
+use seccompiler::sock_filter;
+
+/// From `<linux/seccomp.h>`
+const SECCOMP_SET_MODE_FILTER: libc::c_int = 1;
+
+/// Offsets into `struct seccomp_data`, see `<linux/seccomp.h>`.
+const SECCOMP_DATA_NR_OFFSET: u32 = 0;
+const SECCOMP_DATA_ARCH_OFFSET: u32 = 4;
+
+/// The audit architecture of syscalls made through the native ABI, see `<linux/audit.h>`.
+#[cfg(target_arch = "x86_64")]
+const AUDIT_ARCH_NATIVE: u32 = 0xc000_003e;
+#[cfg(target_arch = "aarch64")]
+const AUDIT_ARCH_NATIVE: u32 = 0xc000_00b7;
+
+/// The audit architecture of `arch`, see `<linux/audit.h>`.
+fn audit_arch(arch: seccompiler::TargetArch) -> u32 {
+    match arch {
+        seccompiler::TargetArch::x86_64 => 0xc000_003e,
+        seccompiler::TargetArch::aarch64 => 0xc000_00b7,
+    }
+}
+
+/// BPF instruction codes, see `<linux/bpf_common.h>`.
+/// `BPF_LD | BPF_W | BPF_ABS`
+const BPF_LD_W_ABS: u16 = 0x20;
+/// `BPF_JMP | BPF_JEQ | BPF_K`
+const BPF_JMP_JEQ_K: u16 = 0x15;
+/// `BPF_JMP | BPF_JA`
+const BPF_JMP_JA: u16 = 0x05;
+/// `BPF_RET | BPF_K`
+const BPF_RET_K: u16 = 0x06;
+
+/// BPF program definition, see `<linux/filter.h>`.
+#[repr(C)]
+struct sock_fprog {
+    len: libc::c_ushort,
+    filter: *const sock_filter,
+}
+
+/// Install `bpf_filter` with the given `SECCOMP_FILTER_FLAG_*` flags, in the same way as
+/// seccompiler's `apply_filter` and `apply_filter_all_threads`.
+pub(crate) fn apply_filter_with_flags(bpf_filter: &[sock_filter], flags: libc::c_ulong, no_new_privs: bool)
+    -> Result<(), seccompiler::Error> {
+    let rc = set_mode_filter(bpf_filter, flags, no_new_privs)?;
+
+    // If TSYNC fails, the return value is the id of the thread that caused the failure.
+    if rc > 0 {
+        return Err(seccompiler::Error::ThreadSync(rc));
+    }
+
+    Ok(())
+}
+
+/// Install `bpf_filter` on the current thread with `SECCOMP_FILTER_FLAG_NEW_LISTENER` and return
+/// the listener file descriptor for user notifications.
+pub(crate) fn apply_filter_with_listener(bpf_filter: &[sock_filter], flags: libc::c_ulong, no_new_privs: bool)
+    -> Result<OwnedFd, seccompiler::Error> {
+    let rc = set_mode_filter(bpf_filter, flags | libc::SECCOMP_FILTER_FLAG_NEW_LISTENER, no_new_privs)?;
+    let fd = libc::c_int::try_from(rc)
+        .map_err(|_| seccompiler::Error::Seccomp(io::Error::from_raw_os_error(libc::EBADF)))?;
+
+    // SAFETY: with NEW_LISTENER, the return value is a new file descriptor owned by us
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Build a filter that returns `SECCOMP_RET_USER_NOTIF` for each of `syscalls` and allows all
+/// other syscalls, so that it can be stacked with the main filter. Syscalls from other
+/// architectures kill the process, like in the filters generated by seccompiler.
+pub(crate) fn user_notif_program(syscalls: &[syscalls::Sysno]) -> Vec<sock_filter> {
+    let actions: Vec<_> = syscalls.iter()
+        .map(|&syscall| (syscall, libc::SECCOMP_RET_USER_NOTIF))
+        .collect();
+    action_program(&actions)
+}
+
+/// Build a filter that returns `SECCOMP_RET_ERRNO` with the given errno for each of the syscalls
+/// and allows all other syscalls, so that it can be stacked with the main filter like
+/// [`user_notif_program`]. The errnos must fit in `SECCOMP_RET_DATA`.
+pub(crate) fn errno_program(errnos: &[(syscalls::Sysno, u32)]) -> Vec<sock_filter> {
+    let actions: Vec<_> = errnos.iter()
+        .map(|&(syscall, errno)| (syscall, libc::SECCOMP_RET_ERRNO | errno))
+        .collect();
+    action_program(&actions)
+}
+
+/// Build a filter that returns the given action for each syscall and allows all other syscalls.
+fn action_program(actions: &[(syscalls::Sysno, u32)]) -> Vec<sock_filter> {
+    let stmt = |code, k| sock_filter { code, jt: 0, jf: 0, k };
+
+    let mut program = vec![
+        stmt(BPF_LD_W_ABS, SECCOMP_DATA_ARCH_OFFSET),
+        sock_filter { code: BPF_JMP_JEQ_K, jt: 1, jf: 0, k: AUDIT_ARCH_NATIVE },
+        stmt(BPF_RET_K, libc::SECCOMP_RET_KILL_PROCESS),
+        stmt(BPF_LD_W_ABS, SECCOMP_DATA_NR_OFFSET),
+    ];
+    for &(syscall, action) in actions {
+        // Jump over the return if the syscall doesn't match, so that the offsets don't depend on
+        // the number of syscalls.
+        #[allow(clippy::cast_sign_loss)]
+        let nr = syscall.id() as u32;
+        program.extend([
+            sock_filter { code: BPF_JMP_JEQ_K, jt: 0, jf: 1, k: nr },
+            stmt(BPF_RET_K, action),
+        ]);
+    }
+    program.push(stmt(BPF_RET_K, libc::SECCOMP_RET_ALLOW));
+
+    program
+}
+
+/// Build a filter that allows each of `syscalls` unconditionally and returns `default_action` for
+/// all other syscalls. Syscalls from architectures other than `arch` kill the process, like in the
+/// filters generated by seccompiler.
+///
+/// This is equivalent to the filter seccompiler generates for rules without conditions, but
+/// every matching comparison jumps to a shared `SECCOMP_RET_ALLOW` rather than to its own return,
+/// so it takes about one instruction per syscall instead of two.
+pub(crate) fn allowlist_program(syscalls: &[syscalls::Sysno], default_action: u32, arch: seccompiler::TargetArch)
+    -> Vec<sock_filter> {
+    let stmt = |code, k| sock_filter { code, jt: 0, jf: 0, k };
+
+    let mut program = vec![
+        stmt(BPF_LD_W_ABS, SECCOMP_DATA_ARCH_OFFSET),
+        sock_filter { code: BPF_JMP_JEQ_K, jt: 1, jf: 0, k: audit_arch(arch) },
+        stmt(BPF_RET_K, libc::SECCOMP_RET_KILL_PROCESS),
+        stmt(BPF_LD_W_ABS, SECCOMP_DATA_NR_OFFSET),
+    ];
+    // Conditional jump offsets are only 8 bits, so each chunk of comparisons is followed by its
+    // own allow, which the jump at the end of the chunk skips over.
+    for chunk in syscalls.chunks(usize::from(u8::MAX)) {
+        for (i, syscall) in chunk.iter().enumerate() {
+            #[allow(clippy::cast_sign_loss)]
+            let nr = syscall.id() as u32;
+            let jt = u8::try_from(chunk.len() - i).expect("chunks are at most u8::MAX long");
+            program.push(sock_filter { code: BPF_JMP_JEQ_K, jt, jf: 0, k: nr });
+        }
+        program.extend([
+            stmt(BPF_JMP_JA, 1),
+            stmt(BPF_RET_K, libc::SECCOMP_RET_ALLOW),
+        ]);
+    }
+    program.push(stmt(BPF_RET_K, default_action));
+
+    program
+}
+
+/// Install each of `bpf_filters` on the current thread in order, after setting
+/// `PR_SET_NO_NEW_PRIVS` if `no_new_privs` is true.
+///
+/// This only makes the `prctl` and `seccomp` syscalls and does not allocate, including on errors,
+/// so it can be called in a child process between `fork` and `exec`. The filters must not be
+/// empty or longer than `u16::MAX` instructions.
+pub(crate) fn apply_filters_after_fork(bpf_filters: &[Vec<sock_filter>], flags: libc::c_ulong, no_new_privs: bool)
+    -> io::Result<()> {
+    if no_new_privs {
+        set_no_new_privs()?;
+    }
+    for bpf_filter in bpf_filters {
+        let _rc = seccomp_set_mode_filter(bpf_filter, flags)?;
+    }
+
+    Ok(())
+}
+
+/// Call `seccomp(SECCOMP_SET_MODE_FILTER)` with `bpf_filter` and `flags`, after setting
+/// `PR_SET_NO_NEW_PRIVS` if `no_new_privs` is true, and return its non-negative return value.
+fn set_mode_filter(bpf_filter: &[sock_filter], flags: libc::c_ulong, no_new_privs: bool)
+    -> Result<libc::c_long, seccompiler::Error> {
+    if bpf_filter.is_empty() {
+        return Err(seccompiler::Error::EmptyFilter);
+    }
+
+    if no_new_privs {
+        set_no_new_privs().map_err(seccompiler::Error::Prctl)?;
+    }
+
+    seccomp_set_mode_filter(bpf_filter, flags).map_err(seccompiler::Error::Seccomp)
+}
+
+/// Call `prctl(PR_SET_NO_NEW_PRIVS)`.
+fn set_no_new_privs() -> io::Result<()> {
+    // SAFETY: the arguments are valid for PR_SET_NO_NEW_PRIVS
+    let rc = unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Call `seccomp(SECCOMP_SET_MODE_FILTER)` with `bpf_filter` and `flags`, and return its
+/// non-negative return value.
+fn seccomp_set_mode_filter(bpf_filter: &[sock_filter], flags: libc::c_ulong) -> io::Result<libc::c_long> {
+    // The kernel rejects filters longer than BPF_MAXINSNS (4096) anyway.
+    let len = u16::try_from(bpf_filter.len())
+        .map_err(|_| io::Error::from_raw_os_error(libc::EINVAL))?;
+    let bpf_prog = sock_fprog {
+        len,
+        filter: bpf_filter.as_ptr(),
+    };
+
+    let bpf_prog_ptr: *const sock_fprog = &bpf_prog;
+
+    // SAFETY: the kernel copies the filter and does not keep a reference to it
+    let rc = unsafe { libc::syscall(libc::SYS_seccomp, SECCOMP_SET_MODE_FILTER, flags, bpf_prog_ptr) };
+    if rc < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(rc)
+}
+
+/// Returns whether a seccomp filter is active on the current thread, e.g. after applying a
+/// [`SafetyContext`](crate::SafetyContext). This does not require any privileges.
+///
+/// This uses `prctl(PR_GET_SECCOMP)`, so it works regardless of whether the filter allows
+/// opening files. If the active filter does not allow `prctl`, the call fails, which also means
+/// that a filter is active. Note that if the filter does not allow `prctl`, calling this raises
+/// `SIGSYS` if it was created with [`trap_on_violation`](crate::SafetyContext::trap_on_violation),
+/// and kills the whole process if it was created with
+/// [`kill_process_on_violation`](crate::SafetyContext::kill_process_on_violation). Enable
+/// [`Prctl::allow_get_seccomp`](crate::builtins::Prctl::allow_get_seccomp) to make the call safe
+/// under those filters.
+#[must_use]
+pub fn is_seccomp_active() -> bool {
+    // SAFETY: PR_GET_SECCOMP takes no other arguments
+    let rc = unsafe { libc::prctl(libc::PR_GET_SECCOMP, 0, 0, 0, 0) };
+
+    // Without a filter, this returns 0 and can't fail. In strict mode (1), this would kill the
+    // process rather than returning.
+    rc != 0
+}
+
+/// Returns the number of seccomp filters attached to the current thread, or `None` if it can't
+/// be determined.
+///
+/// This reads the `Seccomp_filters` field of `/proc/thread-self/status`, which requires Linux
+/// 5.9 or later and that the active filters allow opening and reading the file. Note that
+/// applying a [`SafetyContext`](crate::SafetyContext) may attach more than one filter, e.g. an
+/// additional filter for [`return_errno_for`](crate::SafetyContext::return_errno_for).
+#[must_use]
+pub fn seccomp_filter_count() -> Option<usize> {
+    let status = std::fs::read_to_string("/proc/thread-self/status").ok()?;
+    status.lines()
+        .find_map(|line| line.strip_prefix("Seccomp_filters:"))
+        .and_then(|count| count.trim().parse().ok())
+}
@@ -0,0 +1,270 @@
+#![allow(unsafe_code)]
+
+//! Helpers for handling the `SIGSYS` signal raised when a syscall is blocked by a
+//! [`SafetyContext`] with
+//! [`trap_on_violation`](crate::SafetyContext::trap_on_violation).
+//!
+//! # Signal safety
+//!
+//! The handler installed by [`install_logging_handler`] runs in signal context, so it only uses
+//! async-signal-safe operations: it records the syscall number in an atomic, formats its message
+//! into a buffer on the stack without allocating, and writes it to stderr with a single `write`
+//! syscall. While it runs, `SIGSYS` is blocked, so if the handler itself made a syscall that the
+//! filter blocks, the kernel would kill the process instead. `trap_on_violation` therefore allows
+//! writing to stderr.
+
+use std::io;
+use std::sync::atomic::{AtomicI32, AtomicPtr, Ordering};
+
+use syscalls::Sysno;
+
+use crate::{ExtraSafeError, SafetyContext};
+
+/// The most recent syscall number reported by the handler, or -1 if there hasn't been one.
+static LAST_VIOLATION: AtomicI32 = AtomicI32::new(-1);
+
+/// The offset of the `si_syscall` field within `siginfo_t` for `SIGSYS`, from the `_sigsys` member
+/// of its union in `<asm-generic/siginfo.h>`. `si_signo`, `si_errno` and `si_code` take 12 bytes,
+/// the union is aligned to 8 bytes on 64 bit targets so it starts at 16, and `_sigsys` starts with
+/// the `_call_addr` pointer, followed by `_syscall`.
+#[cfg(target_pointer_width = "64")]
+const SI_SYSCALL_OFFSET: usize = 16 + std::mem::size_of::<*mut libc::c_void>();
+#[cfg(not(target_pointer_width = "64"))]
+compile_error!("the layout of siginfo_t for SIGSYS is only known for 64 bit targets");
+
+const _: () = assert!(SI_SYSCALL_OFFSET + std::mem::size_of::<libc::c_int>() <= std::mem::size_of::<libc::siginfo_t>());
+
+const MESSAGE_PREFIX: &[u8] = b"extrasafe: blocked syscall ";
+
+/// In a child forked by [`SafetyContext::probe`], the memory shared with the parent that the first
+/// blocked syscall number is written to.
+static PROBE_RESULT: AtomicPtr<AtomicI32> = AtomicPtr::new(std::ptr::null_mut());
+
+/// Exit codes of the child forked by [`SafetyContext::probe`] other than 0.
+const PROBE_APPLY_FAILED: libc::c_int = 1;
+const PROBE_PANICKED: libc::c_int = 2;
+
+/// Install a `SIGSYS` handler that writes the name of each blocked syscall to stderr, records it
+/// so that it can be retrieved with [`last_violation`], and makes the blocked syscall return
+/// `EPERM`, as it would without `trap_on_violation`.
+///
+/// This replaces any existing `SIGSYS` handler for the whole process, and should be called before
+/// the `SafetyContext` is applied since `rt_sigaction` may not be allowed afterwards.
+///
+/// # Errors
+/// Returns an error if the handler could not be installed.
+pub fn install_logging_handler() -> Result<(), io::Error> {
+    // SAFETY: sigaction is plain old data, so all zeroes is a valid (empty) value.
+    let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+    action.sa_sigaction = handle_sigsys as *const () as libc::sighandler_t;
+    action.sa_flags = libc::SA_SIGINFO;
+
+    // SAFETY: action is initialized above and the handler is async-signal-safe.
+    let rc = unsafe { libc::sigaction(libc::SIGSYS, &action, std::ptr::null_mut()) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[must_use]
+/// Returns the most recent syscall that was blocked and reported by the handler installed with
+/// [`install_logging_handler`], if any.
+pub fn last_violation() -> Option<Sysno> {
+    let syscall = LAST_VIOLATION.load(Ordering::SeqCst);
+    usize::try_from(syscall).ok()
+        .and_then(Sysno::new)
+}
+
+impl SafetyContext {
+    /// Run `f` in a forked child process with the [`SafetyContext`]'s rules applied, and return
+    /// the first syscall that the filter blocked, or `None` if `f` returned without making any
+    /// blocked syscalls. The current process is not restricted.
+    ///
+    /// This is meant for developing a policy iteratively: instead of running the program, getting
+    /// killed or an `EPERM`, and guessing which syscall was missing, the missing syscall is
+    /// reported directly. The filter is applied with
+    /// [`trap_on_violation`](Self::trap_on_violation), replacing the default action, and the child
+    /// exits as soon as a syscall is blocked, so only the first one is found. Violations of
+    /// landlock rules are not reported, since they only make syscalls fail.
+    ///
+    /// The current process must be single-threaded, since `f` runs in the child after `fork` and
+    /// may allocate or take other locks that another thread could have held while forking. This
+    /// is checked, so `probe` is best called at the start of a small test program or example
+    /// rather than from a multithreaded test harness. Changes `f` makes to memory are not visible
+    /// in the current process.
+    ///
+    /// # Errors
+    /// Returns an error without forking if the current process has more than one thread, if
+    /// syscalls were marked with [`notify_on`](Self::notify_on), or if `exit_group` or `write`
+    /// were denied with [`deny`](Self::deny), since the child exits from the `SIGSYS` handler and
+    /// the trap handler may write to stderr. Otherwise, returns an error if the filter could not
+    /// be compiled or applied in the child, if forking failed, or if `f` panicked or the child was
+    /// killed by a signal.
+    pub fn probe<F: FnOnce()>(self, f: F) -> io::Result<Option<Sysno>> {
+        let threads = std::fs::read_dir("/proc/self/task")?.count();
+        if threads != 1 {
+            return Err(io::Error::other(format!("probe requires a single-threaded process, but there are {} threads", threads)));
+        }
+        if !self.notify.is_empty() {
+            return Err(io::Error::other(ExtraSafeError::NotifyListenerRequired));
+        }
+        if let Some(syscall) = [Sysno::exit_group, Sysno::write].into_iter().find(|syscall| self.denied.contains(syscall)) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                format!("probe requires `{}`, which was denied", syscall)));
+        }
+
+        let filter = self.trap_on_violation()
+            .into_filter()
+            .map_err(io::Error::other)?;
+
+        let size = std::mem::size_of::<AtomicI32>();
+        // SAFETY: an anonymous mapping does not alias any existing memory.
+        let shared = unsafe {
+            libc::mmap(std::ptr::null_mut(), size, libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_ANONYMOUS, -1, 0)
+        };
+        if shared == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        let shared = shared.cast::<AtomicI32>();
+        // SAFETY: the mapping is page aligned, at least as large as an AtomicI32, and zeroed.
+        let result = unsafe { &*shared };
+        result.store(-1, Ordering::SeqCst);
+
+        // SAFETY: the process is single-threaded, as checked above, so no locks can be held by
+        // other threads in the child. The child applies the filter, runs `f`, and exits without
+        // returning.
+        let pid = unsafe { libc::fork() };
+        if pid == 0 {
+            PROBE_RESULT.store(shared, Ordering::SeqCst);
+
+            // SAFETY: sigaction is plain old data, so all zeroes is a valid (empty) value.
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            action.sa_sigaction = handle_probe_sigsys as *const () as libc::sighandler_t;
+            action.sa_flags = libc::SA_SIGINFO;
+            // SAFETY: action is initialized above and the handler is async-signal-safe.
+            let rc = unsafe { libc::sigaction(libc::SIGSYS, &action, std::ptr::null_mut()) };
+            if rc != 0 || filter.apply_to_current_thread().is_err() {
+                // SAFETY: _exit does not return.
+                unsafe { libc::_exit(PROBE_APPLY_FAILED) };
+            }
+
+            let code = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)) {
+                Ok(()) => 0,
+                Err(_) => PROBE_PANICKED,
+            };
+            // SAFETY: _exit does not return.
+            unsafe { libc::_exit(code) };
+        }
+
+        let status = if pid < 0 { Err(io::Error::last_os_error()) } else { wait_for(pid) };
+        let syscall = result.load(Ordering::SeqCst);
+        // SAFETY: the mapping was created above and `result` is not used afterwards.
+        let _rc = unsafe { libc::munmap(shared.cast(), size) };
+
+        let status = status?;
+        if libc::WIFSIGNALED(status) {
+            return Err(io::Error::other(format!("probed child was killed by signal {}", libc::WTERMSIG(status))));
+        }
+        match libc::WEXITSTATUS(status) {
+            0 => Ok(usize::try_from(syscall).ok().and_then(Sysno::new)),
+            PROBE_APPLY_FAILED => Err(io::Error::other("the filter could not be applied in the probed child")),
+            PROBE_PANICKED => Err(io::Error::other("the probed function panicked")),
+            code => Err(io::Error::other(format!("probed child exited with code {}", code))),
+        }
+    }
+}
+
+/// Wait for the child `pid` to exit and return its status.
+fn wait_for(pid: libc::pid_t) -> io::Result<libc::c_int> {
+    let mut status = 0;
+    loop {
+        // SAFETY: status is valid for writes.
+        let rc = unsafe { libc::waitpid(pid, &mut status, 0) };
+        if rc == pid {
+            return Ok(status);
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// The `SIGSYS` handler in a child forked by [`SafetyContext::probe`]: record the blocked syscall
+/// in the memory shared with the parent and exit immediately.
+extern "C" fn handle_probe_sigsys(_signal: libc::c_int, info: *mut libc::siginfo_t, _context: *mut libc::c_void) {
+    // SAFETY: the kernel passes a valid siginfo_t, which contains the si_syscall field for SIGSYS.
+    let syscall = unsafe { info.cast::<u8>().add(SI_SYSCALL_OFFSET).cast::<libc::c_int>().read() };
+
+    let result = PROBE_RESULT.load(Ordering::SeqCst);
+    // SAFETY: the pointer is set to the shared mapping before the handler is installed.
+    if let Some(result) = unsafe { result.as_ref() } {
+        result.store(syscall, Ordering::SeqCst);
+    }
+    // SAFETY: _exit is async-signal-safe and `exit_group` is allowed by the BasicCapabilities.
+    unsafe { libc::_exit(0) };
+}
+
+extern "C" fn handle_sigsys(_signal: libc::c_int, info: *mut libc::siginfo_t, context: *mut libc::c_void) {
+    // SAFETY: the kernel passes a valid siginfo_t, which contains the si_syscall field for SIGSYS.
+    let syscall = unsafe { info.cast::<u8>().add(SI_SYSCALL_OFFSET).cast::<libc::c_int>().read() };
+    LAST_VIOLATION.store(syscall, Ordering::SeqCst);
+
+    let mut message = [0u8; 64];
+    let len = format_message(&mut message, syscall);
+    // SAFETY: message is valid for len bytes. If the write fails there's nothing we can do.
+    let _written = unsafe { libc::write(libc::STDERR_FILENO, message.as_ptr().cast(), len) };
+
+    // The kernel doesn't set a return value for trapped syscalls, so set it to -EPERM ourselves.
+    // SAFETY: the kernel passes a valid ucontext_t since SA_SIGINFO is set, and changes to it are
+    // applied to the thread when the handler returns.
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        let context = context.cast::<libc::ucontext_t>();
+        (*context).uc_mcontext.gregs[libc::REG_RAX as usize] = -i64::from(libc::EPERM);
+    }
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        let context = context.cast::<libc::ucontext_t>();
+        (*context).uc_mcontext.regs[0] = (-i64::from(libc::EPERM)) as u64;
+    }
+}
+
+/// Write "extrasafe: blocked syscall <name>\n" into `buf` without allocating, using the number if
+/// the syscall is unknown, and return the length of the message.
+fn format_message(buf: &mut [u8; 64], syscall: libc::c_int) -> usize {
+    let mut len = 0;
+    let mut push = |bytes: &[u8]| {
+        let n = bytes.len().min(buf.len() - len);
+        buf[len..len + n].copy_from_slice(&bytes[..n]);
+        len += n;
+    };
+
+    push(MESSAGE_PREFIX);
+    match usize::try_from(syscall).ok().and_then(Sysno::new) {
+        Some(sysno) => push(sysno.name().as_bytes()),
+        None => {
+            let mut digits = [0u8; 12];
+            let mut i = digits.len();
+            let mut n = i64::from(syscall).unsigned_abs();
+            loop {
+                i -= 1;
+                digits[i] = b'0' + (n % 10) as u8;
+                n /= 10;
+                if n == 0 {
+                    break;
+                }
+            }
+            if syscall < 0 {
+                push(b"-");
+            }
+            push(&digits[i..]);
+        }
+    }
+    push(b"\n");
+
+    len
+}
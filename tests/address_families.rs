@@ -0,0 +1,72 @@
+#![allow(unsafe_code)]
+// allow unsafe to call socket directly
+
+use extrasafe::*;
+use extrasafe::builtins::Networking;
+use extrasafe::builtins::network::AddressFamily;
+use extrasafe::syscalls::Sysno;
+
+/// Try to create a socket, and close it if successful.
+fn can_create_socket(family: libc::c_int, ty: libc::c_int) -> bool {
+    // SAFETY: socket has no memory safety requirements
+    let fd = unsafe { libc::socket(family, ty, 0) };
+    if fd < 0 {
+        assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+        return false;
+    }
+    // SAFETY: fd was created above
+    let rc = unsafe { libc::close(fd) };
+    assert_eq!(rc, 0);
+    true
+}
+
+#[test]
+/// No families adds no rule on `socket` at all.
+fn address_families_empty() {
+    let net = Networking::nothing()
+        .allow_address_families(&[]);
+    assert!(net.conditional_rules().is_empty());
+}
+
+#[test]
+fn address_family_rules() {
+    let net = Networking::nothing()
+        .allow_address_families(&[AddressFamily::Inet, AddressFamily::Netlink]);
+    assert!(net.simple_rules().is_empty());
+
+    let filters: Vec<_> = net.conditional_rules()[&Sysno::socket].iter()
+        .map(|rule| rule.argument_filters.clone())
+        .collect();
+    assert_eq!(filters, vec![
+        vec![seccomp_arg_filter!(arg0 == libc::AF_INET as u64)],
+        vec![seccomp_arg_filter!(arg0 == libc::AF_NETLINK as u64)],
+    ]);
+}
+
+#[test]
+fn single_address_family() {
+    SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_address_families(&[AddressFamily::Inet])).unwrap()
+        .enable(Sysno::close).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert!(can_create_socket(libc::AF_INET, libc::SOCK_STREAM));
+    assert!(can_create_socket(libc::AF_INET, libc::SOCK_DGRAM));
+    assert!(!can_create_socket(libc::AF_INET6, libc::SOCK_STREAM));
+    assert!(!can_create_socket(libc::AF_UNIX, libc::SOCK_STREAM));
+}
+
+#[test]
+fn multiple_address_families() {
+    SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_address_families(&[AddressFamily::Inet, AddressFamily::Inet6])).unwrap()
+        .enable(Sysno::close).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert!(can_create_socket(libc::AF_INET, libc::SOCK_STREAM));
+    assert!(can_create_socket(libc::AF_INET6, libc::SOCK_DGRAM));
+    assert!(!can_create_socket(libc::AF_UNIX, libc::SOCK_STREAM));
+    assert!(!can_create_socket(libc::AF_NETLINK, libc::SOCK_RAW));
+}
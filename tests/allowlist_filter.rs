@@ -0,0 +1,103 @@
+#![allow(unsafe_code)]
+// allow unsafe to call raw syscalls
+
+use std::collections::HashMap;
+use std::thread;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+/// Allows `socket` only with an address family that doesn't exist, so that the filter has a
+/// conditional rule that doesn't change any of the probed syscalls.
+struct NeverSocket;
+impl RuleSet for NeverSocket {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        let rule = SeccompRule::new(Sysno::socket)
+            .and_condition(seccomp_arg_filter!(arg0 == 0xffff));
+        HashMap::from([(Sysno::socket, vec![rule])])
+    }
+
+    fn name(&self) -> &'static str {
+        "NeverSocket"
+    }
+}
+
+/// Syscalls with arguments that don't have any effect if they are allowed.
+const PROBES: &[(libc::c_long, libc::c_long)] = &[
+    (libc::SYS_getpid, 0),
+    (libc::SYS_sched_yield, 0),
+    (libc::SYS_close, -1),
+    (libc::SYS_getuid, 0),
+    (libc::SYS_getppid, 0),
+    (libc::SYS_dup, -1),
+];
+
+/// Apply `ctx` on a new thread and return, for each probe, whether it was denied with `EPERM`.
+fn probe(ctx: SafetyContext) -> Vec<bool> {
+    thread::spawn(move || {
+        ctx.apply_to_current_thread().unwrap();
+
+        PROBES.iter().map(|&(nr, arg)| {
+            // SAFETY: the probes don't touch any memory or file descriptors
+            let rc = unsafe { libc::syscall(nr, arg) };
+            rc == -1 && std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+        }).collect()
+    }).join().unwrap()
+}
+
+fn simple_only() -> SafetyContext {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_close()).unwrap()
+}
+
+#[test]
+/// A filter with only simple rules, which is built without seccompiler, allows and denies the
+/// same syscalls as one with an unrelated conditional rule, which is built by seccompiler.
+fn simple_only_matches_seccompiler() {
+    let with_conditional = simple_only()
+        .enable(NeverSocket).unwrap();
+
+    let fast = probe(simple_only());
+    let general = probe(with_conditional);
+    assert_eq!(fast, general);
+    assert_eq!(fast, vec![false, false, false, true, true, true]);
+}
+
+#[test]
+fn simple_only_is_smaller() {
+    let simple = simple_only();
+    let syscalls = simple.enabled_syscalls().len();
+    let program = simple.compile_bpf().unwrap();
+
+    // Besides one comparison per syscall, the filter needs the architecture check, loading the
+    // syscall number, and the returns.
+    assert!(program.len() < syscalls + builtins::BasicCapabilities.simple_rules().len() + 10);
+}
+
+/// Allows every syscall, which is more than fit in a single chunk of comparisons.
+struct Everything;
+impl RuleSet for Everything {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        extrasafe::syscalls::SysnoSet::all().iter().collect()
+    }
+
+    fn name(&self) -> &'static str {
+        "Everything"
+    }
+}
+
+#[test]
+fn simple_only_many_syscalls() {
+    assert!(Everything.simple_rules().len() > usize::from(u8::MAX));
+
+    let ctx = SafetyContext::new()
+        .deny(Sysno::getuid)
+        .enable(Everything).unwrap();
+    assert_eq!(probe(ctx), vec![false, false, false, true, false, false]);
+}
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{BasicCapabilities, Networking, Pipes, Polling, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+fn names<R: RuleSet>(ruleset: &R) -> HashSet<&'static str> {
+    ruleset.simple_rules().into_iter()
+        .chain(ruleset.conditional_rules().into_keys())
+        .map(|syscall| syscall.name())
+        .collect()
+}
+
+#[test]
+/// The `*at` variants are available everywhere.
+fn at_syscalls() {
+    let io = names(&SystemIO::everything().allow_directory_ops());
+    for name in ["openat", "unlinkat", "mkdirat", "renameat", "getdents64", "statx"] {
+        assert!(io.contains(name), "missing {}", name);
+    }
+    assert!(names(&Polling::everything()).contains("ppoll"));
+    assert!(names(&Pipes).contains("pipe2"));
+    assert!(names(&SystemIO::nothing().allow_open_readonly()).contains("openat"));
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+/// The legacy syscalls are included on x86_64.
+fn legacy_syscalls_x86_64() {
+    let io = SystemIO::everything().allow_directory_ops().simple_rules();
+    for syscall in [Sysno::open, Sysno::stat, Sysno::lstat, Sysno::newfstatat, Sysno::unlink, Sysno::mkdir, Sysno::rename] {
+        assert!(io.contains(&syscall), "missing {}", syscall);
+    }
+    assert!(SystemIO::nothing().allow_open_readonly().conditional_rules().contains_key(&Sysno::open));
+    assert!(BasicCapabilities.simple_rules().contains(&Sysno::readlink));
+    assert!(Polling::everything().simple_rules().contains(&Sysno::poll));
+    assert!(Networking::nothing().allow_running_tcp_clients().simple_rules().contains(&Sysno::epoll_wait));
+}
+
+#[test]
+#[cfg(target_arch = "aarch64")]
+/// The legacy syscalls don't exist on aarch64, so only the `*at` variants are included.
+fn legacy_syscalls_aarch64() {
+    let io = names(&SystemIO::everything().allow_directory_ops());
+    for name in ["open", "stat", "lstat", "unlink", "mkdir", "rename"] {
+        assert!(!io.contains(name), "{} should not be included", name);
+    }
+    assert!(io.contains("fstatat"));
+    assert!(BasicCapabilities.simple_rules().contains(&Sysno::readlinkat));
+    assert!(!names(&Polling::everything()).contains("poll"));
+    assert!(!names(&Networking::nothing().allow_running_tcp_clients()).contains("epoll_wait"));
+}
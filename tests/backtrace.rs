@@ -0,0 +1,82 @@
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Output};
+
+use extrasafe::*;
+use extrasafe::builtins::{Backtrace, SystemIO};
+
+/// Set in the environment of the child process, which runs the test that panics.
+const CHILD_ENV: &str = "EXTRASAFE_BACKTRACE_TEST_CHILD";
+
+/// Run `test` from this test binary in a child process with backtraces enabled.
+fn run_child(test: &str) -> Output {
+    Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", test, "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, "1")
+        .env("RUST_BACKTRACE", "1")
+        .output().unwrap()
+}
+
+/// Apply a restrictive filter that kills the process on violations, optionally with
+/// [`Backtrace`] enabled, and panic.
+fn panic_sandboxed(backtrace: bool) {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+
+    let mut ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stderr()).unwrap()
+        .kill_process_on_violation();
+    if backtrace {
+        ctx = ctx.enable(Backtrace).unwrap();
+    }
+    ctx.apply_to_current_thread().unwrap();
+
+    panic!("sandboxed panic");
+}
+
+#[test]
+fn child_with_backtrace() {
+    panic_sandboxed(true);
+}
+
+#[test]
+fn child_without_backtrace() {
+    panic_sandboxed(false);
+}
+
+#[test]
+/// With `Backtrace` enabled, the panic prints a symbolized backtrace and the test fails normally.
+fn backtrace_printed() {
+    let output = run_child("child_with_backtrace");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.signal(), None, "child was killed: {}", stderr);
+    assert!(stderr.contains("sandboxed panic"), "no panic message: {}", stderr);
+    assert!(stderr.contains("stack backtrace:"), "no backtrace: {}", stderr);
+    assert!(stderr.contains("panic_sandboxed"), "backtrace not symbolized: {}", stderr);
+}
+
+#[test]
+/// Without `Backtrace`, the process is killed while printing the backtrace.
+fn killed_without_backtrace() {
+    let output = run_child("child_without_backtrace");
+    assert_eq!(output.status.signal(), Some(libc::SIGSYS));
+}
+
+#[test]
+/// `Backtrace` only allows opening files read-only, so it conflicts with `allow_open` unless a
+/// precedence is given.
+fn backtrace_with_open() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_open().yes_really()).unwrap()
+        .enable(Backtrace);
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(..))));
+
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_open().yes_really()).unwrap()
+        .enable_with_precedence(Backtrace, Precedence::Simple);
+    assert!(res.is_ok());
+}
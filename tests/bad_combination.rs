@@ -34,7 +34,7 @@ fn invalid_combination_new_simple() {
     );
 
     let err = res.unwrap_err();
-    assert_eq!(err.to_string(), "A conditional rule on syscall `write` from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `SystemIO`.");
+    assert_eq!(err.to_string(), "A conditional rule on syscall `write` (arg0 == 0x1) from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `SystemIO`.");
 }
 
 #[test]
@@ -46,7 +46,7 @@ fn invalid_combination_new_conditional() {
     assert!(res.is_err(), "Extrasafe didn't fail when adding conflicting rules");
 
     let err = res.unwrap_err();
-    assert_eq!(err.to_string(), "A conditional rule on syscall `write` from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `SystemIO`.");
+    assert_eq!(err.to_string(), "A conditional rule on syscall `write` (arg0 == 0x1) from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `SystemIO`.");
 }
 
 #[test]
@@ -62,7 +62,7 @@ fn invalid_combination_new_simple_different_name() {
     );
 
     let err = res.unwrap_err();
-    assert_eq!(err.to_string(), "A conditional rule on syscall `write` from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `JustWrite`.");
+    assert_eq!(err.to_string(), "A conditional rule on syscall `write` (arg0 == 0x1) from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `JustWrite`.");
 }
 
 #[test]
@@ -74,7 +74,7 @@ fn invalid_combination_new_conditional_different_name() {
     assert!(res.is_err(), "Extrasafe didn't fail when adding conflicting rules");
 
     let err = res.unwrap_err();
-    assert_eq!(err.to_string(), "A conditional rule on syscall `write` from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `JustWrite`.");
+    assert_eq!(err.to_string(), "A conditional rule on syscall `write` (arg0 == 0x1) from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `JustWrite`.");
 }
 
 #[test]
@@ -89,7 +89,7 @@ fn invalid_combination_read_and_stdin() {
     assert!(res.is_err(), "Extrasafe didn't fail when adding conflicting rules");
 
     let err = res.unwrap_err();
-    assert_eq!(err.to_string(), "A conditional rule on syscall `read` from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `SystemIO`.");
+    assert_eq!(err.to_string(), "A conditional rule on syscall `read` (arg0 == 0x0) from RuleSet `SystemIO` would be overridden by a simple rule from RuleSet `SystemIO`.");
 }
 
 #[test]
@@ -154,3 +154,30 @@ fn not_invalid_combination_duplicate_conditional2() {
     let res = res.unwrap().apply_to_current_thread();
     assert!(res.is_ok());
 }
+
+#[test]
+/// The error contains the conditional rule that would be overridden.
+fn invalid_combination_structured() {
+    let res = extrasafe::SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(JustWrite);
+
+    let Err(ExtraSafeError::ConditionalNoEffectError(sysno, conditional, simple, rule)) = res else {
+        panic!("Extrasafe didn't fail when adding conflicting rules");
+    };
+    assert_eq!(sysno, Sysno::write);
+    assert_eq!(conditional, "SystemIO");
+    assert_eq!(simple, "JustWrite");
+    assert_eq!(rule.syscall, Sysno::write);
+    assert_eq!(rule.argument_filters.len(), 1);
+    assert_eq!(rule.argument_filters[0].arg_idx, 0);
+    assert!([1, 2].contains(&rule.argument_filters[0].value));
+}
+
+#[test]
+fn argument_filter_display() {
+    assert_eq!(seccomp_arg_filter!(arg1 <= 5).to_string(), "arg1 <= 0x5");
+    assert_eq!(seccomp_arg_filter!(arg2 & 0x3 == 0).to_string(), "arg2 & 0x3 == 0x0");
+}
@@ -0,0 +1,40 @@
+use extrasafe::*;
+use extrasafe::builtins::{Memory, SystemIO};
+
+#[test]
+/// The report counts the syscalls and conditional rules of a policy with both kinds of rules,
+/// and estimates the filter size on each architecture.
+fn budget_report_mixed_policy() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_read()
+            .allow_close()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Memory::nothing()
+            .allow_mmap_no_wx()).unwrap();
+
+    let report = ctx.budget_report().unwrap();
+    assert_eq!(report.syscalls, ctx.enabled_syscalls().len());
+    assert!(report.conditional_rules >= 3, "expected conditional rules for write and mmap: {}", report);
+
+    let arches: Vec<_> = report.instructions.iter().map(|&(arch, _)| arch).collect();
+    assert_eq!(arches, [SeccompilerTargetArch::x86_64, SeccompilerTargetArch::aarch64]);
+    assert!(report.instructions.iter().all(|&(_, len)| len > 0));
+
+    // The count for the current architecture is the real filter length.
+    let native = SeccompilerTargetArch::try_from(std::env::consts::ARCH).unwrap();
+    let native_len = report.instructions.iter()
+        .find(|&&(arch, _)| arch == native)
+        .unwrap().1;
+    assert_eq!(native_len, ctx.filter_len().unwrap());
+
+    assert!(!report.is_near_limit());
+    assert!(!report.to_string().contains("warning"));
+}
+
+#[test]
+fn budget_report_no_rules() {
+    let res = SafetyContext::new().budget_report();
+    assert!(matches!(res, Err(ExtraSafeError::NoRulesEnabled)));
+}
@@ -0,0 +1,35 @@
+use std::collections::HashSet;
+
+use extrasafe::builtins;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn builtins_all_names() {
+    let all = builtins::all();
+    assert!(!all.is_empty());
+
+    let names: HashSet<&str> = all.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names.len(), all.len(), "duplicate names in builtins::all()");
+    for name in ["BasicCapabilities", "SystemIO", "Networking", "Time", "Pipes", "Threads", "ForkAndExec"] {
+        assert!(names.contains(name), "missing {}", name);
+    }
+
+    for (name, syscalls) in &all {
+        assert!(!syscalls.is_empty(), "{} allows no syscalls", name);
+    }
+}
+
+#[test]
+/// Conditional rules and functions returning `YesReally` are included.
+fn builtins_all_syscalls() {
+    let all = builtins::all();
+    let syscalls = |name: &str| all.iter()
+        .find(|(ruleset, _)| *ruleset == name)
+        .map(|(_, syscalls)| syscalls.clone())
+        .unwrap();
+
+    assert!(syscalls("Time").contains(&Sysno::clock_gettime));
+    assert!(syscalls("SystemIO").contains(&Sysno::openat));
+    assert!(syscalls("Terminal").contains(&Sysno::ioctl));
+    assert!(syscalls("Memory").contains(&Sysno::mprotect));
+}
@@ -0,0 +1,65 @@
+#![allow(unsafe_code)]
+// allow unsafe to call prctl directly
+
+use extrasafe::*;
+use extrasafe::builtins::{SystemIO, YesReally, danger_zone::Capabilities};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn capabilities_nothing() {
+    let capabilities = Capabilities::nothing();
+    assert!(capabilities.simple_rules().is_empty());
+    assert!(capabilities.conditional_rules().is_empty());
+    assert_eq!(capabilities.name(), "Capabilities");
+}
+
+#[test]
+fn capabilities_get_set() {
+    let capget = Capabilities::nothing()
+        .allow_capget();
+    assert_eq!(capget.simple_rules(), vec![Sysno::capget]);
+
+    let capset: YesReally<Capabilities> = Capabilities::nothing()
+        .allow_capset();
+    assert_eq!(capset.yes_really().simple_rules(), vec![Sysno::capset]);
+}
+
+#[test]
+fn capabilities_prctl_options() {
+    let rules = Capabilities::nothing()
+        .allow_drop_privileges()
+        .conditional_rules();
+    assert_eq!(rules.len(), 1);
+
+    let filters: Vec<SeccompArgumentFilter> = rules[&Sysno::prctl].iter()
+        .map(|rule| {
+            assert_eq!(rule.argument_filters.len(), 1);
+            rule.argument_filters[0].clone()
+        })
+        .collect();
+    let expected = vec![
+        SeccompArgumentFilter::new(0, SeccompilerComparator::Eq, libc::PR_CAPBSET_DROP as u64),
+        SeccompArgumentFilter::new(0, SeccompilerComparator::Eq, libc::PR_SET_NO_NEW_PRIVS as u64),
+    ];
+    assert_eq!(filters, expected);
+}
+
+#[test]
+fn capabilities_other_prctl_denied() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Capabilities::nothing()
+            .allow_drop_privileges()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: the arguments are valid for PR_SET_NO_NEW_PRIVS
+    let rc = unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) };
+    assert_eq!(rc, 0);
+
+    // SAFETY: PR_GET_DUMPABLE takes no other arguments
+    let rc = unsafe { libc::prctl(libc::PR_GET_DUMPABLE, 0, 0, 0, 0) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,30 @@
+use extrasafe::*;
+use extrasafe::builtins::{Memory, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// Syscalls that aren't allowed are reported once each, in trace order.
+fn check_trace_denied() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_read()
+            .allow_close()).unwrap();
+
+    let trace = [Sysno::read, Sysno::openat, Sysno::close, Sysno::socket, Sysno::openat, Sysno::exit_group];
+    assert_eq!(ctx.check_trace(&trace), vec![Sysno::openat, Sysno::socket]);
+    assert!(ctx.check_trace(&[Sysno::read, Sysno::close]).is_empty());
+}
+
+#[test]
+/// Syscalls with conditional rules are reported since the trace has no arguments, including
+/// basic capabilities that have been restricted.
+fn check_trace_conditional() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .enable(Memory::nothing()
+            .allow_mmap()).unwrap();
+
+    let trace = [Sysno::write, Sysno::mmap, Sysno::brk, Sysno::munmap];
+    assert_eq!(ctx.check_trace(&trace), vec![Sysno::write, Sysno::mmap]);
+}
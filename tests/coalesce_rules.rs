@@ -0,0 +1,101 @@
+#![allow(unsafe_code)]
+// allow unsafe to call socket directly
+
+use extrasafe::*;
+use extrasafe::builtins::{Networking, SystemIO, network::AddressFamily};
+use extrasafe::syscalls::Sysno;
+
+use std::collections::HashMap;
+
+/// Allows only unix stream sockets, which is already covered by allowing all unix sockets.
+struct UnixStreams;
+impl RuleSet for UnixStreams {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        let rule = SeccompRule::new(Sysno::socket)
+            .and_condition(seccomp_arg_filter!(arg0 == libc::AF_UNIX as u64))
+            .and_condition(seccomp_arg_filter!(arg1 == libc::SOCK_STREAM as u64));
+        HashMap::from([(Sysno::socket, vec![rule])])
+    }
+
+    fn name(&self) -> &'static str {
+        "UnixStreams"
+    }
+}
+
+fn base_context() -> SafetyContext {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Networking::nothing()
+            .allow_address_families(&[AddressFamily::Inet, AddressFamily::Unix])).unwrap()
+}
+
+/// Returns the errno of creating a socket, or 0 if it succeeded.
+fn socket_errno(family: libc::c_int, kind: libc::c_int) -> i32 {
+    // SAFETY: socket has no memory safety requirements
+    let fd = unsafe { libc::socket(family, kind, 0) };
+    if fd < 0 {
+        return std::io::Error::last_os_error().raw_os_error().unwrap();
+    }
+    // SAFETY: fd was just created and is not used afterwards
+    let _ = unsafe { libc::close(fd) };
+    0
+}
+
+/// The outcome of creating each kind of socket, after applying `ctx` in a new thread.
+fn outcomes(ctx: SafetyContext) -> Vec<i32> {
+    std::thread::spawn(move || {
+        ctx.apply_to_current_thread().unwrap();
+        [
+            (libc::AF_INET, libc::SOCK_STREAM),
+            (libc::AF_INET, libc::SOCK_DGRAM),
+            (libc::AF_UNIX, libc::SOCK_STREAM),
+            (libc::AF_UNIX, libc::SOCK_DGRAM),
+            (libc::AF_INET6, libc::SOCK_STREAM),
+            (libc::AF_NETLINK, libc::SOCK_RAW),
+        ].into_iter()
+            .map(|(family, kind)| socket_errno(family, kind))
+            .collect()
+    }).join().unwrap()
+}
+
+#[test]
+/// A rule covered by another rule on the same syscall does not take up space in the filter.
+fn coalesce_covered_rule() {
+    let base_len = base_context().filter_len().unwrap();
+    let covered = base_context()
+        .enable(UnixStreams).unwrap();
+    assert_eq!(covered.filter_len().unwrap(), base_len);
+
+    // Rules that are not covered by each other are all kept.
+    let uncovered = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Networking::nothing()
+            .allow_address_families(&[AddressFamily::Inet])).unwrap()
+        .enable(UnixStreams).unwrap();
+    assert!(uncovered.filter_len().unwrap() > base_len);
+}
+
+#[test]
+/// Coalescing the rules does not change which sockets can be created.
+fn coalesce_same_outcomes() {
+    let expected = vec![0, 0, 0, 0, libc::EPERM, libc::EPERM];
+    assert_eq!(outcomes(base_context()), expected);
+    assert_eq!(outcomes(base_context().enable(UnixStreams).unwrap()), expected);
+
+    let uncovered = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Networking::nothing()
+            .allow_address_families(&[AddressFamily::Inet])).unwrap()
+        .enable(UnixStreams).unwrap();
+    assert_eq!(outcomes(uncovered), vec![0, 0, 0, libc::EPERM, libc::EPERM, libc::EPERM]);
+}
@@ -0,0 +1,26 @@
+#![cfg(target_arch = "x86_64")]
+#![allow(unsafe_code)]
+// allow unsafe to make syscalls with the x32 bit set
+
+use extrasafe::*;
+
+const X32_SYSCALL_BIT: libc::c_long = 0x4000_0000;
+
+#[test]
+/// x32 syscalls don't match the native rules, so they are denied even if the native syscall is
+/// allowed. Without a filter they fail with `ENOSYS` or succeed, depending on the kernel config.
+fn x32_syscall_denied() {
+    SafetyContext::new()
+        .enable(builtins::ProcessInfo::nothing()
+            .allow_ids()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: getpid takes no arguments
+    let pid = unsafe { libc::syscall(libc::SYS_getpid) };
+    assert!(pid > 0);
+
+    // SAFETY: same as above
+    let rc = unsafe { libc::syscall(X32_SYSCALL_BIT | libc::SYS_getpid) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,48 @@
+#![allow(unsafe_code)]
+// allow unsafe to load the compiled program with prctl directly
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+const BPF_RET: u16 = 0x06;
+
+#[test]
+/// The compiled program is nonempty and ends with a return instruction.
+fn compile_bpf_ends_with_ret() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_read()
+            .allow_stdout()).unwrap();
+    let program = ctx.compile_bpf().unwrap();
+    assert!(!program.is_empty());
+    assert_eq!(program.len(), ctx.filter_len().unwrap());
+
+    let last = program.last().unwrap();
+    assert_eq!(last.code & 0x07, BPF_RET);
+}
+
+#[test]
+/// The compiled program can be loaded without extrasafe.
+fn compile_bpf_load_with_prctl() {
+    let mut program = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .compile_bpf().unwrap();
+
+    let prog = libc::sock_fprog {
+        len: program.len().try_into().unwrap(),
+        filter: program.as_mut_ptr().cast::<libc::sock_filter>(),
+    };
+
+    // SAFETY: PR_SET_NO_NEW_PRIVS takes integer arguments
+    let rc = unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) };
+    assert_eq!(rc, 0);
+    let prog_ptr: *const libc::sock_fprog = &prog;
+    // SAFETY: prog points to a valid program that outlives the call
+    let rc = unsafe { libc::prctl(libc::PR_SET_SECCOMP, libc::SECCOMP_MODE_FILTER, prog_ptr) };
+    assert_eq!(rc, 0);
+
+    let res = std::fs::File::open("/dev/null");
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EPERM));
+}
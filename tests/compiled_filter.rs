@@ -0,0 +1,75 @@
+use std::sync::mpsc::channel;
+use std::thread;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// Compile a filter on the main thread, send it to a worker thread, and have the worker apply it
+/// to itself. The main thread is not affected.
+fn worker_applies_compiled_filter() {
+    let (sender, receiver) = channel::<CompiledFilter>();
+
+    let worker = thread::spawn(move || {
+        let filter = receiver.recv().unwrap();
+        filter.apply_to_current_thread().unwrap();
+
+        let res = std::fs::File::open("/etc/passwd");
+        assert!(res.is_err(), "worker opened a file after applying filter");
+    });
+
+    let filter = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .into_filter().unwrap();
+    sender.send(filter.clone()).unwrap();
+
+    let res = worker.join();
+    assert!(res.is_ok(), "worker failed: {:?}", res.unwrap_err());
+
+    // The same filter can be applied again elsewhere
+    let res = thread::spawn(move || {
+        filter.apply_to_current_thread().unwrap();
+        std::fs::File::open("/etc/passwd").is_err()
+    }).join().unwrap();
+    assert!(res, "second worker opened a file after applying filter");
+
+    let res = std::fs::File::open("/etc/passwd");
+    assert!(res.is_ok(), "main thread was restricted by worker's filter");
+}
+
+#[test]
+/// Compile a filter once and apply the same filter, by reference, on several threads.
+fn compile_once_apply_on_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<CompiledFilter>();
+
+    let filter = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .into_filter().unwrap();
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..2).map(|_| scope.spawn(|| {
+            filter.apply_to_current_thread().unwrap();
+            std::fs::File::open("/etc/passwd").is_err()
+        })).collect();
+
+        for worker in workers {
+            let res = worker.join().unwrap();
+            assert!(res, "worker opened a file after applying filter");
+        }
+    });
+
+    let res = std::fs::File::open("/etc/passwd");
+    assert!(res.is_ok(), "main thread was restricted by workers' filter");
+}
+
+#[test]
+fn into_filter_no_rules() {
+    let res = SafetyContext::new()
+        .into_filter();
+    assert!(matches!(res, Err(ExtraSafeError::NoRulesEnabled)));
+}
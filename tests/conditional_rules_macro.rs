@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use extrasafe::*;
+use extrasafe::syscalls::Sysno;
+
+const AF_NETLINK: u64 = libc::AF_NETLINK as u64;
+const AF_UNIX: u64 = libc::AF_UNIX as u64;
+const SOCK_RAW: u64 = libc::SOCK_RAW as u64;
+const SOCK_TYPE_MASK: u64 = 0xf;
+
+/// Allows raw netlink and unix sockets, written out by hand.
+struct HandWritten;
+impl RuleSet for HandWritten {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        let netlink = SeccompRule::new(Sysno::socket)
+            .and_condition(SeccompArgumentFilter::new(0, SeccompilerComparator::Eq, AF_NETLINK))
+            .and_condition(SeccompArgumentFilter::new(1, SeccompilerComparator::MaskedEq(SOCK_TYPE_MASK), SOCK_RAW));
+        let unix = SeccompRule::new(Sysno::socket)
+            .and_condition(SeccompArgumentFilter::new(0, SeccompilerComparator::Eq, AF_UNIX));
+        let stdout = SeccompRule::new(Sysno::write)
+            .and_condition(SeccompArgumentFilter::new(0, SeccompilerComparator::Eq, 1))
+            .and_condition(SeccompArgumentFilter::new(2, SeccompilerComparator::Le, 4096));
+
+        HashMap::from([
+            (Sysno::socket, vec![netlink, unix]),
+            (Sysno::write, vec![stdout]),
+        ])
+    }
+
+    fn name(&self) -> &'static str {
+        "HandWritten"
+    }
+}
+
+/// The same rules as [`HandWritten`], generated with the macro.
+struct MacroGenerated;
+impl RuleSet for MacroGenerated {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        conditional_rules! {
+            socket: (arg0 == AF_NETLINK) && (arg1 & SOCK_TYPE_MASK == SOCK_RAW);
+            socket: (arg0 == AF_UNIX);
+            write: (arg0 == 1) && (arg2 <= 4096);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "MacroGenerated"
+    }
+}
+
+#[test]
+fn macro_matches_hand_written() {
+    assert_eq!(MacroGenerated.conditional_rules(), HandWritten.conditional_rules());
+
+    let hand_written = SafetyContext::new()
+        .enable(HandWritten).unwrap()
+        .compile_bpf().unwrap();
+    let generated = SafetyContext::new()
+        .enable(MacroGenerated).unwrap()
+        .compile_bpf().unwrap();
+    assert_eq!(generated, hand_written);
+}
+
+#[test]
+fn macro_single_rule() {
+    let rules = conditional_rules! {
+        read: (arg0 == 0)
+    };
+    let expected = SeccompRule::new(Sysno::read)
+        .and_condition(seccomp_arg_filter!(arg0 == 0));
+    assert_eq!(rules, HashMap::from([(Sysno::read, vec![expected])]));
+}
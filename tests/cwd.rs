@@ -0,0 +1,26 @@
+use std::collections::HashSet;
+
+use extrasafe::RuleSet;
+use extrasafe::builtins::Cwd;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn cwd_nothing() {
+    let cwd = Cwd::nothing();
+    assert!(cwd.simple_rules().is_empty());
+    assert!(cwd.conditional_rules().is_empty());
+    assert_eq!(cwd.name(), "Cwd");
+}
+
+#[test]
+fn cwd_each_group() {
+    assert_eq!(Cwd::nothing().allow_getcwd().simple_rules(), vec![Sysno::getcwd]);
+
+    let chdir: HashSet<Sysno> = Cwd::nothing().allow_chdir().simple_rules().into_iter().collect();
+    assert_eq!(chdir, HashSet::from([Sysno::chdir, Sysno::fchdir]));
+
+    assert_eq!(Cwd::read_only().simple_rules(), vec![Sysno::getcwd]);
+
+    let everything: HashSet<Sysno> = Cwd::everything().simple_rules().into_iter().collect();
+    assert_eq!(everything, HashSet::from([Sysno::getcwd, Sysno::chdir, Sysno::fchdir]));
+}
@@ -0,0 +1,61 @@
+#![allow(unsafe_code)]
+// allow unsafe to call getrandom directly
+
+use extrasafe::*;
+use extrasafe::builtins::{BasicCapabilities, SystemIO, Time};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// A denied syscall is removed from an enabled ruleset.
+///
+/// `Time` does not allow `settimeofday`, so `clock_getres` is denied instead.
+fn deny_simple_rule() {
+    let ctx = SafetyContext::new()
+        .enable(Time::nothing()
+            .allow_gettime()).unwrap()
+        .deny(Sysno::clock_getres);
+
+    assert_eq!(ctx.check_trace(&[Sysno::clock_gettime, Sysno::clock_getres]), vec![Sysno::clock_getres]);
+
+    // Enabling the ruleset again after denying does not allow the syscall again.
+    let ctx = ctx
+        .enable(Time::nothing()
+            .allow_gettime()).unwrap();
+    assert_eq!(ctx.check_trace(&[Sysno::clock_gettime, Sysno::clock_getres]), vec![Sysno::clock_getres]);
+}
+
+#[test]
+/// Conditional rules and basic capabilities can be denied as well.
+fn deny_conditional_and_basic() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()
+            .allow_read()).unwrap()
+        .deny(Sysno::write)
+        .deny(Sysno::getrandom);
+
+    assert!(BasicCapabilities.simple_rules().contains(&Sysno::getrandom));
+    assert_eq!(ctx.check_trace(&[Sysno::read, Sysno::write, Sysno::getrandom]),
+        vec![Sysno::write, Sysno::getrandom]);
+}
+
+#[test]
+/// A denied basic capability is not allowed when the filter is applied.
+fn deny_applied() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .deny(Sysno::getrandom);
+
+    std::thread::spawn(move || {
+        ctx.apply_to_current_thread().unwrap();
+
+        let mut buf = [0u8; 8];
+        // SAFETY: buf is valid to write buf.len() bytes to
+        let rc = unsafe { libc::getrandom(buf.as_mut_ptr().cast(), buf.len(), 0) };
+        assert_eq!(rc, -1);
+        assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+    }).join().unwrap();
+}
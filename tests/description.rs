@@ -0,0 +1,22 @@
+use extrasafe::RuleSet;
+use extrasafe::builtins::{BasicCapabilities, Networking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// Builtin rulesets describe what they allow.
+fn builtin_descriptions() {
+    assert!(!Networking::nothing().description().is_empty());
+    assert!(!SystemIO::nothing().description().is_empty());
+    assert!(!BasicCapabilities.description().is_empty());
+}
+
+#[test]
+/// Rulesets without a description return an empty string, including through references and boxes.
+fn default_description() {
+    assert_eq!(Sysno::write.description(), "");
+
+    let boxed: Box<dyn RuleSet> = Box::new(SystemIO::nothing());
+    assert_eq!(boxed.description(), SystemIO::nothing().description());
+    let networking = Networking::nothing();
+    assert_eq!(RuleSet::description(&&networking), networking.description());
+}
@@ -0,0 +1,74 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use extrasafe::*;
+use extrasafe::builtins::{SystemIO, Time};
+use extrasafe::syscalls::Sysno;
+
+fn base() -> SafetyContext {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+}
+
+#[test]
+fn diff_identical() {
+    let diff = base().diff(&base());
+    assert!(diff.is_empty());
+    assert!(!diff.widens());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+/// Enabling one more ruleset shows up as added simple syscalls, and as removed syscalls in the
+/// other direction.
+fn diff_added_ruleset() {
+    let old = base();
+    let new = base()
+        .enable(Time::nothing()
+            .allow_gettime()).unwrap();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.added_simple, BTreeSet::from([Sysno::clock_gettime, Sysno::clock_getres]));
+    assert!(diff.removed_simple.is_empty());
+    assert!(diff.added_conditional.is_empty());
+    assert!(diff.removed_conditional.is_empty());
+    assert!(diff.widens());
+
+    let diff = new.diff(&old);
+    assert!(diff.added_simple.is_empty());
+    assert_eq!(diff.removed_simple, BTreeSet::from([Sysno::clock_gettime, Sysno::clock_getres]));
+    assert!(!diff.widens());
+    assert!(!diff.is_empty());
+}
+
+#[test]
+fn diff_conditional_rules() {
+    let old = base();
+    let new = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap();
+
+    let diff = old.diff(&new);
+    let stderr = SeccompRule::new(Sysno::write)
+        .and_condition(seccomp_arg_filter!(arg0 == 2));
+    assert_eq!(diff.added_conditional, BTreeMap::from([("SystemIO", vec![stderr])]));
+    assert!(diff.removed_conditional.is_empty());
+    assert!(diff.added_simple.is_empty());
+    assert!(diff.widens());
+    assert_eq!(diff.to_string(), "+ write if arg0 == 0x2 (from RuleSet `SystemIO`)\n");
+}
+
+#[test]
+/// Changing a syscall from conditional to simple is both an added and a removed rule.
+fn diff_conditional_to_simple() {
+    let old = base();
+    let new = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_write()).unwrap();
+
+    let diff = old.diff(&new);
+    assert!(diff.added_simple.contains(&Sysno::write));
+    assert!(diff.removed_conditional.contains_key("SystemIO"));
+    assert!(diff.widens());
+}
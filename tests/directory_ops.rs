@@ -0,0 +1,93 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+use std::collections::HashSet;
+
+fn simple_rules(io: &SystemIO) -> HashSet<Sysno> {
+    io.simple_rules().into_iter().collect()
+}
+
+#[test]
+fn systemio_rename() {
+    assert_eq!(simple_rules(&SystemIO::nothing().allow_rename()),
+        HashSet::from([Sysno::rename, Sysno::renameat, Sysno::renameat2]));
+}
+
+#[test]
+fn systemio_directory_ops() {
+    assert_eq!(simple_rules(&SystemIO::nothing().allow_directory_ops()),
+        HashSet::from([Sysno::mkdir, Sysno::mkdirat, Sysno::rmdir,
+            Sysno::unlink, Sysno::unlinkat,
+            Sysno::rename, Sysno::renameat, Sysno::renameat2]));
+}
+
+#[test]
+fn systemio_directory_ops_allowed() {
+    let dir = tempfile::tempdir().unwrap();
+    let created = dir.path().join("created");
+    let renamed = dir.path().join("renamed");
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()
+            .allow_directory_ops()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    std::fs::create_dir(&created).unwrap();
+    std::fs::rename(&created, &renamed).unwrap();
+    std::fs::remove_dir(&renamed).unwrap();
+}
+
+#[test]
+fn systemio_symlink_ops() {
+    let mut expected = HashSet::from([Sysno::readlinkat]);
+    #[cfg(target_arch = "x86_64")]
+    let _new = expected.insert(Sysno::readlink);
+    assert_eq!(simple_rules(&SystemIO::nothing().allow_symlink_ops()), expected);
+}
+
+#[test]
+/// Creating symlinks is separate from reading them.
+fn systemio_symlink_create() {
+    let mut expected = HashSet::from([Sysno::symlinkat]);
+    #[cfg(target_arch = "x86_64")]
+    let _new = expected.insert(Sysno::symlink);
+    assert_eq!(simple_rules(&SystemIO::nothing().allow_symlink_create()), expected);
+
+    let read_only = simple_rules(&SystemIO::nothing().allow_symlink_ops());
+    assert!(read_only.is_disjoint(&expected));
+}
+
+#[test]
+fn systemio_symlink_create_allowed() {
+    let dir = tempfile::tempdir().unwrap();
+    let link = dir.path().join("link");
+    let denied = dir.path().join("denied");
+
+    std::thread::spawn(move || {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()
+                .allow_symlink_ops()
+                .allow_symlink_create()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        std::os::unix::fs::symlink("target", &link).unwrap();
+        assert_eq!(std::fs::read_link(&link).unwrap(), std::path::Path::new("target"));
+    }).join().unwrap();
+
+    std::thread::spawn(move || {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()
+                .allow_symlink_ops()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        let res = std::os::unix::fs::symlink("target", &denied);
+        assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::PermissionDenied);
+    }).join().unwrap();
+}
@@ -0,0 +1,78 @@
+use extrasafe::*;
+use extrasafe::builtins::{Networking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// Disabling a narrow ruleset removes its syscalls from a broad one.
+fn disable_simple() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::everything()).unwrap()
+        .disable(SystemIO::nothing()
+            .allow_unlink());
+
+    let enabled = ctx.enabled_syscalls();
+    assert!(!enabled.contains(&Sysno::unlinkat));
+    #[cfg(target_arch = "x86_64")]
+    assert!(!enabled.contains(&Sysno::unlink));
+    assert!(enabled.contains(&Sysno::openat));
+    assert!(enabled.contains(&Sysno::read));
+}
+
+#[test]
+/// Syscalls are removed even if they were enabled by several rulesets.
+fn disable_multiple_sources() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_read()
+            .allow_close()).unwrap()
+        .enable(Networking::nothing()
+            .allow_running_tcp_clients()).unwrap()
+        .disable(SystemIO::nothing()
+            .allow_read());
+
+    let enabled = ctx.enabled_syscalls();
+    assert!(!enabled.contains(&Sysno::read));
+    assert!(!enabled.contains(&Sysno::readv));
+    assert!(enabled.contains(&Sysno::close));
+    assert!(enabled.contains(&Sysno::recvfrom));
+
+    // enabling again after disabling works
+    let ctx = ctx
+        .enable(SystemIO::nothing()
+            .allow_read()).unwrap();
+    assert!(ctx.enabled_syscalls().contains(&Sysno::read));
+}
+
+#[test]
+/// Only identical conditional rules are removed.
+fn disable_conditional() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .disable(SystemIO::nothing()
+            .allow_stdout());
+    assert!(ctx.enabled_syscalls().contains(&Sysno::write));
+
+    let stderr_only = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stderr()).unwrap();
+    assert_eq!(ctx.compile_bpf().unwrap(), stderr_only.compile_bpf().unwrap());
+
+    // disabling all of the conditional rules removes the syscall
+    let ctx = ctx
+        .disable(SystemIO::nothing()
+            .allow_stderr());
+    assert!(!ctx.enabled_syscalls().contains(&Sysno::write));
+}
+
+#[test]
+/// Conditional rules don't remove a syscall that is allowed unconditionally.
+fn disable_conditional_keeps_simple() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_write()).unwrap()
+        .disable(SystemIO::nothing()
+            .allow_stdout());
+    assert!(ctx.enabled_syscalls().contains(&Sysno::write));
+}
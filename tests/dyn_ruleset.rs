@@ -0,0 +1,70 @@
+use std::collections::BTreeSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{Memory, Pipes, SystemIO, Time};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// Rulesets of different types can be stored together as trait objects and enabled in a loop.
+fn enable_all_boxed() {
+    let rulesets: Vec<Box<dyn RuleSet>> = vec![
+        Box::new(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()),
+        Box::new(Time::nothing()
+            .allow_gettime()),
+        Box::new(Pipes),
+        Box::new(Memory::basic_allocation()),
+        Box::new(Sysno::close),
+    ];
+
+    let ctx = SafetyContext::new()
+        .enable_all(rulesets).unwrap();
+    assert_eq!(ctx.enabled_syscalls(), BTreeSet::from([
+        Sysno::write,
+        Sysno::clock_gettime, Sysno::clock_getres,
+        Sysno::pipe, Sysno::pipe2,
+        Sysno::brk, Sysno::mmap, Sysno::munmap,
+        Sysno::close,
+    ]));
+
+    ctx.apply_to_current_thread().unwrap();
+}
+
+#[test]
+fn enable_boxed() {
+    let ruleset: Box<dyn RuleSet> = Box::new(Pipes);
+    let ctx = SafetyContext::new()
+        .enable(ruleset).unwrap();
+    assert_eq!(ctx.enabled_syscalls(), BTreeSet::from([Sysno::pipe, Sysno::pipe2]));
+}
+
+#[test]
+fn enable_all_error() {
+    let rulesets: Vec<Box<dyn RuleSet>> = vec![
+        Box::new(SystemIO::nothing()
+            .allow_stdout()),
+        Box::new(SystemIO::nothing()
+            .allow_write()),
+    ];
+
+    let res = SafetyContext::new()
+        .enable_all(rulesets);
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::write, "SystemIO", "SystemIO", _))));
+}
+
+#[test]
+/// All builtins return their simple rules the same way, so they can be used through the same
+/// trait object.
+fn simple_rules_through_trait_objects() {
+    let rulesets: [&dyn RuleSet; 2] = [&Pipes, &Time::nothing().allow_gettime()];
+    let syscalls: BTreeSet<Sysno> = rulesets.iter()
+        .flat_map(|ruleset| ruleset.simple_rules())
+        .collect();
+    assert_eq!(syscalls, BTreeSet::from([Sysno::pipe, Sysno::pipe2, Sysno::clock_gettime, Sysno::clock_getres]));
+
+    let names: Vec<&str> = rulesets.iter()
+        .map(|ruleset| ruleset.name())
+        .collect();
+    assert_eq!(names, vec!["Pipes", "Time"]);
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{SystemIO, Time};
+use extrasafe::syscalls::Sysno;
+
+fn time_syscalls() -> BTreeSet<Sysno> {
+    BTreeSet::from([Sysno::clock_gettime, Sysno::clock_getres])
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn enable_on_x86_64() {
+    let ctx = SafetyContext::new()
+        .enable_on_arch(Time::nothing().allow_gettime(), SeccompilerTargetArch::x86_64).unwrap();
+    assert_eq!(ctx.enabled_syscalls(), time_syscalls());
+
+    let ctx = SafetyContext::new()
+        .enable_on_arch(Time::nothing().allow_gettime(), SeccompilerTargetArch::aarch64).unwrap();
+    assert!(ctx.enabled_syscalls().is_empty());
+}
+
+#[test]
+#[cfg(target_arch = "aarch64")]
+fn enable_on_aarch64() {
+    let ctx = SafetyContext::new()
+        .enable_on_arch(Time::nothing().allow_gettime(), SeccompilerTargetArch::aarch64).unwrap();
+    assert_eq!(ctx.enabled_syscalls(), time_syscalls());
+
+    let ctx = SafetyContext::new()
+        .enable_on_arch(Time::nothing().allow_gettime(), SeccompilerTargetArch::x86_64).unwrap();
+    assert!(ctx.enabled_syscalls().is_empty());
+}
+
+#[test]
+/// Conflicts are only reported if the ruleset is enabled on the current architecture.
+fn enable_on_other_arch_no_conflict() {
+    #[cfg(target_arch = "x86_64")]
+    let other = SeccompilerTargetArch::aarch64;
+    #[cfg(target_arch = "aarch64")]
+    let other = SeccompilerTargetArch::x86_64;
+
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .enable_on_arch(SystemIO::nothing()
+            .allow_write(), other);
+    assert!(res.is_ok());
+}
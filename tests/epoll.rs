@@ -0,0 +1,26 @@
+#![allow(unsafe_code)]
+// allow unsafe to call epoll_create1 and epoll_ctl directly
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{Epoll, SystemIO};
+
+#[test]
+fn epoll_create_allowed() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()
+            .allow_close()).unwrap()
+        .enable(Epoll::nothing()
+            .allow_create()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: epoll_create1 has no memory safety requirements
+    let fd = unsafe { libc::epoll_create1(0) };
+    assert!(fd >= 0, "epoll_create1 failed: {:?}", std::io::Error::last_os_error());
+
+    // SAFETY: a null event is allowed with EPOLL_CTL_DEL
+    let rc = unsafe { libc::epoll_ctl(fd, libc::EPOLL_CTL_DEL, 0, std::ptr::null_mut()) };
+    assert_eq!(rc, -1, "epoll_ctl succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,34 @@
+use extrasafe::{ExtraSafeError, SafetyContext};
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// A denied syscall returns the configured errno rather than the default EPERM.
+fn custom_errno_enosys() {
+    SafetyContext::new()
+        .with_errno(libc::ENOSYS as u32)
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let res = std::fs::File::open("/etc/passwd");
+    assert!(res.is_err(), "opening file succeeded erroneously");
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ENOSYS));
+}
+
+#[test]
+/// An errno that doesn't fit in `SECCOMP_RET_DATA` is rejected.
+fn custom_errno_too_large() {
+    let res = SafetyContext::new()
+        .with_errno(0x1_0000)
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .apply_to_current_thread();
+
+    assert!(matches!(res, Err(ExtraSafeError::InvalidErrno(0x1_0000))), "{:?}", res);
+
+    // the filter was not applied
+    let res = std::fs::File::open("/etc/passwd");
+    assert!(res.is_ok(), "Failed to open file after invalid SafetyContext: {:?}", res.unwrap_err());
+}
@@ -0,0 +1,24 @@
+#![allow(unsafe_code)]
+// allow unsafe to call eventfd and timerfd_create directly
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{Eventing, SystemIO};
+
+#[test]
+fn eventing_eventfd_allowed() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Eventing::eventfd()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: eventfd has no memory safety requirements
+    let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC) };
+    assert!(fd >= 0, "eventfd failed: {:?}", std::io::Error::last_os_error());
+
+    // SAFETY: timerfd_create has no memory safety requirements
+    let fd = unsafe { libc::timerfd_create(libc::CLOCK_MONOTONIC, libc::TFD_CLOEXEC) };
+    assert_eq!(fd, -1, "timerfd_create succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
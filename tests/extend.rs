@@ -0,0 +1,46 @@
+use std::collections::BTreeSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{Networking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// Compose a networking-only context with a systemio-only context and apply the result.
+fn extend_networking_with_systemio() {
+    let networking = SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_running_tcp_clients()).unwrap();
+    let systemio = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_open_readonly()
+            .allow_close()).unwrap();
+
+    let expected: BTreeSet<Sysno> = networking.enabled_syscalls()
+        .union(&systemio.enabled_syscalls())
+        .copied()
+        .collect();
+
+    let ctx = networking.extend(systemio).unwrap();
+    assert_eq!(ctx.enabled_syscalls(), expected);
+
+    ctx.apply_to_current_thread().unwrap();
+
+    // readonly open is allowed, but not writing
+    let res = std::fs::File::open("/etc/passwd");
+    assert!(res.is_ok(), "failed to open file: {:?}", res.unwrap_err());
+    let res = std::fs::OpenOptions::new().write(true).open("/etc/passwd");
+    assert!(res.is_err(), "incorrectly succeeded in opening file for writing");
+}
+
+#[test]
+fn extend_conflict() {
+    let base = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap();
+    let other = SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_running_tcp_clients()).unwrap();
+
+    let res = base.extend(other);
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::write, "SystemIO", "Networking", _))));
+}
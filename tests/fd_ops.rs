@@ -0,0 +1,98 @@
+#![allow(unsafe_code)]
+// allow unsafe to call dup and fcntl directly
+
+use std::collections::HashSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{FdOps, FileLocking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+use std::os::unix::io::AsRawFd;
+
+#[test]
+fn fd_ops_nothing() {
+    let fd_ops = FdOps::nothing();
+    assert!(fd_ops.simple_rules().is_empty());
+    assert!(fd_ops.conditional_rules().is_empty());
+    assert_eq!(fd_ops.name(), "FdOps");
+}
+
+#[test]
+fn fd_ops_membership() {
+    let simple: HashSet<Sysno> = FdOps::duplication_only().simple_rules().into_iter().collect();
+    assert!(simple.contains(&Sysno::dup));
+    assert!(simple.contains(&Sysno::dup3));
+    #[cfg(target_arch = "x86_64")]
+    assert!(simple.contains(&Sysno::dup2));
+    assert!(!simple.contains(&Sysno::close_range));
+    assert!(FdOps::duplication_only().conditional_rules().is_empty());
+
+    let everything = FdOps::everything();
+    let simple: HashSet<Sysno> = everything.simple_rules().into_iter().collect();
+    assert!(simple.contains(&Sysno::close_range));
+    assert!(!simple.contains(&Sysno::fcntl));
+    assert_eq!(everything.conditional_rules().keys().collect::<Vec<_>>(), vec![&Sysno::fcntl]);
+}
+
+#[test]
+fn fd_ops_fcntl_commands() {
+    let rules = FdOps::nothing()
+        .allow_fcntl()
+        .conditional_rules();
+    let filters: Vec<SeccompArgumentFilter> = rules[&Sysno::fcntl].iter()
+        .map(|rule| {
+            assert_eq!(rule.argument_filters.len(), 1);
+            rule.argument_filters[0].clone()
+        })
+        .collect();
+    let expected: Vec<SeccompArgumentFilter> = [libc::F_DUPFD, libc::F_DUPFD_CLOEXEC, libc::F_GETFD, libc::F_SETFD].into_iter()
+        .map(|command| SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, command as u32))
+        .collect();
+    assert_eq!(filters, expected);
+}
+
+#[test]
+/// The conditional `fcntl` rules compose with the ones from `FileLocking`.
+fn fd_ops_compose_with_file_locking() {
+    let res = SafetyContext::new()
+        .enable(FdOps::everything()).unwrap()
+        .enable(FileLocking::advisory());
+    assert!(res.is_ok());
+}
+
+#[test]
+fn fd_ops_other_fcntl_denied() {
+    let file = tempfile::tempfile().unwrap();
+    let fd = file.as_raw_fd();
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()
+            .allow_close()).unwrap()
+        .enable(FdOps::everything()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: fd is a valid open file
+    let new_fd = unsafe { libc::dup(fd) };
+    assert!(new_fd >= 0);
+    // SAFETY: new_fd was just created by dup
+    let rc = unsafe { libc::fcntl(new_fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+    assert_eq!(rc, 0);
+    // SAFETY: F_GETFD takes no arguments
+    let rc = unsafe { libc::fcntl(new_fd, libc::F_GETFD) };
+    assert_eq!(rc, libc::FD_CLOEXEC);
+
+    // std uses F_DUPFD_CLOEXEC to clone files
+    let _clone = file.try_clone().unwrap();
+
+    // Other fcntl commands are denied
+    // SAFETY: F_GETFL takes no arguments
+    let rc = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+
+    // SAFETY: new_fd is a valid fd that is not used afterwards
+    let rc = unsafe { libc::close(new_fd) };
+    assert_eq!(rc, 0);
+}
@@ -0,0 +1,118 @@
+#![allow(unsafe_code)]
+// allow unsafe to call sendmsg and recvmsg directly
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::os::unix::net::UnixStream;
+
+use extrasafe::*;
+use extrasafe::builtins::{Networking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+fn simple(networking: &Networking) -> HashSet<Sysno> {
+    networking.simple_rules().into_iter().collect()
+}
+
+#[test]
+fn sendmsg_recvmsg_membership() {
+    let send = simple(&Networking::nothing().allow_sendmsg());
+    assert_eq!(send, HashSet::from([Sysno::sendmsg, Sysno::sendmmsg]));
+
+    let recv = simple(&Networking::nothing().allow_recvmsg());
+    assert_eq!(recv, HashSet::from([Sysno::recvmsg, Sysno::recvmmsg]));
+
+    let fd_passing = Networking::nothing().allow_fd_passing();
+    assert_eq!(simple(&fd_passing), send.union(&recv).copied().collect());
+    assert!(fd_passing.conditional_rules().is_empty());
+}
+
+/// Space for a control message with a single fd, aligned for `cmsghdr`.
+#[repr(C)]
+union ControlBuffer {
+    buf: [u8; 64],
+    _align: libc::cmsghdr,
+}
+
+fn send_fd(socket: &UnixStream, fd: RawFd) -> isize {
+    let mut data = [0u8; 1];
+    let mut iov = libc::iovec { iov_base: data.as_mut_ptr().cast(), iov_len: data.len() };
+    let mut control = ControlBuffer { buf: [0; 64] };
+
+    // SAFETY: all pointers point to valid buffers of the given sizes, and the control buffer is
+    // large enough for a single fd
+    unsafe {
+        let mut msg: libc::msghdr = std::mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.buf.as_mut_ptr().cast();
+        msg.msg_controllen = libc::CMSG_SPACE(std::mem::size_of::<RawFd>() as u32) as _;
+
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(std::mem::size_of::<RawFd>() as u32) as _;
+        libc::CMSG_DATA(cmsg).cast::<RawFd>().write_unaligned(fd);
+
+        libc::sendmsg(socket.as_raw_fd(), &msg, 0)
+    }
+}
+
+fn recv_fd(socket: &UnixStream) -> Option<File> {
+    let mut data = [0u8; 1];
+    let mut iov = libc::iovec { iov_base: data.as_mut_ptr().cast(), iov_len: data.len() };
+    let mut control = ControlBuffer { buf: [0; 64] };
+
+    // SAFETY: all pointers point to valid buffers of the given sizes, and the received fd is
+    // owned by the returned File
+    unsafe {
+        let mut msg: libc::msghdr = std::mem::zeroed();
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.buf.as_mut_ptr().cast();
+        msg.msg_controllen = std::mem::size_of::<ControlBuffer>() as _;
+
+        if libc::recvmsg(socket.as_raw_fd(), &mut msg, 0) != 1 {
+            return None;
+        }
+
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        if cmsg.is_null() || (*cmsg).cmsg_type != libc::SCM_RIGHTS {
+            return None;
+        }
+        let fd = libc::CMSG_DATA(cmsg).cast::<RawFd>().read_unaligned();
+        Some(File::from_raw_fd(fd))
+    }
+}
+
+#[test]
+/// A sandboxed thread that can't open files can still read a file whose fd it receives.
+fn fd_passing_grants_access() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("passed.txt");
+    File::create(&path).unwrap().write_all(b"passed").unwrap();
+
+    let (sender, receiver) = UnixStream::pair().unwrap();
+    let file = File::open(&path).unwrap();
+
+    let sandboxed = std::thread::spawn(move || {
+        SafetyContext::new()
+            .enable(Networking::nothing()
+                .allow_fd_passing()).unwrap()
+            .enable(SystemIO::nothing()
+                .allow_read()
+                .allow_close()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        assert!(File::open(&path).is_err());
+
+        let mut received = recv_fd(&receiver).expect("did not receive an fd");
+        let mut contents = String::new();
+        let _len = received.read_to_string(&mut contents).unwrap();
+        contents
+    });
+
+    assert_eq!(send_fd(&sender, file.as_raw_fd()), 1);
+    assert_eq!(sandboxed.join().unwrap(), "passed");
+}
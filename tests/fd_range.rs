@@ -0,0 +1,99 @@
+#![allow(unsafe_code)]
+// allow unsafe to duplicate fds to fixed numbers and read and write them
+
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use std::thread;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn fd_range_rules() {
+    let rules = SystemIO::nothing()
+        .allow_write_fds(3..=5)
+        .conditional_rules();
+    let expected = SeccompRule::new(Sysno::write)
+        .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Ge, 3))
+        .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Le, 5));
+    assert_eq!(rules[&Sysno::write], vec![expected]);
+
+    let rules = SystemIO::nothing()
+        .allow_read_fds(3..=5)
+        .conditional_rules();
+    assert!(rules.contains_key(&Sysno::read));
+    assert!(rules.contains_key(&Sysno::preadv));
+    assert!(!rules.contains_key(&Sysno::write));
+}
+
+#[test]
+fn fd_range_single_fd() {
+    let rules = SystemIO::nothing()
+        .allow_write_fds(3..=3)
+        .conditional_rules();
+    let expected = SeccompRule::new(Sysno::write)
+        .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Eq, 3));
+    assert_eq!(rules[&Sysno::write], vec![expected]);
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn fd_range_empty() {
+    let io = SystemIO::nothing()
+        .allow_read_fds(5..=3)
+        .allow_write_fds(5..=3);
+    assert!(io.conditional_rules().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "provided fd was negative")]
+fn fd_range_negative() {
+    let _io = SystemIO::nothing()
+        .allow_read_fds(-1..=3);
+}
+
+/// Duplicate `file` to `fd`, replacing whatever was open there.
+fn dup_to(file: &File, fd: libc::c_int) {
+    // SAFETY: fds this high are not used by anything else in the tests
+    let rc = unsafe { libc::dup2(file.as_raw_fd(), fd) };
+    assert_eq!(rc, fd);
+}
+
+#[test]
+/// Only fds within the bounds can be read from and written to.
+fn fd_range_enforced() {
+    let zero = File::open("/dev/zero").unwrap();
+    let null = File::options().write(true).open("/dev/null").unwrap();
+    for fd in [899, 900, 901, 902] {
+        dup_to(&zero, fd);
+    }
+    for fd in [909, 910, 911, 912] {
+        dup_to(&null, fd);
+    }
+
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_read_fds(900..=901)
+                .allow_write_fds(910..=911)).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        let mut buf = [1u8; 8];
+        for (fd, allowed) in [(899, false), (900, true), (901, true), (902, false)] {
+            // SAFETY: buf is valid for its length
+            let rc = unsafe { libc::read(fd, buf.as_mut_ptr().cast(), buf.len()) };
+            assert_eq!(rc >= 0, allowed, "read from fd {}", fd);
+        }
+        for (fd, allowed) in [(909, false), (910, true), (911, true), (912, false)] {
+            // SAFETY: buf is valid for its length
+            let rc = unsafe { libc::write(fd, buf.as_ptr().cast(), buf.len()) };
+            assert_eq!(rc >= 0, allowed, "write to fd {}", fd);
+        }
+    }).join().unwrap();
+
+    for fd in [899, 900, 901, 902, 909, 910, 911, 912] {
+        // SAFETY: these fds were created above and are not used elsewhere
+        let _rc = unsafe { libc::close(fd) };
+    }
+}
@@ -0,0 +1,74 @@
+#![allow(unsafe_code)]
+// allow unsafe to call fcntl directly
+
+use extrasafe::*;
+use extrasafe::builtins::{FileLocking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+use std::os::unix::io::AsRawFd;
+
+#[test]
+fn file_locking_nothing() {
+    let locking = FileLocking::nothing();
+    assert!(locking.simple_rules().is_empty());
+    assert!(locking.conditional_rules().is_empty());
+    assert_eq!(locking.name(), "FileLocking");
+}
+
+#[test]
+fn file_locking_flock() {
+    let locking = FileLocking::nothing()
+        .allow_flock();
+    assert_eq!(locking.simple_rules(), vec![Sysno::flock]);
+    assert!(locking.conditional_rules().is_empty());
+}
+
+#[test]
+fn file_locking_fcntl_commands() {
+    let locking = FileLocking::advisory();
+    assert_eq!(locking.simple_rules(), vec![Sysno::flock]);
+
+    let rules = locking.conditional_rules();
+    assert_eq!(rules.len(), 1);
+    let filters: Vec<SeccompArgumentFilter> = rules[&Sysno::fcntl].iter()
+        .map(|rule| {
+            assert_eq!(rule.argument_filters.len(), 1);
+            rule.argument_filters[0].clone()
+        })
+        .collect();
+    let expected: Vec<SeccompArgumentFilter> = [libc::F_SETLK, libc::F_SETLKW, libc::F_GETLK].into_iter()
+        .map(|command| SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, command as u32))
+        .collect();
+    assert_eq!(filters, expected);
+}
+
+#[test]
+fn file_locking_other_fcntl_denied() {
+    let file = tempfile::tempfile().unwrap();
+    let fd = file.as_raw_fd();
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(FileLocking::advisory()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: fd is a valid open file
+    let rc = unsafe { libc::flock(fd, libc::LOCK_EX) };
+    assert_eq!(rc, 0);
+
+    // SAFETY: lock is a valid flock struct
+    let mut lock: libc::flock = unsafe { std::mem::zeroed() };
+    lock.l_type = libc::F_WRLCK as libc::c_short;
+    lock.l_whence = libc::SEEK_SET as libc::c_short;
+    // SAFETY: F_SETLK takes a pointer to a flock struct
+    let rc = unsafe { libc::fcntl(fd, libc::F_SETLK, &lock) };
+    assert_eq!(rc, 0);
+
+    // Other fcntl commands are denied
+    // SAFETY: F_GETFL takes no arguments
+    let rc = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
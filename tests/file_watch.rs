@@ -0,0 +1,34 @@
+#![allow(unsafe_code)]
+// allow unsafe to call inotify functions directly
+
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{FileWatch, SystemIO};
+
+#[test]
+fn file_watch_inotify_allowed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = CString::new(dir.path().as_os_str().as_bytes()).unwrap();
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(FileWatch::inotify()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: inotify_init1 has no memory safety requirements
+    let fd = unsafe { libc::inotify_init1(0) };
+    assert!(fd >= 0, "inotify_init1 failed: {:?}", std::io::Error::last_os_error());
+
+    // SAFETY: path is a valid nul-terminated string
+    let wd = unsafe { libc::inotify_add_watch(fd, path.as_ptr(), libc::IN_CREATE) };
+    assert!(wd >= 0, "inotify_add_watch failed: {:?}", std::io::Error::last_os_error());
+
+    // SAFETY: fanotify_init has no memory safety requirements
+    let fd = unsafe { libc::fanotify_init(libc::FAN_CLASS_NOTIF, libc::O_RDONLY as u32) };
+    assert_eq!(fd, -1, "fanotify_init succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,48 @@
+#![allow(unsafe_code)]
+// allow unsafe to call statfs directly
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+use std::collections::HashSet;
+use std::ffi::CString;
+
+fn simple_rules(io: &SystemIO) -> HashSet<Sysno> {
+    io.simple_rules().into_iter().collect()
+}
+
+#[test]
+fn systemio_filesystem_metadata() {
+    assert_eq!(simple_rules(&SystemIO::nothing().allow_filesystem_metadata()),
+        HashSet::from([Sysno::statfs, Sysno::fstatfs]));
+
+    // per-file and filesystem metadata are separate
+    let metadata = simple_rules(&SystemIO::nothing().allow_metadata());
+    assert!(metadata.contains(&Sysno::statx));
+    assert!(!metadata.contains(&Sysno::statfs));
+    assert!(!metadata.contains(&Sysno::fstatfs));
+}
+
+#[test]
+fn systemio_filesystem_metadata_allowed() {
+    let path = CString::new("/").unwrap();
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()
+            .allow_filesystem_metadata()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: zeroed statfs is valid
+    let mut buf: libc::statfs = unsafe { std::mem::zeroed() };
+    // SAFETY: path is a valid nul-terminated string and buf is a valid statfs struct
+    let rc = unsafe { libc::statfs(path.as_ptr(), &mut buf) };
+    assert_eq!(rc, 0);
+    assert!(buf.f_bsize > 0);
+
+    // per-file metadata is still denied
+    let res = std::fs::metadata("/");
+    assert!(res.is_err(), "Incorrectly succeeded in calling stat");
+}
@@ -0,0 +1,39 @@
+use extrasafe::*;
+use extrasafe::builtins::{FileLocking, Futex, Memory, SystemIO, Terminal};
+
+/// The maximum number of instructions in a BPF program accepted by the kernel.
+const BPF_MAXINSNS: usize = 4096;
+
+#[test]
+/// The filter length accounts for conditional rules and is within the kernel's limit.
+fn filter_len_conditional_rules() {
+    let simple = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_read()
+            .allow_close()).unwrap();
+    let simple_len = simple.filter_len().unwrap();
+    assert!(simple_len > 0);
+
+    let conditional = simple
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Memory::nothing()
+            .allow_mmap_no_wx()).unwrap()
+        .enable(Terminal::raw_mode()).unwrap()
+        .enable(FileLocking::advisory()).unwrap()
+        .enable(Futex::nothing()
+            .allow_futex()).unwrap();
+    let conditional_len = conditional.filter_len().unwrap();
+    assert!(conditional_len > simple_len);
+    assert!(conditional_len < BPF_MAXINSNS);
+
+    // The context can still be applied afterwards.
+    conditional.apply_to_current_thread().unwrap();
+}
+
+#[test]
+fn filter_len_no_rules() {
+    let res = SafetyContext::new().filter_len();
+    assert!(matches!(res, Err(ExtraSafeError::NoRulesEnabled)));
+}
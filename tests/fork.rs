@@ -0,0 +1,99 @@
+#![allow(unsafe_code)]
+// allow unsafe to call fork and execve directly
+
+use std::collections::HashSet;
+
+use extrasafe::*;
+use extrasafe::builtins::YesReally;
+use extrasafe::builtins::danger_zone::{Fork, ForkAndExec, Threads};
+use extrasafe::syscalls::Sysno;
+
+/// Only compiles if `value` is a `YesReally`.
+fn yes_really<T>(value: YesReally<T>) -> T {
+    value.yes_really()
+}
+
+#[test]
+fn fork_nothing() {
+    let fork = Fork::nothing();
+    assert!(fork.simple_rules().is_empty());
+    assert!(fork.conditional_rules().is_empty());
+    assert_eq!(fork.name(), "Fork");
+}
+
+#[test]
+/// `clone` is allowed without the thread and namespace flags, and exec is never allowed.
+fn fork_membership() {
+    let fork = yes_really(Fork::nothing().allow_fork());
+    let simple: HashSet<Sysno> = fork.simple_rules().into_iter().collect();
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(simple, HashSet::from([Sysno::fork]));
+    #[cfg(not(target_arch = "x86_64"))]
+    assert!(simple.is_empty());
+
+    let rules = fork.conditional_rules();
+    assert_eq!(rules.keys().collect::<Vec<_>>(), vec![&Sysno::clone]);
+    let denied_flags = (libc::CLONE_THREAD | libc::CLONE_NEWNS | libc::CLONE_NEWCGROUP | libc::CLONE_NEWUTS
+        | libc::CLONE_NEWIPC | libc::CLONE_NEWUSER | libc::CLONE_NEWPID | libc::CLONE_NEWNET) as u64;
+    assert_eq!(rules[&Sysno::clone][0].argument_filters, vec![
+        SeccompArgumentFilter::new(0, SeccompilerComparator::MaskedEq(denied_flags), 0),
+    ]);
+
+    let vfork = yes_really(Fork::nothing().allow_vfork());
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(vfork.simple_rules(), vec![Sysno::vfork]);
+    assert!(vfork.conditional_rules().is_empty());
+
+    let everything = yes_really(yes_really(Fork::nothing().allow_fork()).allow_vfork());
+    let simple: HashSet<Sysno> = everything.simple_rules().into_iter().collect();
+    for exec in [Sysno::execve, Sysno::execveat, Sysno::clone3] {
+        assert!(!simple.contains(&exec));
+        assert!(!everything.conditional_rules().contains_key(&exec));
+    }
+}
+
+#[test]
+/// The conditional `clone` rule composes with threads-only creation, but not with rulesets that
+/// allow `clone` unconditionally.
+fn fork_compose() {
+    let res = SafetyContext::new()
+        .enable(Fork::nothing().allow_fork().yes_really()).unwrap()
+        .enable(Threads::nothing().allow_create_threads_only());
+    assert!(res.is_ok());
+
+    let res = SafetyContext::new()
+        .enable(Fork::nothing().allow_fork().yes_really()).unwrap()
+        .enable(ForkAndExec);
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::clone, "Fork", "ForkAndExec", _))));
+}
+
+#[test]
+/// The child can be forked, but it can't exec.
+fn fork_without_exec() {
+    SafetyContext::new()
+        .enable(Fork::nothing().allow_fork().yes_really()).unwrap()
+        .enable(extrasafe::builtins::ProcessControl::nothing().allow_wait()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: the child only makes syscalls and exits without returning
+    let pid = unsafe { libc::fork() };
+    assert!(pid >= 0, "fork failed: {}", std::io::Error::last_os_error());
+    if pid == 0 {
+        let path = c"/bin/true";
+        let argv = [path.as_ptr(), std::ptr::null()];
+        let envp = [std::ptr::null()];
+        // SAFETY: path, argv and envp are valid and null-terminated
+        let rc = unsafe { libc::execve(path.as_ptr(), argv.as_ptr(), envp.as_ptr()) };
+        let errno = std::io::Error::last_os_error().raw_os_error().unwrap_or(0);
+        let code = if rc == -1 && errno == libc::EPERM { 0 } else { 1 };
+        // SAFETY: _exit is async-signal-safe
+        unsafe { libc::_exit(code) };
+    }
+
+    let mut status = 0;
+    // SAFETY: status is a valid pointer
+    let rc = unsafe { libc::waitpid(pid, &mut status, 0) };
+    assert_eq!(rc, pid);
+    assert!(libc::WIFEXITED(status));
+    assert_eq!(libc::WEXITSTATUS(status), 0, "the child was able to exec");
+}
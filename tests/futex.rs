@@ -0,0 +1,63 @@
+#![allow(unsafe_code)]
+// allow unsafe to call futex directly
+
+use std::collections::HashSet;
+use std::sync::atomic::AtomicU32;
+
+use extrasafe::*;
+use extrasafe::builtins::{Futex, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+fn futex(word: &AtomicU32, op: i32) -> i64 {
+    // SAFETY: word is a valid aligned u32, and the remaining arguments are unused by the
+    // operations tested here or are null timeouts.
+    unsafe { libc::syscall(libc::SYS_futex, word.as_ptr(), op, 1, std::ptr::null::<libc::timespec>(), 0, 0) }
+}
+
+
+#[test]
+fn futex_everything() {
+    let futex = Futex::everything();
+    assert_eq!(futex.simple_rules(), [Sysno::futex_waitv]);
+
+    let rules = &futex.conditional_rules()[&Sysno::futex];
+    assert_eq!(rules.len(), 7);
+    let mask = u64::from(!((libc::FUTEX_PRIVATE_FLAG | libc::FUTEX_CLOCK_REALTIME) as u32));
+    assert_eq!(rules[0].argument_filters,
+        vec![SeccompArgumentFilter::new32(1, SeccompilerComparator::MaskedEq(mask), libc::FUTEX_WAIT as u32)]);
+}
+
+#[test]
+fn futex_pi_overrides_restricted() {
+    let futex = Futex::everything()
+        .allow_futex_pi().yes_really()
+        .allow_futex();
+    let simple: HashSet<_> = futex.simple_rules().into_iter().collect();
+    assert_eq!(simple, HashSet::from([Sysno::futex, Sysno::futex_waitv]));
+    assert!(futex.conditional_rules().is_empty());
+}
+
+#[test]
+/// The restricted futex rule takes precedence over the unrestricted one from `BasicCapabilities`.
+fn futex_pi_denied() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Futex::everything()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let word = AtomicU32::new(0);
+
+    let rc = futex(&word, libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG);
+    assert_eq!(rc, 0, "futex wake failed: {:?}", std::io::Error::last_os_error());
+
+    let rc = futex(&word, libc::FUTEX_UNLOCK_PI | libc::FUTEX_PRIVATE_FLAG);
+    assert_eq!(rc, -1, "pi futex succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+
+    // mutexes still work
+    let mutex = std::sync::Arc::new(std::sync::Mutex::new(0));
+    *mutex.lock().unwrap() += 1;
+    assert_eq!(*mutex.lock().unwrap(), 1);
+}
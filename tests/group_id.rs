@@ -0,0 +1,24 @@
+#![allow(unsafe_code)]
+// allow unsafe to call getgid and setgid directly
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{GroupId, SystemIO};
+
+#[test]
+fn group_id_set_denied() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(GroupId::nothing()
+            .allow_get()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: getgid has no arguments
+    let gid = unsafe { libc::getgid() };
+
+    // SAFETY: setting the gid to the current gid does not change anything even if it succeeds
+    let rc = unsafe { libc::syscall(libc::SYS_setgid, gid) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,32 @@
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{Networking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn enabled_syscalls_empty() {
+    assert!(SafetyContext::new().enabled_syscalls().is_empty());
+}
+
+#[test]
+/// Both simple and conditional rules from all enabled rulesets are included.
+fn enabled_syscalls_networking_systemio() {
+    let ctx = SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_start_tcp_clients()).unwrap()
+        .enable(SystemIO::nothing()
+            .allow_open_readonly()
+            .allow_close()).unwrap();
+
+    let syscalls = ctx.enabled_syscalls();
+    // simple rules
+    assert!(syscalls.contains(&Sysno::connect));
+    assert!(syscalls.contains(&Sysno::close));
+    // conditional rules
+    assert!(syscalls.contains(&Sysno::socket));
+    assert!(syscalls.contains(&Sysno::openat));
+    assert!(syscalls.contains(&Sysno::write));
+    assert!(syscalls.contains(&Sysno::read));
+
+    assert!(!syscalls.contains(&Sysno::bind));
+    assert!(!syscalls.contains(&Sysno::unlink));
+}
@@ -0,0 +1,59 @@
+#![allow(unsafe_code)]
+// allow unsafe to call io_uring_setup directly
+
+use std::collections::HashSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{IoUring, SystemIO, YesReally};
+use extrasafe::syscalls::Sysno;
+
+/// Only compiles if `value` is a `YesReally`.
+fn yes_really<T>(value: YesReally<T>) -> T {
+    value.yes_really()
+}
+
+fn simple(io_uring: &IoUring) -> HashSet<Sysno> {
+    io_uring.simple_rules().into_iter().collect()
+}
+
+#[test]
+fn io_uring_nothing() {
+    let io_uring = IoUring::nothing();
+    assert!(io_uring.simple_rules().is_empty());
+    assert!(io_uring.conditional_rules().is_empty());
+    assert_eq!(io_uring.name(), "IoUring");
+}
+
+#[test]
+/// Every function returns a `YesReally` and allows only its own syscalls.
+fn io_uring_yes_really() {
+    let setup = yes_really(IoUring::nothing().allow_setup());
+    assert_eq!(simple(&setup), HashSet::from([Sysno::io_uring_setup]));
+
+    let enter = yes_really(IoUring::nothing().allow_enter());
+    assert_eq!(simple(&enter), HashSet::from([Sysno::io_uring_enter]));
+
+    let register = yes_really(IoUring::nothing().allow_register());
+    assert_eq!(simple(&register), HashSet::from([Sysno::io_uring_register]));
+
+    let aio = yes_really(IoUring::nothing().allow_aio());
+    let aio = simple(&aio);
+    assert!(aio.contains(&Sysno::io_setup));
+    assert!(aio.contains(&Sysno::io_submit));
+    assert!(!aio.contains(&Sysno::io_uring_setup));
+}
+
+#[test]
+/// io_uring is denied unless explicitly allowed.
+fn io_uring_denied_by_default() {
+    SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let mut params = [0u8; 120];
+    // SAFETY: io_uring_setup takes the number of entries and a pointer to a 120 byte
+    // io_uring_params struct
+    let rc = unsafe { libc::syscall(libc::SYS_io_uring_setup, 1, params.as_mut_ptr()) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
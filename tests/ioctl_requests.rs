@@ -0,0 +1,75 @@
+#![allow(unsafe_code)]
+// allow unsafe to call ioctl directly
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::UnixStream;
+
+#[test]
+/// One rule is generated per distinct request, comparing the second argument.
+fn ioctl_requests_comparators() {
+    let io = SystemIO::nothing()
+        .allow_ioctl_requests(&[libc::FIONREAD, libc::FIONBIO, libc::FIONREAD]);
+    assert!(io.simple_rules().is_empty());
+
+    let filters: Vec<Vec<SeccompArgumentFilter>> = io.conditional_rules()[&Sysno::ioctl].iter()
+        .map(|rule| rule.argument_filters.clone())
+        .collect();
+    let expected: Vec<Vec<SeccompArgumentFilter>> = [libc::FIONREAD, libc::FIONBIO].into_iter()
+        .map(|request| vec![SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, request as u32)])
+        .collect();
+    assert_eq!(filters, expected);
+
+    // calling it again with the same requests doesn't add more rules
+    let io = io.allow_ioctl_requests(&[libc::FIONBIO]);
+    assert_eq!(io.conditional_rules()[&Sysno::ioctl].len(), 2);
+}
+
+#[test]
+/// Allowing all ioctls replaces the restricted rules, regardless of order.
+fn ioctl_requests_with_allow_ioctl() {
+    let io = SystemIO::nothing()
+        .allow_ioctl_requests(&[libc::FIONREAD])
+        .allow_ioctl();
+    assert!(io.simple_rules().contains(&Sysno::ioctl));
+    assert!(io.conditional_rules().is_empty());
+
+    let io = SystemIO::nothing()
+        .allow_ioctl()
+        .allow_ioctl_requests(&[libc::FIONREAD]);
+    assert!(io.simple_rules().contains(&Sysno::ioctl));
+    assert!(io.conditional_rules().is_empty());
+}
+
+#[test]
+/// Other requests are rejected.
+fn ioctl_requests_others_denied() {
+    let (sock, _other) = UnixStream::pair().unwrap();
+    let fd = sock.as_raw_fd();
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()
+            .allow_ioctl_requests(&[libc::FIONREAD, libc::FIONBIO])).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let mut available: libc::c_int = -1;
+    // SAFETY: FIONREAD takes a pointer to an int
+    let rc = unsafe { libc::ioctl(fd, libc::FIONREAD, &mut available) };
+    assert_eq!(rc, 0);
+    assert_eq!(available, 0);
+
+    let nonblocking: libc::c_int = 1;
+    // SAFETY: FIONBIO takes a pointer to an int
+    let rc = unsafe { libc::ioctl(fd, libc::FIONBIO, &nonblocking) };
+    assert_eq!(rc, 0);
+
+    // SAFETY: FIOCLEX takes no arguments
+    let rc = unsafe { libc::ioctl(fd, libc::FIOCLEX) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
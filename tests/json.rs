@@ -0,0 +1,73 @@
+#![cfg(feature = "serde")]
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{Memory, SystemIO};
+
+#[test]
+fn to_json_snapshot() {
+    let ctx = SafetyContext::new()
+        .enable(Memory::basic_allocation()).unwrap()
+        .enable(SystemIO::nothing()
+            .allow_close()
+            .allow_stdout()).unwrap();
+
+    let expected = r#"{
+  "Memory": {
+    "simple": [
+      "brk",
+      "munmap"
+    ],
+    "conditional": {
+      "mmap": [
+        [
+          {
+            "arg": 2,
+            "op": "masked_eq",
+            "mask": 4,
+            "value": 0,
+            "is_64bit": true
+          }
+        ]
+      ]
+    }
+  },
+  "SystemIO": {
+    "simple": [
+      "close",
+      "close_range"
+    ],
+    "conditional": {
+      "write": [
+        [
+          {
+            "arg": 0,
+            "op": "eq",
+            "value": 1,
+            "is_64bit": true
+          }
+        ]
+      ]
+    }
+  }
+}"#;
+    assert_eq!(ctx.to_json(), expected);
+}
+
+#[test]
+/// The output doesn't depend on the order rulesets or rules are enabled in.
+fn to_json_deterministic() {
+    let first = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Memory::nothing()
+            .allow_mmap_no_wx()).unwrap();
+    let second = SafetyContext::new()
+        .enable(Memory::nothing()
+            .allow_mmap_no_wx()).unwrap()
+        .enable(SystemIO::nothing()
+            .allow_stderr()
+            .allow_stdout()).unwrap();
+
+    assert_eq!(first.to_json(), second.to_json());
+}
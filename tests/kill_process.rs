@@ -0,0 +1,34 @@
+use extrasafe::SafetyContext;
+use extrasafe::builtins::SystemIO;
+
+use std::os::unix::process::ExitStatusExt;
+use std::process::Command;
+
+#[test]
+/// A child process that makes a syscall that isn't allowed is killed by `SIGSYS`.
+fn kill_process_on_violation() {
+    // The child is killed as soon as the dynamic loader makes a syscall that isn't allowed.
+    let child = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .kill_process_on_violation()
+        .spawn_sandboxed(Command::new("true"));
+
+    let status = child.unwrap().wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGSYS));
+}
+
+#[test]
+/// By default, the child just fails without being killed.
+fn errno_by_default() {
+    let child = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .spawn_sandboxed(Command::new("true"));
+
+    let status = child.unwrap().wait().unwrap();
+    assert!(!status.success());
+    assert_eq!(status.signal(), None);
+}
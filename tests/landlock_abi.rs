@@ -0,0 +1,45 @@
+#![cfg(feature = "landlock")]
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+fn landlock_abi_sane() {
+    let abi = SafetyContext::landlock_abi();
+    // The existing landlock tests require ABI v2 for filesystem rules
+    assert!(abi.is_some_and(|abi| abi >= ABI::V2), "unexpected landlock ABI {:?}", abi);
+}
+
+#[test]
+fn require_supported_abi() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .require_landlock_abi(ABI::V1)
+        .apply_to_current_thread().unwrap();
+}
+
+#[test]
+fn require_unsupported_abi() {
+    let available = SafetyContext::landlock_abi();
+    // The newest ABI known to the landlock crate, which the kernel may already support.
+    let newest = ABI::V9;
+    if available == Some(newest) {
+        println!("skipping: kernel supports the newest known landlock ABI");
+        return;
+    }
+
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .require_landlock_abi(newest)
+        .apply_to_current_thread();
+    assert!(matches!(res, Err(ExtraSafeError::LandlockAbiUnsupported(ABI::V9, abi)) if abi == available),
+        "unexpected result {:?}", res);
+
+    // the filter was not applied
+    let res = std::fs::File::open("/etc/passwd");
+    assert!(res.is_ok(), "filter was applied despite error");
+}
@@ -398,8 +398,26 @@ fn test_nonexistant_file_no_error() {
     assert!(res.is_ok(), "Errored when passing nonexistant file to landlock rule: {:?} ", res.unwrap_err());
 }
 
+/// A ruleset with the same rules as the `SystemIO` it wraps, but a different name.
+struct OtherIO(SystemIO);
+
+impl extrasafe::RuleSet for OtherIO {
+    fn simple_rules(&self) -> Vec<extrasafe::syscalls::Sysno> {
+        self.0.simple_rules()
+    }
+
+    fn landlock_rules(&self) -> Vec<extrasafe::LandlockRule> {
+        self.0.landlock_rules()
+    }
+
+    fn name(&self) -> &'static str {
+        "OtherIO"
+    }
+}
+
 #[test]
-/// Test extrasafe returns an error when trying to apply multiple rules to the same path
+/// Test extrasafe returns an error when trying to apply rules to the same path from multiple
+/// different rulesets
 fn test_duplicate_path() {
     let dir = tempfile::tempdir().unwrap();
 
@@ -409,8 +427,8 @@ fn test_duplicate_path() {
                 .allow_create_in_dir(&dir)
             ).unwrap()
         .enable(
-            SystemIO::nothing()
-                .allow_create_in_dir(&dir)
+            OtherIO(SystemIO::nothing()
+                .allow_create_in_dir(&dir))
             );
 
     assert!(res.is_err(), "Did not error on passing same dir in multiple rulesets");
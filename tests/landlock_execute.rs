@@ -0,0 +1,63 @@
+#![cfg(feature = "landlock")]
+
+use std::path::Path;
+use std::process::Command;
+
+use extrasafe::builtins::SystemIO;
+
+/// Directories containing the dynamic loader and libraries needed to run `/bin/true`.
+const LIBRARY_DIRS: &[&str] = &["/lib", "/lib64", "/usr/lib", "/usr/lib64", "/etc"];
+
+/// Copy `/bin/true` into a new temporary directory and return both.
+fn copy_true() -> (tempfile::TempDir, std::path::PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let program = dir.path().join("true");
+    let _bytes = std::fs::copy("/bin/true", &program).unwrap();
+    (dir, program)
+}
+
+/// SystemIO allowing the dynamic loader to be executed and the libraries to be read.
+fn libraries() -> SystemIO {
+    let mut io = SystemIO::nothing();
+    for dir in LIBRARY_DIRS.iter().filter(|dir| Path::new(dir).exists()) {
+        io = io.allow_execute_in_dir(dir);
+    }
+    io
+}
+
+#[test]
+/// Files in an executable directory can be run.
+fn landlock_execute() {
+    let (dir, program) = copy_true();
+
+    std::thread::spawn(move || {
+        extrasafe::SafetyContext::new()
+            .enable(libraries()
+                .allow_execute_in_dir(dir.path())).unwrap()
+            .landlock_only()
+            .apply_to_current_thread().unwrap();
+
+        let status = Command::new(&program).status().unwrap();
+        assert!(status.success());
+    }).join().unwrap();
+}
+
+#[test]
+/// Files in a directory that is only readable can be read but not run.
+fn landlock_read_only() {
+    let (dir, program) = copy_true();
+
+    std::thread::spawn(move || {
+        extrasafe::SafetyContext::new()
+            .enable(libraries()
+                .allow_read_path(dir.path())).unwrap()
+            .landlock_only()
+            .apply_to_current_thread().unwrap();
+
+        let res = std::fs::read(&program);
+        assert!(res.is_ok(), "Failed to read readable file: {:?}", res.unwrap_err());
+
+        let res = Command::new(&program).status();
+        assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::PermissionDenied);
+    }).join().unwrap();
+}
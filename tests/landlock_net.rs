@@ -0,0 +1,78 @@
+#![cfg(feature = "landlock")]
+
+use std::net::{TcpListener, TcpStream};
+
+use extrasafe::*;
+use extrasafe::builtins::{Networking, SystemIO};
+
+/// Landlock network rules require ABI v4 (Linux 6.7).
+fn landlock_net_supported() -> bool {
+    Ruleset::default()
+        .set_compatibility(CompatLevel::HardRequirement)
+        .handle_access(AccessNet::from_all(ABI::V4))
+        .and_then(|ruleset| ruleset.create())
+        .is_ok()
+}
+
+#[test]
+fn bind_allowed_port() {
+    if !landlock_net_supported() {
+        println!("skipping: landlock network rules not supported by this kernel");
+        return;
+    }
+
+    SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_bind_tcp(31771)).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let res = TcpListener::bind("127.0.0.1:31771");
+    assert!(res.is_ok(), "failed to bind to allowed port: {:?}", res.unwrap_err());
+
+    let res = TcpListener::bind("127.0.0.1:31772");
+    assert!(res.is_err(), "incorrectly succeeded in binding to denied port");
+}
+
+#[test]
+fn connect_allowed_port() {
+    if !landlock_net_supported() {
+        println!("skipping: landlock network rules not supported by this kernel");
+        return;
+    }
+
+    let allowed = TcpListener::bind("127.0.0.1:31773").unwrap();
+    let denied = TcpListener::bind("127.0.0.1:31774").unwrap();
+
+    SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_connect_tcp(31773)).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let res = TcpStream::connect(allowed.local_addr().unwrap());
+    assert!(res.is_ok(), "failed to connect to allowed port: {:?}", res.unwrap_err());
+
+    let res = TcpStream::connect(denied.local_addr().unwrap());
+    assert!(res.is_err(), "incorrectly succeeded in connecting to denied port");
+}
+
+#[test]
+/// Network rules alone don't restrict filesystem access.
+fn net_rules_dont_restrict_files() {
+    if !landlock_net_supported() {
+        println!("skipping: landlock network rules not supported by this kernel");
+        return;
+    }
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_open_readonly()
+            .allow_read()
+            .allow_metadata()
+            .allow_close()).unwrap()
+        .enable(Networking::nothing()
+            .allow_connect_tcp(31775)).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let res = std::fs::read_to_string("/etc/passwd");
+    assert!(res.is_ok(), "failed to read file: {:?}", res.unwrap_err());
+}
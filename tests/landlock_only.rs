@@ -0,0 +1,67 @@
+#![cfg(feature = "landlock")]
+
+use std::fs::{read_to_string, write};
+
+use extrasafe::builtins::SystemIO;
+
+/// A ruleset with only the landlock rules of the `SystemIO` it wraps.
+struct LandlockOnlyIO(SystemIO);
+
+impl extrasafe::RuleSet for LandlockOnlyIO {
+    fn simple_rules(&self) -> Vec<extrasafe::syscalls::Sysno> {
+        Vec::new()
+    }
+
+    fn landlock_rules(&self) -> Vec<extrasafe::LandlockRule> {
+        self.0.landlock_rules()
+    }
+
+    fn name(&self) -> &'static str {
+        "LandlockOnlyIO"
+    }
+}
+
+#[test]
+/// A context with only landlock rules and `landlock_only` applies landlock without installing a
+/// seccomp filter.
+fn only_landlock_rules_skips_seccomp() {
+    let dir = tempfile::tempdir().unwrap();
+    let allowed = dir.path().join("allowed.txt");
+    let denied = dir.path().join("denied.txt");
+    write(&allowed, "allowed").unwrap();
+    write(&denied, "denied").unwrap();
+
+    std::thread::spawn(move || {
+        extrasafe::SafetyContext::new()
+            .enable(LandlockOnlyIO(SystemIO::nothing()
+                .allow_read_path(&allowed))).unwrap()
+            .landlock_only()
+            .apply_to_current_thread().unwrap();
+
+        assert!(!extrasafe::is_seccomp_active(), "seccomp filter was installed");
+        assert_eq!(read_to_string(&allowed).unwrap(), "allowed");
+        assert!(read_to_string(&denied).is_err(), "landlock did not restrict other files");
+    }).join().unwrap();
+}
+
+#[test]
+/// `landlock_only` skips seccomp even though `SystemIO` also has seccomp rules.
+fn landlock_only_read_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let allowed = dir.path().join("allowed.txt");
+    let denied = dir.path().join("denied.txt");
+    write(&allowed, "allowed").unwrap();
+    write(&denied, "denied").unwrap();
+
+    std::thread::spawn(move || {
+        extrasafe::SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_read_path(&allowed)).unwrap()
+            .landlock_only()
+            .apply_to_current_thread().unwrap();
+
+        assert!(!extrasafe::is_seccomp_active(), "seccomp filter was installed");
+        assert_eq!(read_to_string(&allowed).unwrap(), "allowed");
+        assert!(read_to_string(&denied).is_err(), "landlock did not restrict other files");
+    }).join().unwrap();
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+/// Only allows writing small buffers to stdout.
+struct SmallStdoutWrites;
+impl SmallStdoutWrites {
+    fn rule() -> SeccompRule {
+        SeccompRule::new(Sysno::write)
+            .and_condition(seccomp_arg_filter!(arg0 == 1))
+            .and_condition(seccomp_arg_filter!(arg2 <= 4096))
+    }
+}
+impl RuleSet for SmallStdoutWrites {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        HashMap::from([(Sysno::write, vec![SmallStdoutWrites::rule()])])
+    }
+
+    fn name(&self) -> &'static str {
+        "SmallStdoutWrites"
+    }
+}
+
+#[test]
+fn lint_clean_context() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .enable(SystemIO::nothing().allow_open_readonly()).unwrap();
+    assert!(ctx.lint().is_empty());
+}
+
+#[test]
+/// A rule with more conditions than another rule for the same syscall has no effect.
+fn lint_shadowed_rule() {
+    let ctx = SafetyContext::new()
+        .enable(SmallStdoutWrites).unwrap()
+        .enable(SystemIO::nothing().allow_stdout()).unwrap();
+
+    let lints = ctx.lint();
+    assert_eq!(lints, vec![Lint::ShadowedRule(Sysno::write, "SmallStdoutWrites", SmallStdoutWrites::rule(), "SystemIO")]);
+    assert!(lints[0].to_string().contains("SmallStdoutWrites"));
+}
+
+#[test]
+/// Identical rules from different rulesets are only reported once.
+fn lint_identical_rules() {
+    let stdout = SeccompRule::new(Sysno::write)
+        .and_condition(seccomp_arg_filter!(arg0 == 1));
+
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing().allow_stdout()).unwrap()
+        .enable(SystemIO::nothing().allow_stderr()).unwrap();
+    assert!(ctx.lint().is_empty());
+
+    struct Stdout;
+    impl RuleSet for Stdout {
+        fn simple_rules(&self) -> Vec<Sysno> {
+            Vec::new()
+        }
+
+        fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+            HashMap::from([(Sysno::write, vec![SeccompRule::new(Sysno::write)
+                .and_condition(seccomp_arg_filter!(arg0 == 1))])])
+        }
+
+        fn name(&self) -> &'static str {
+            "Stdout"
+        }
+    }
+
+    let lints = ctx.enable(Stdout).unwrap().lint();
+    assert_eq!(lints.len(), 1);
+    assert!(matches!(&lints[0], Lint::ShadowedRule(Sysno::write, _, rule, _) if *rule == stdout));
+}
+
+#[test]
+/// Restricting `openat` to read-only has no effect if `openat2` is allowed unconditionally.
+fn lint_restriction_bypassed() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing().allow_open_readonly()).unwrap()
+        .enable(Sysno::openat2).unwrap();
+
+    let lints = ctx.lint();
+    assert!(lints.contains(&Lint::RestrictionBypassed(Sysno::openat, "SystemIO", Sysno::openat2, Sysno::openat2.name())));
+    assert!(lints.iter().all(|lint| matches!(lint, Lint::RestrictionBypassed(_, _, Sysno::openat2, _))));
+
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .enable(Sysno::writev).unwrap();
+    assert_eq!(ctx.lint(), vec![Lint::RestrictionBypassed(Sysno::write, "SystemIO", Sysno::writev, Sysno::writev.name())]);
+}
@@ -0,0 +1,24 @@
+use std::io::Write;
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// Syscalls that would be denied are only logged, and still succeed.
+fn log_only_does_not_deny() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("logged.txt");
+
+    SafetyContext::new()
+        .log_only()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let res = std::fs::File::create(&path);
+    assert!(res.is_ok(), "Failed to create file in log-only mode: {:?}", res.unwrap_err());
+
+    let res = res.unwrap().write_all(b"hello");
+    assert!(res.is_ok(), "Failed to write file in log-only mode: {:?}", res.unwrap_err());
+}
@@ -0,0 +1,39 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+fn context() -> SafetyContext {
+    SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+}
+
+#[test]
+/// The log flag is only set when requested, and independently of the other flags.
+fn log_violations_flag() {
+    let flags = context().into_filter().unwrap().flags();
+    assert_eq!(flags & libc::SECCOMP_FILTER_FLAG_LOG, 0);
+
+    let flags = context()
+        .log_violations()
+        .into_filter().unwrap()
+        .flags();
+    assert_eq!(flags, libc::SECCOMP_FILTER_FLAG_LOG);
+
+    let flags = context()
+        .log_violations()
+        .allow_speculation(true)
+        .kill_process_on_violation()
+        .into_filter().unwrap()
+        .flags();
+    assert_eq!(flags, libc::SECCOMP_FILTER_FLAG_LOG | libc::SECCOMP_FILTER_FLAG_SPEC_ALLOW);
+}
+
+#[test]
+/// The filter can be installed with the log flag, and still denies syscalls.
+fn log_violations_still_denies() {
+    context()
+        .log_violations()
+        .apply_to_current_thread().unwrap();
+
+    let res = std::fs::File::open("/proc/self/status");
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::PermissionDenied);
+}
@@ -0,0 +1,22 @@
+use std::collections::HashSet;
+
+use extrasafe::RuleSet;
+use extrasafe::builtins::Memfd;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn memfd_nothing() {
+    let memfd = Memfd::nothing();
+    assert!(memfd.simple_rules().is_empty());
+    assert!(memfd.conditional_rules().is_empty());
+    assert_eq!(memfd.name(), "Memfd");
+}
+
+#[test]
+fn memfd_each_group() {
+    assert_eq!(Memfd::nothing().allow_memfd_create().simple_rules(), vec![Sysno::memfd_create]);
+    assert_eq!(Memfd::nothing().allow_memfd_secret().simple_rules(), vec![Sysno::memfd_secret]);
+
+    let everything: HashSet<Sysno> = Memfd::everything().simple_rules().into_iter().collect();
+    assert_eq!(everything, HashSet::from([Sysno::memfd_create, Sysno::memfd_secret]));
+}
@@ -0,0 +1,197 @@
+#![allow(unsafe_code)]
+// allow unsafe to call mmap directly
+
+use std::collections::HashSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{Memory, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+const PROT_EXEC: u64 = libc::PROT_EXEC as u64;
+
+fn simple_set<R: RuleSet>(ruleset: &R) -> HashSet<Sysno> {
+    ruleset.simple_rules().into_iter().collect()
+}
+
+fn conditional_set<R: RuleSet>(ruleset: &R) -> HashSet<Sysno> {
+    ruleset.conditional_rules().into_keys().collect()
+}
+
+
+#[test]
+fn memory_basic_allocation() {
+    let memory = Memory::basic_allocation();
+    assert_eq!(simple_set(&memory), HashSet::from([Sysno::brk, Sysno::munmap]));
+    assert_eq!(conditional_set(&memory), HashSet::from([Sysno::mmap]));
+
+    let rules = &memory.conditional_rules()[&Sysno::mmap];
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].argument_filters, vec![seccomp_arg_filter!(arg2 & PROT_EXEC == 0)]);
+}
+
+
+#[test]
+/// Allowing exec replaces the restricted rule rather than conflicting with it, regardless of order.
+fn memory_exec_overrides_restricted() {
+    let memory = Memory::nothing()
+        .allow_mmap()
+        .allow_mprotect()
+        .allow_mmap_exec().yes_really()
+        .allow_mprotect_exec().yes_really();
+    assert_eq!(simple_set(&memory), HashSet::from([Sysno::mmap, Sysno::mprotect]));
+    assert!(memory.conditional_rules().is_empty());
+
+    let memory = Memory::nothing()
+        .allow_mmap_exec().yes_really()
+        .allow_mmap();
+    assert_eq!(simple_set(&memory), HashSet::from([Sysno::mmap]));
+    assert!(memory.conditional_rules().is_empty());
+}
+
+#[test]
+/// The restricted mmap rule takes precedence over the unrestricted one from `BasicCapabilities`.
+fn memory_mmap_exec_denied() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Memory::basic_allocation()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // allocating still works
+    let v = vec![1u8; 1 << 20];
+    assert_eq!(v.len(), 1 << 20);
+
+    // SAFETY: anonymous mapping with no address hint, unmapped below if it succeeds
+    let ptr = unsafe {
+        libc::mmap(std::ptr::null_mut(), 4096,
+            libc::PROT_READ | libc::PROT_EXEC, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+    };
+    assert_eq!(ptr, libc::MAP_FAILED, "executable mmap succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+
+    // SAFETY: same as above
+    let ptr = unsafe {
+        libc::mmap(std::ptr::null_mut(), 4096,
+            libc::PROT_READ | libc::PROT_WRITE, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+    };
+    assert_ne!(ptr, libc::MAP_FAILED, "non-executable mmap failed");
+    // SAFETY: ptr was mapped above with length 4096
+    let rc = unsafe { libc::munmap(ptr, 4096) };
+    assert_eq!(rc, 0);
+}
+
+#[test]
+fn memory_no_wx_rules() {
+    const PROT_WRITE: u64 = libc::PROT_WRITE as u64;
+    const WX: u64 = PROT_WRITE | PROT_EXEC;
+
+    let memory = Memory::nothing()
+        .allow_mmap_no_wx();
+    assert!(memory.simple_rules().is_empty());
+    assert_eq!(conditional_set(&memory), HashSet::from([Sysno::mmap, Sysno::mprotect]));
+
+    let expected = vec![
+        vec![seccomp_arg_filter!(arg2 & WX == 0)],
+        vec![seccomp_arg_filter!(arg2 & WX == PROT_WRITE)],
+        vec![seccomp_arg_filter!(arg2 & WX == PROT_EXEC)],
+    ];
+    for syscall in [Sysno::mmap, Sysno::mprotect] {
+        let filters: Vec<_> = memory.conditional_rules()[&syscall].iter()
+            .map(|rule| rule.argument_filters.clone())
+            .collect();
+        assert_eq!(filters, expected);
+    }
+}
+
+#[test]
+fn memory_no_wx_denies_write_exec() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Memory::nothing()
+            .allow_munmap()
+            .allow_mmap_no_wx()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let map = |prot| {
+        // SAFETY: anonymous mapping with no address hint
+        unsafe { libc::mmap(std::ptr::null_mut(), 4096, prot, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0) }
+    };
+
+    let ptr = map(libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC);
+    assert_eq!(ptr, libc::MAP_FAILED, "write+exec mmap succeeded incorrectly");
+
+    let ptr = map(libc::PROT_READ | libc::PROT_EXEC);
+    assert_ne!(ptr, libc::MAP_FAILED, "exec-only mmap failed");
+
+    // SAFETY: ptr was mapped above with length 4096
+    let rc = unsafe { libc::mprotect(ptr, 4096, libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC) };
+    assert_eq!(rc, -1, "write+exec mprotect succeeded incorrectly");
+    // SAFETY: same as above
+    let rc = unsafe { libc::mprotect(ptr, 4096, libc::PROT_READ | libc::PROT_WRITE) };
+    assert_eq!(rc, 0, "write-only mprotect failed");
+    // SAFETY: same as above
+    let rc = unsafe { libc::munmap(ptr, 4096) };
+    assert_eq!(rc, 0);
+}
+
+#[test]
+fn memory_madvise_safe_rules() {
+    let memory = Memory::nothing()
+        .allow_madvise_safe();
+    assert!(memory.simple_rules().is_empty());
+    assert_eq!(conditional_set(&memory), HashSet::from([Sysno::madvise]));
+
+    let filters: Vec<_> = memory.conditional_rules()[&Sysno::madvise].iter()
+        .map(|rule| rule.argument_filters.clone())
+        .collect();
+    let expected: Vec<_> = [libc::MADV_NORMAL, libc::MADV_RANDOM, libc::MADV_SEQUENTIAL, libc::MADV_WILLNEED]
+        .into_iter()
+        .map(|advice| vec![seccomp_arg_filter!(arg2 == advice as u64)])
+        .collect();
+    assert_eq!(filters, expected);
+
+    // allowing madvise unconditionally replaces the restricted rules, regardless of order
+    let memory = Memory::nothing()
+        .allow_madvise_safe()
+        .allow_madvise();
+    assert_eq!(simple_set(&memory), HashSet::from([Sysno::madvise]));
+    assert!(memory.conditional_rules().is_empty());
+
+    let memory = Memory::nothing()
+        .allow_madvise()
+        .allow_madvise_safe();
+    assert_eq!(simple_set(&memory), HashSet::from([Sysno::madvise]));
+    assert!(memory.conditional_rules().is_empty());
+}
+
+#[test]
+fn memory_madvise_safe_denies_dontneed() {
+    // SAFETY: anonymous mapping with no address hint
+    let ptr = unsafe {
+        libc::mmap(std::ptr::null_mut(), 4096,
+            libc::PROT_READ | libc::PROT_WRITE, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+    };
+    assert_ne!(ptr, libc::MAP_FAILED);
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Memory::nothing()
+            .allow_madvise_safe()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: ptr was mapped above with length 4096
+    let rc = unsafe { libc::madvise(ptr, 4096, libc::MADV_WILLNEED) };
+    assert_eq!(rc, 0, "MADV_WILLNEED failed");
+    // SAFETY: same as above
+    let rc = unsafe { libc::madvise(ptr, 4096, libc::MADV_DONTNEED) };
+    assert_eq!(rc, -1, "MADV_DONTNEED succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+    // SAFETY: same as above
+    let rc = unsafe { libc::munmap(ptr, 4096) };
+    assert_eq!(rc, 0);
+}
@@ -0,0 +1,60 @@
+#![allow(unsafe_code)]
+// allow unsafe to call unshare directly
+
+use extrasafe::*;
+use extrasafe::builtins::{SystemIO, YesReally, danger_zone::Namespaces};
+use extrasafe::syscalls::Sysno;
+
+use std::collections::HashSet;
+
+fn simple_rules(namespaces: &Namespaces) -> HashSet<Sysno> {
+    namespaces.simple_rules().into_iter().collect()
+}
+
+#[test]
+fn namespaces_nothing() {
+    let namespaces = Namespaces::nothing();
+    assert!(namespaces.simple_rules().is_empty());
+    assert!(namespaces.conditional_rules().is_empty());
+    assert_eq!(namespaces.name(), "Namespaces");
+}
+
+#[test]
+fn namespaces_syscalls() {
+    let mount: YesReally<Namespaces> = Namespaces::nothing().allow_mount();
+    assert_eq!(simple_rules(&mount.yes_really()), HashSet::from([Sysno::mount, Sysno::umount2]));
+
+    let namespace_ops: YesReally<Namespaces> = Namespaces::nothing().allow_namespace_ops();
+    assert_eq!(simple_rules(&namespace_ops.yes_really()), HashSet::from([Sysno::unshare, Sysno::setns]));
+
+    let pivot_root: YesReally<Namespaces> = Namespaces::nothing().allow_pivot_root();
+    assert_eq!(simple_rules(&pivot_root.yes_really()), HashSet::from([Sysno::pivot_root]));
+
+    let all = Namespaces::nothing()
+        .allow_mount().yes_really()
+        .allow_namespace_ops().yes_really()
+        .allow_pivot_root().yes_really();
+    assert_eq!(simple_rules(&all).len(), 5);
+}
+
+#[test]
+fn namespaces_unshare_not_denied() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Namespaces::nothing()
+            .allow_namespace_ops().yes_really()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // Unsharing nothing always succeeds if unshare is allowed.
+    // SAFETY: unshare with no flags does nothing
+    let rc = unsafe { libc::unshare(0) };
+    assert_eq!(rc, 0);
+
+    // Other namespace syscalls are still denied
+    // SAFETY: all arguments are null, so nothing is mounted
+    let rc = unsafe { libc::mount(std::ptr::null(), std::ptr::null(), std::ptr::null(), 0, std::ptr::null()) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,38 @@
+#![allow(unsafe_code)]
+// allow unsafe to call prctl
+
+use std::thread;
+
+use extrasafe::*;
+use extrasafe::builtins::Prctl;
+
+/// Apply a filter that allows reading `no_new_privs` with the given setting, and return
+/// whether applying succeeded and whether `no_new_privs` is set afterwards.
+fn apply_and_get_no_new_privs(set: bool) -> (bool, libc::c_int) {
+    thread::spawn(move || {
+        let res = SafetyContext::new()
+            .enable(Prctl::nothing()
+                .allow_get_no_new_privs()).unwrap()
+            .set_no_new_privs(set)
+            .apply_to_current_thread();
+
+        // SAFETY: PR_GET_NO_NEW_PRIVS takes no other arguments
+        let no_new_privs = unsafe { libc::prctl(libc::PR_GET_NO_NEW_PRIVS, 0, 0, 0, 0) };
+        (res.is_ok(), no_new_privs)
+    }).join().unwrap()
+}
+
+#[test]
+fn no_new_privs_set_by_default() {
+    let (applied, no_new_privs) = apply_and_get_no_new_privs(true);
+    assert!(applied);
+    assert_eq!(no_new_privs, 1);
+}
+
+#[test]
+/// Without `no_new_privs`, the filter can only be installed with `CAP_SYS_ADMIN`, but either way
+/// the flag must not be set.
+fn no_new_privs_disabled() {
+    let (_applied, no_new_privs) = apply_and_get_no_new_privs(false);
+    assert_eq!(no_new_privs, 0);
+}
@@ -1,6 +1,13 @@
+#![allow(unsafe_code)]
+// allow unsafe to call open directly with O_TRUNC, which std does not allow without write access
+
 use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
 use extrasafe::*;
 
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+
 use std::fs::{File, OpenOptions};
 
 use std::io::Write;
@@ -48,7 +55,7 @@ fn allow_open_readonly() {
     );
 
     // Try to open for create and fail
-    let res = OpenOptions::new().read(true).create(true).open(&path);
+    let res = OpenOptions::new().read(true).create(true).truncate(false).open(&path);
     assert!(
         res.is_err(),
         "Successfully opened file with create incorrectly"
@@ -66,6 +73,13 @@ fn allow_open_readonly() {
         "Successfully opened file with create_new incorrectly"
     );
 
+    // Try to open for truncate and fail, without truncating the file
+    let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+    // SAFETY: c_path is a valid nul-terminated string
+    let fd = unsafe { libc::open(c_path.as_ptr(), libc::O_RDONLY | libc::O_TRUNC) };
+    assert_eq!(fd, -1, "Successfully opened file with truncate incorrectly");
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), 11);
+
     // open for read only and succeed
     let res = OpenOptions::new().read(true).write(false).open(&path);
     assert!(
@@ -74,3 +88,21 @@ fn allow_open_readonly() {
         res.unwrap_err()
     );
 }
+
+#[test]
+/// The flags argument of `openat` is checked against all of the write and create flags.
+fn open_readonly_comparator() {
+    let rules = SystemIO::nothing()
+        .allow_open_readonly()
+        .conditional_rules();
+
+    let rule = &rules[&Sysno::openat][0];
+    assert_eq!(rule.argument_filters.len(), 1);
+
+    let rejected = [libc::O_WRONLY, libc::O_RDWR, libc::O_APPEND, libc::O_CREAT, libc::O_EXCL, libc::O_TRUNC]
+        .into_iter()
+        .fold(0, |mask, flag| mask | flag as u64);
+    let expected = SeccompArgumentFilter::new(2, SeccompilerComparator::MaskedEq(rejected), 0);
+    assert_eq!(rule.argument_filters[0], expected);
+    assert_eq!(libc::O_RDONLY, 0);
+}
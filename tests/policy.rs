@@ -0,0 +1,64 @@
+#![cfg(feature = "config")]
+
+use extrasafe::{policy, ExtraSafeError, SafetyContext};
+use extrasafe::builtins::{SystemIO, Time};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn policy_matches_builder() {
+    let ctx = policy::from_toml(r#"
+        [system_io]
+        stdout = true
+        stderr = true
+        open_readonly = true
+        close = false
+
+        [time]
+        gettime = true
+    "#).unwrap();
+
+    let expected = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()
+            .allow_open_readonly()).unwrap()
+        .enable(Time::nothing()
+            .allow_gettime()).unwrap();
+
+    assert_eq!(ctx.enabled_syscalls(), expected.enabled_syscalls());
+    assert!(ctx.enabled_syscalls().contains(&Sysno::clock_gettime));
+    assert!(!ctx.enabled_syscalls().contains(&Sysno::close));
+}
+
+#[test]
+fn policy_applies() {
+    policy::from_toml(r#"
+        [system_io]
+        stdout = true
+        stderr = true
+    "#).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let res = std::fs::File::open("/etc/passwd");
+    assert!(res.is_err(), "opening a file succeeded incorrectly");
+}
+
+#[test]
+fn policy_errors() {
+    let cases = [
+        ("[nonexistent]\nfoo = true", "unknown ruleset `nonexistent`"),
+        ("[time]\nsleep = true", "unknown option `sleep` for ruleset `time`"),
+        ("[time]\ngettime = 1", "option `gettime` for ruleset `time` must be a boolean"),
+        ("time = true", "`time` must be a table"),
+    ];
+
+    for (config, message) in cases {
+        match policy::from_toml(config) {
+            Err(ExtraSafeError::InvalidPolicy(msg)) => assert_eq!(msg, message),
+            other => panic!("expected InvalidPolicy for {:?}, got {:?}", config, other.map(|_| ())),
+        }
+    }
+
+    let res = policy::from_toml("[time");
+    assert!(matches!(res, Err(ExtraSafeError::InvalidPolicy(_))));
+}
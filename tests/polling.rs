@@ -0,0 +1,28 @@
+#![allow(unsafe_code)]
+// allow unsafe to call poll and pselect6 directly
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{Polling, SystemIO};
+
+#[test]
+fn polling_poll_allowed() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Polling::nothing()
+            .allow_poll()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: polling no fds with no timeout does not access memory
+    let rc = unsafe { libc::poll(std::ptr::null_mut(), 0, 0) };
+    assert_eq!(rc, 0);
+
+    // SAFETY: selecting no fds with null sets and timeout does not access memory
+    let rc = unsafe {
+        libc::syscall(libc::SYS_pselect6, 0, std::ptr::null_mut::<libc::fd_set>(), std::ptr::null_mut::<libc::fd_set>(),
+            std::ptr::null_mut::<libc::fd_set>(), std::ptr::null::<libc::timespec>(), std::ptr::null::<libc::c_void>())
+    };
+    assert_eq!(rc, -1, "pselect6 succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
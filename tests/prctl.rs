@@ -0,0 +1,100 @@
+#![allow(unsafe_code)]
+// allow unsafe to call prctl directly
+
+use extrasafe::*;
+use extrasafe::builtins::Prctl;
+use extrasafe::builtins::danger_zone::Capabilities;
+use extrasafe::syscalls::Sysno;
+
+/// A function allowing a `prctl` option.
+type Allow = fn(Prctl) -> Prctl;
+
+/// The options allowed by `prctl`, in the order they were added.
+fn options(prctl: &Prctl) -> Vec<SeccompArgumentFilter> {
+    prctl.conditional_rules()[&Sysno::prctl].iter()
+        .map(|rule| {
+            assert_eq!(rule.argument_filters.len(), 1);
+            rule.argument_filters[0].clone()
+        })
+        .collect()
+}
+
+fn option_filter(option: libc::c_int) -> SeccompArgumentFilter {
+    SeccompArgumentFilter::new32(0, SeccompilerComparator::Eq, option as u32)
+}
+
+#[test]
+fn prctl_nothing() {
+    let prctl = Prctl::nothing();
+    assert!(prctl.simple_rules().is_empty());
+    assert!(prctl.conditional_rules().is_empty());
+    assert_eq!(prctl.name(), "Prctl");
+}
+
+#[test]
+/// Each function adds a single rule comparing the option.
+fn prctl_option_comparators() {
+    let cases: [(Allow, libc::c_int); 8] = [
+        (Prctl::allow_set_name, libc::PR_SET_NAME),
+        (Prctl::allow_get_name, libc::PR_GET_NAME),
+        (Prctl::allow_set_no_new_privs, libc::PR_SET_NO_NEW_PRIVS),
+        (Prctl::allow_get_no_new_privs, libc::PR_GET_NO_NEW_PRIVS),
+        (Prctl::allow_get_dumpable, libc::PR_GET_DUMPABLE),
+        (Prctl::allow_get_seccomp, libc::PR_GET_SECCOMP),
+        (|prctl| prctl.allow_set_dumpable().yes_really(), libc::PR_SET_DUMPABLE),
+        (|prctl| prctl.allow_set_seccomp().yes_really(), libc::PR_SET_SECCOMP),
+    ];
+
+    for (allow, option) in cases {
+        let prctl = allow(Prctl::nothing());
+        assert!(prctl.simple_rules().is_empty());
+        assert_eq!(options(&prctl), vec![option_filter(option)]);
+    }
+}
+
+#[test]
+/// Allowing the same option twice only adds one rule.
+fn prctl_dedup() {
+    let prctl = Prctl::nothing()
+        .allow_set_name()
+        .allow_get_name()
+        .allow_set_name();
+    assert_eq!(options(&prctl), vec![option_filter(libc::PR_SET_NAME), option_filter(libc::PR_GET_NAME)]);
+}
+
+#[test]
+/// The conditional `prctl` rules compose with the ones from `Capabilities`.
+fn prctl_compose_with_capabilities() {
+    let res = SafetyContext::new()
+        .enable(Prctl::nothing().allow_set_no_new_privs()).unwrap()
+        .enable(Capabilities::nothing().allow_drop_privileges());
+    assert!(res.is_ok());
+}
+
+#[test]
+/// Only the allowed options can be used.
+fn prctl_other_options_denied() {
+    SafetyContext::new()
+        .enable(Prctl::nothing()
+            .allow_set_name()
+            .allow_get_name()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let name = c"sandboxed";
+    // SAFETY: PR_SET_NAME takes a pointer to a nul-terminated string
+    let rc = unsafe { libc::prctl(libc::PR_SET_NAME, name.as_ptr(), 0, 0, 0) };
+    assert_eq!(rc, 0);
+
+    let mut buf = [0 as libc::c_char; 16];
+    // SAFETY: PR_GET_NAME takes a pointer to a buffer of 16 bytes
+    let rc = unsafe { libc::prctl(libc::PR_GET_NAME, buf.as_mut_ptr(), 0, 0, 0) };
+    assert_eq!(rc, 0);
+    // SAFETY: the kernel nul-terminates the name
+    let got = unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) };
+    assert_eq!(got, name);
+
+    // SAFETY: PR_GET_DUMPABLE takes no other arguments
+    let rc = unsafe { libc::prctl(libc::PR_GET_DUMPABLE, 0, 0, 0, 0) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,89 @@
+#![allow(unsafe_code)]
+// allow unsafe to call mmap directly
+
+use extrasafe::*;
+use extrasafe::builtins::{Memory, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+fn stdio() -> SystemIO {
+    SystemIO::nothing()
+        .allow_stdout()
+        .allow_stderr()
+}
+
+/// Returns whether an executable anonymous mapping can be created.
+fn can_mmap_exec() -> bool {
+    // SAFETY: anonymous mapping with no address hint, unmapped below if it succeeds
+    let ptr = unsafe {
+        libc::mmap(std::ptr::null_mut(), 4096,
+            libc::PROT_READ | libc::PROT_EXEC, libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+    };
+    if ptr == libc::MAP_FAILED {
+        return false;
+    }
+    // SAFETY: ptr was mapped above with length 4096
+    let rc = unsafe { libc::munmap(ptr, 4096) };
+    assert_eq!(rc, 0);
+    true
+}
+
+#[test]
+fn collision_without_precedence_errors() {
+    let res = SafetyContext::new()
+        .enable(Memory::nothing().allow_mmap()).unwrap()
+        .enable(Sysno::mmap);
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::mmap, "Memory", "mmap", _))));
+
+    let res = SafetyContext::new()
+        .enable(Sysno::mmap).unwrap()
+        .enable(Memory::nothing().allow_mmap());
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::mmap, "Memory", "mmap", _))));
+}
+
+#[test]
+/// A simple rule enabled after a conditional one is dropped.
+fn conditional_precedence_new_simple() {
+    SafetyContext::new()
+        .enable(stdio()).unwrap()
+        .enable(Memory::basic_allocation()).unwrap()
+        .enable_with_precedence(Sysno::mmap, Precedence::Conditional).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert!(!can_mmap_exec(), "executable mmap succeeded incorrectly");
+}
+
+#[test]
+/// A conditional rule enabled after a simple one replaces it.
+fn conditional_precedence_new_conditional() {
+    SafetyContext::new()
+        .enable(stdio()).unwrap()
+        .enable(Sysno::mmap).unwrap()
+        .enable_with_precedence(Memory::basic_allocation(), Precedence::Conditional).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert!(!can_mmap_exec(), "executable mmap succeeded incorrectly");
+}
+
+#[test]
+/// A simple rule enabled after a conditional one replaces it.
+fn simple_precedence_new_simple() {
+    SafetyContext::new()
+        .enable(stdio()).unwrap()
+        .enable(Memory::basic_allocation()).unwrap()
+        .enable_with_precedence(Sysno::mmap, Precedence::Simple).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert!(can_mmap_exec(), "executable mmap failed");
+}
+
+#[test]
+/// A conditional rule enabled after a simple one is dropped.
+fn simple_precedence_new_conditional() {
+    SafetyContext::new()
+        .enable(stdio()).unwrap()
+        .enable(Sysno::mmap).unwrap()
+        .enable_with_precedence(Memory::basic_allocation(), Precedence::Simple).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert!(can_mmap_exec(), "executable mmap failed");
+}
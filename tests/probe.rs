@@ -0,0 +1,77 @@
+//! `SafetyContext::probe` requires a single-threaded process, so this test runs without the
+//! libtest harness, which runs each test on its own thread.
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+fn main() {
+    probe_reports_blocked_syscall();
+    probe_nothing_blocked();
+    probe_no_rules();
+    probe_rejects_notify();
+    probe_rejects_denied_exit_group();
+    probe_rejects_multiple_threads();
+}
+
+/// The first blocked syscall is reported, and the current process is not restricted.
+fn probe_reports_blocked_syscall() {
+    let blocked = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .probe(|| {
+            let _res = std::fs::File::open("/etc/passwd");
+        }).unwrap();
+    assert_eq!(blocked, Some(Sysno::openat));
+
+    assert!(!is_seccomp_active());
+    assert!(std::fs::File::open("/etc/passwd").is_ok());
+}
+
+fn probe_nothing_blocked() {
+    let blocked = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .probe(|| {
+            let _pid = std::process::id();
+        }).unwrap();
+    assert_eq!(blocked, None);
+}
+
+fn probe_no_rules() {
+    let res = SafetyContext::new().probe(|| ());
+    assert!(res.is_err());
+}
+
+fn probe_rejects_notify() {
+    let err = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .notify_on(&[Sysno::openat])
+        .probe(|| ()).unwrap_err();
+    let err = err.into_inner().unwrap();
+    assert!(matches!(err.downcast_ref(), Some(ExtraSafeError::NotifyListenerRequired)));
+}
+
+fn probe_rejects_denied_exit_group() {
+    let err = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .deny(Sysno::exit_group)
+        .probe(|| ()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+fn probe_rejects_multiple_threads() {
+    let (sender, receiver) = std::sync::mpsc::channel::<()>();
+    let thread = std::thread::spawn(move || receiver.recv());
+
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .probe(|| ());
+    assert!(res.is_err());
+
+    sender.send(()).unwrap();
+    let _res = thread.join().unwrap();
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "landlock")]
+
+use std::thread;
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// The process's own files in /proc can be read, but not those of other processes.
+fn proc_self_only() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_proc_self()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        let res = std::fs::read_to_string("/proc/self/stat");
+        assert!(res.is_ok(), "failed to read /proc/self/stat: {:?}", res.unwrap_err());
+
+        let res = std::fs::read_to_string(format!("/proc/{}/stat", std::process::id()));
+        assert!(res.is_ok(), "failed to read own stat by pid: {:?}", res.unwrap_err());
+
+        let res = std::fs::read_dir("/proc/self/task");
+        assert!(res.is_ok(), "failed to list /proc/self/task: {:?}", res.unwrap_err());
+
+        let res = std::fs::read_to_string("/proc/1/stat");
+        assert!(res.is_err(), "read another process's stat");
+
+        let res = std::fs::read_to_string("/etc/passwd");
+        assert!(res.is_err(), "read a file outside of /proc/self");
+    }).join().unwrap();
+}
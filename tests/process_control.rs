@@ -0,0 +1,43 @@
+#![allow(unsafe_code)]
+// allow unsafe to call wait4 directly
+
+use std::thread;
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{ProcessControl, SystemIO};
+
+/// Call `wait4` for any child without blocking and return the errno it fails with.
+fn wait_errno() -> Option<i32> {
+    // SAFETY: a null status and rusage are allowed
+    let rc = unsafe { libc::wait4(-1, std::ptr::null_mut(), libc::WNOHANG, std::ptr::null_mut()) };
+    assert_eq!(rc, -1, "wait4 found a child incorrectly");
+    std::io::Error::last_os_error().raw_os_error()
+}
+
+#[test]
+/// Without reaping allowed, waiting fails with EPERM. With it, waiting fails because there are
+/// no children.
+fn process_control_reaping() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .enable(ProcessControl::nothing()
+                .allow_exit()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(wait_errno(), Some(libc::EPERM));
+    }).join().unwrap();
+
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .enable(ProcessControl::reaping()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(wait_errno(), Some(libc::ECHILD));
+    }).join().unwrap();
+}
@@ -0,0 +1,27 @@
+#![allow(unsafe_code)]
+// allow unsafe to call getppid and sysinfo directly
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{ProcessInfo, SystemIO};
+
+#[test]
+fn process_info_ids_allowed() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(ProcessInfo::nothing()
+            .allow_ids()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: getppid has no arguments and can't fail
+    let ppid = unsafe { libc::getppid() };
+    assert!(ppid > 0, "getppid failed");
+
+    // SAFETY: info is a valid sysinfo struct
+    let mut info = unsafe { std::mem::zeroed::<libc::sysinfo>() };
+    // SAFETY: same as above
+    let rc = unsafe { libc::sysinfo(&mut info) };
+    assert_eq!(rc, -1, "sysinfo succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,18 @@
+use extrasafe::RuleSet;
+use extrasafe::builtins::Randomness;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn randomness_nothing() {
+    let randomness = Randomness::nothing();
+    assert!(randomness.simple_rules().is_empty());
+    assert!(randomness.conditional_rules().is_empty());
+}
+
+#[test]
+fn randomness_getrandom() {
+    let randomness = Randomness::nothing()
+        .allow_getrandom();
+    assert_eq!(randomness.simple_rules(), vec![Sysno::getrandom]);
+    assert_eq!(randomness.name(), "Randomness");
+}
@@ -0,0 +1,80 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// Without any landlock rules, requiring landlock fails before anything is applied.
+fn require_landlock_without_rules() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .require_landlock()
+        .into_filter();
+    assert!(matches!(res, Err(ExtraSafeError::LandlockRequired)), "{:?}", res.err());
+
+    // Not requiring it is fine
+    let res = SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .into_filter();
+    assert!(res.is_ok());
+}
+
+#[cfg(not(feature = "landlock"))]
+#[test]
+/// Without the landlock feature, landlock can never be enforced.
+fn require_landlock_feature_disabled() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .require_landlock()
+        .apply_to_current_thread();
+    assert!(matches!(res, Err(ExtraSafeError::LandlockRequired)), "{:?}", res);
+
+    // nothing was applied
+    assert!(!is_seccomp_active());
+}
+
+#[cfg(feature = "landlock")]
+#[test]
+/// With landlock rules, requiring landlock succeeds if the kernel supports it.
+fn require_landlock_with_rules() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let res = SafetyContext::new()
+        .enable(SystemIO::stdio()
+            .allow_list_dir(dir.path())).unwrap()
+        .require_landlock()
+        .into_filter();
+
+    if SafetyContext::landlock_abi().is_some() {
+        assert!(res.is_ok(), "{:?}", res.err());
+    }
+    else {
+        assert!(matches!(res, Err(ExtraSafeError::LandlockRequired)), "{:?}", res.err());
+    }
+}
+
+#[cfg(feature = "landlock")]
+#[test]
+/// If landlock can't be enforced when the filter is applied, applying fails instead of silently
+/// continuing without it. The kernel allows at most 16 landlock layers per thread, so the 17th
+/// `landlock_restrict_self` fails.
+fn require_landlock_fails_on_apply() {
+    if SafetyContext::landlock_abi().is_none() {
+        return;
+    }
+
+    std::thread::spawn(|| {
+        let dir = tempfile::tempdir().unwrap();
+        let context = || SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_list_dir(dir.path())).unwrap()
+            .require_landlock()
+            .landlock_only();
+
+        for _ in 0..16 {
+            let res = context().apply_to_current_thread();
+            assert!(res.is_ok(), "{:?}", res);
+        }
+
+        let res = context().apply_to_current_thread();
+        assert!(matches!(res, Err(ExtraSafeError::LandlockError(_))), "{:?}", res);
+    }).join().unwrap();
+}
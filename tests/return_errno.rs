@@ -0,0 +1,122 @@
+#![allow(unsafe_code)]
+// allow unsafe to call raw syscalls
+
+use std::thread;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+/// Call `syscall` without arguments and return the errno it fails with, or `None` if it
+/// succeeds.
+fn errno_of(syscall: libc::c_long) -> Option<i32> {
+    // SAFETY: the syscalls used here fail with EFAULT at worst when given null pointers
+    let rc = unsafe { libc::syscall(syscall, 0, 0, 0, 0, 0) };
+    (rc == -1).then(|| std::io::Error::last_os_error().raw_os_error().unwrap())
+}
+
+#[test]
+/// Mapped syscalls return their errno, and other denied syscalls keep the default one.
+fn mapped_syscalls_return_errno() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stderr()).unwrap()
+            .return_errno_for(&[(Sysno::statx, libc::ENOSYS), (Sysno::getuid, libc::EACCES)])
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(errno_of(libc::SYS_statx), Some(libc::ENOSYS));
+        assert_eq!(errno_of(libc::SYS_getuid), Some(libc::EACCES));
+        assert_eq!(errno_of(libc::SYS_getppid), Some(libc::EPERM));
+    }).join().unwrap();
+}
+
+#[test]
+/// With `ENOSYS` for `statx`, std falls back to `newfstatat`.
+fn statx_enosys_fallback() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_metadata()).unwrap()
+            .return_errno_for(&[(Sysno::statx, libc::ENOSYS)])
+            .apply_to_current_thread().unwrap();
+
+        let res = std::fs::metadata("/");
+        assert!(res.is_ok(), "metadata failed: {:?}", res.unwrap_err());
+    }).join().unwrap();
+}
+
+#[test]
+/// Mapped syscalls fail even if a ruleset allows them, and don't kill the process.
+fn mapped_syscalls_override_rules() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_metadata()).unwrap()
+            .kill_process_on_violation()
+            .return_errno_for(&[(Sysno::getcwd, libc::ENOSYS)])
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(errno_of(libc::SYS_getcwd), Some(libc::ENOSYS));
+    }).join().unwrap();
+}
+
+#[test]
+/// Syscalls denied with `deny` keep the default action.
+fn denied_syscalls_keep_default() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_metadata()).unwrap()
+            .deny(Sysno::getcwd)
+            .return_errno_for(&[(Sysno::getcwd, libc::ENOSYS)])
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(errno_of(libc::SYS_getcwd), Some(libc::EPERM));
+    }).join().unwrap();
+}
+
+#[test]
+fn return_errno_invalid() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stderr()).unwrap()
+        .return_errno_for(&[(Sysno::statx, -1)])
+        .into_filter();
+    assert!(matches!(res, Err(ExtraSafeError::ErrnoOutOfRange(Sysno::statx, -1))));
+
+    for errno in [0, 4096, 0x10000] {
+        let res = SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stderr()).unwrap()
+            .return_errno_for(&[(Sysno::statx, errno)])
+            .into_filter();
+        assert!(matches!(res, Err(ExtraSafeError::ErrnoOutOfRange(Sysno::statx, e)) if e == errno));
+    }
+
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stderr()).unwrap()
+        .return_errno_for(&[(Sysno::statx, 4095)])
+        .into_filter();
+    assert!(res.is_ok());
+}
+
+#[test]
+/// Mapped syscalls and syscalls sent to the notify listener are not reported as allowed, even if
+/// a ruleset allows them.
+fn mapped_syscalls_not_enabled() {
+    let context = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_metadata()).unwrap()
+        .return_errno_for(&[(Sysno::getcwd, libc::ENOSYS), (Sysno::getuid, libc::EACCES)])
+        .notify_on(&[Sysno::statx]);
+
+    let enabled = context.enabled_syscalls();
+    assert!(!enabled.contains(&Sysno::getcwd));
+    assert!(!enabled.contains(&Sysno::statx));
+    assert!(enabled.contains(&Sysno::fstat));
+
+    let denied = context.check_trace(&[Sysno::fstat, Sysno::getcwd, Sysno::statx, Sysno::getuid]);
+    assert_eq!(denied, [Sysno::getcwd, Sysno::statx, Sysno::getuid]);
+}
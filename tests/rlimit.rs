@@ -0,0 +1,69 @@
+#![allow(unsafe_code)]
+// allow unsafe to call getrlimit and setrlimit
+
+use std::collections::HashSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{Rlimit, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn rlimit_nothing() {
+    let rlimit = Rlimit::nothing();
+    assert!(rlimit.simple_rules().is_empty());
+    assert!(rlimit.conditional_rules().is_empty());
+    assert_eq!(rlimit.name(), "Rlimit");
+}
+
+#[test]
+/// Getting only allows `prlimit64` on the current process without a new limit.
+fn rlimit_get_membership() {
+    let rlimit = Rlimit::nothing().allow_get();
+    assert_eq!(rlimit.simple_rules(), vec![Sysno::getrlimit]);
+
+    let rules = rlimit.conditional_rules();
+    assert_eq!(rules.keys().collect::<Vec<_>>(), vec![&Sysno::prlimit64]);
+    assert_eq!(rules[&Sysno::prlimit64].len(), 1);
+    assert_eq!(rules[&Sysno::prlimit64][0].argument_filters, vec![
+        SeccompArgumentFilter::new32(0, SeccompilerComparator::Eq, 0),
+        seccomp_arg_filter!(arg2 == 0),
+    ]);
+}
+
+#[test]
+/// Setting allows all three syscalls unconditionally, regardless of order.
+fn rlimit_set_membership() {
+    let expected = HashSet::from([Sysno::getrlimit, Sysno::setrlimit, Sysno::prlimit64]);
+
+    let rlimit = Rlimit::nothing().allow_set().yes_really();
+    assert_eq!(rlimit.simple_rules().into_iter().collect::<HashSet<_>>(), expected);
+    assert!(rlimit.conditional_rules().is_empty());
+
+    let rlimit = Rlimit::nothing().allow_get().allow_set().yes_really();
+    assert_eq!(rlimit.simple_rules().into_iter().collect::<HashSet<_>>(), expected);
+    assert!(rlimit.conditional_rules().is_empty());
+
+    let rlimit = Rlimit::nothing().allow_set().yes_really().allow_get();
+    assert_eq!(rlimit.simple_rules().into_iter().collect::<HashSet<_>>(), expected);
+    assert!(rlimit.conditional_rules().is_empty());
+}
+
+#[test]
+/// libc's getrlimit works with `allow_get`, but setrlimit doesn't.
+fn rlimit_get_allowed_set_denied() {
+    SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .enable(Rlimit::nothing().allow_get()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+    // SAFETY: limit is a valid rlimit struct
+    let rc = unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) };
+    assert_eq!(rc, 0);
+    assert!(limit.rlim_cur > 0);
+
+    // SAFETY: limit is a valid rlimit struct
+    let rc = unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
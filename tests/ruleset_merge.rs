@@ -0,0 +1,83 @@
+use std::collections::BTreeSet;
+
+use extrasafe::*;
+use extrasafe::builtins::{Memory, SystemIO, Time};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// Enabling two disjoint subsets of the same ruleset gives their union.
+fn merge_disjoint_subsets() {
+    let ctx = SafetyContext::new()
+        .enable(Time::nothing()
+            .allow_gettime()).unwrap()
+        .enable(Memory::nothing()
+            .allow_brk()).unwrap()
+        .enable(Memory::nothing()
+            .allow_mremap()
+            .allow_mmap()).unwrap();
+
+    assert_eq!(ctx.enabled_syscalls(), BTreeSet::from([
+        Sysno::clock_gettime, Sysno::clock_getres,
+        Sysno::brk, Sysno::mremap, Sysno::mmap,
+    ]));
+}
+
+#[test]
+/// Enabling the same rules twice doesn't conflict, and the result still applies.
+fn merge_same_rules() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .enable(Time::nothing()
+            .allow_gettime()).unwrap()
+        .enable(Time::nothing()
+            .allow_gettime()).unwrap()
+        .apply_to_current_thread().unwrap();
+}
+
+#[test]
+/// Conflicts between simple and conditional rules are still errors, even within the same ruleset.
+fn merge_conflict_still_errors() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .enable(SystemIO::nothing()
+            .allow_write());
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::write, "SystemIO", "SystemIO", _))));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn merge_no_duplicate_rules() {
+    let once = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap();
+    let twice = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap();
+    assert_eq!(once.to_json(), twice.to_json());
+}
+
+#[cfg(feature = "landlock")]
+#[test]
+/// Landlock rules for the same path from the same ruleset are combined.
+fn merge_landlock_same_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("file");
+    std::fs::write(&file, "hello").unwrap();
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_read_path(dir.path())).unwrap()
+        .enable(SystemIO::nothing()
+            .allow_list_dir(dir.path())).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "hello");
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
@@ -0,0 +1,28 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// The closure runs with the filter applied, and the filter stays applied afterwards.
+fn run_applies_filter() {
+    std::thread::spawn(|| {
+        let res = SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .run(|| std::fs::File::open("/proc/cpuinfo").map_err(|err| err.kind()));
+        assert_eq!(res.unwrap().unwrap_err(), std::io::ErrorKind::PermissionDenied);
+
+        let res = std::fs::File::open("/proc/cpuinfo");
+        assert!(res.is_err(), "Filter was removed after the closure returned");
+    }).join().unwrap();
+}
+
+#[test]
+/// The closure is not run if the filter can't be applied.
+fn run_error() {
+    let mut ran = false;
+    let res = SafetyContext::new()
+        .run(|| ran = true);
+    assert!(matches!(res, Err(ExtraSafeError::NoRulesEnabled)));
+    assert!(!ran);
+}
@@ -0,0 +1,58 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use extrasafe::*;
+use extrasafe::builtins::{BasicCapabilities, RustRuntime};
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn rust_runtime_syscalls() {
+    let syscalls: HashSet<Sysno> = RustRuntime.simple_rules().into_iter().collect();
+    for syscall in [
+        Sysno::mmap, Sysno::munmap, Sysno::brk,
+        Sysno::futex, Sysno::sched_yield,
+        Sysno::sigaltstack, Sysno::rt_sigaction, Sysno::rt_sigreturn,
+        Sysno::getrandom, Sysno::rseq, Sysno::exit_group,
+    ] {
+        assert!(syscalls.contains(&syscall), "missing {}", syscall);
+    }
+    assert!(RustRuntime.conditional_rules().is_empty());
+
+    // nothing that can create threads or processes, or write
+    for syscall in [Sysno::clone, Sysno::clone3, Sysno::execve, Sysno::write] {
+        assert!(!syscalls.contains(&syscall), "{} should not be included", syscall);
+    }
+
+    // composes with BasicCapabilities
+    let res = SafetyContext::new()
+        .enable(RustRuntime).unwrap()
+        .enable(BasicCapabilities);
+    assert!(res.is_ok());
+}
+
+#[test]
+/// Allocating, hashing, locking, and parking work with only `RustRuntime` enabled.
+fn rust_runtime_only() {
+    SafetyContext::new()
+        .enable(RustRuntime).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let mut v = vec![1u8; 1 << 20];
+    v.resize(4 << 20, 2);
+    assert_eq!(v.len(), 4 << 20);
+    drop(v);
+
+    let mut map = HashMap::new();
+    let _prev = map.insert("key", 1);
+    assert_eq!(map["key"], 1);
+
+    let counter = Arc::new(Mutex::new(0));
+    *counter.lock().unwrap() += 1;
+    assert_eq!(*counter.lock().unwrap(), 1);
+
+    std::thread::current().unpark();
+    std::thread::park();
+    std::thread::park_timeout(Duration::from_millis(1));
+    std::thread::yield_now();
+}
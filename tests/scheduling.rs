@@ -0,0 +1,27 @@
+#![allow(unsafe_code)]
+// allow unsafe to call the sched functions directly
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{Scheduling, SystemIO};
+
+#[test]
+fn scheduling_query_allowed() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Scheduling::nothing()
+            .allow_query()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let mut param = libc::sched_param { sched_priority: 0 };
+    // SAFETY: param is a valid sched_param
+    let rc = unsafe { libc::sched_getparam(0, &mut param) };
+    assert_eq!(rc, 0, "sched_getparam failed: {:?}", std::io::Error::last_os_error());
+
+    // SAFETY: same as above, and setting the current parameters does not change anything even if
+    // it succeeds
+    let rc = unsafe { libc::sched_setparam(0, &param) };
+    assert_eq!(rc, -1, "sched_setparam succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,21 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// A filter is only reported as active on the thread it was applied to.
+fn seccomp_active_after_apply() {
+    std::thread::spawn(|| {
+        assert!(!is_seccomp_active());
+
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        // prctl is not allowed by the filter, which also means the filter is active.
+        assert!(is_seccomp_active());
+    }).join().unwrap();
+
+    assert!(!is_seccomp_active());
+}
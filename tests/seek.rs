@@ -0,0 +1,31 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// `lseek` is part of both the read and write groups.
+fn lseek_in_read_and_write() {
+    assert!(SystemIO::nothing().allow_read().simple_rules().contains(&Sysno::lseek));
+    assert!(SystemIO::nothing().allow_write().simple_rules().contains(&Sysno::lseek));
+}
+
+#[test]
+/// Seeking in an already opened file works with only `allow_read`.
+fn seek_with_allow_read() {
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(b"hello world").unwrap();
+
+    std::thread::spawn(move || {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_read()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        let _pos = file.seek(SeekFrom::Start(6)).unwrap();
+        let mut contents = String::new();
+        let _len = file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "world");
+    }).join().unwrap();
+}
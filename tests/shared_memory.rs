@@ -0,0 +1,38 @@
+use extrasafe::*;
+use extrasafe::builtins::{SharedMemory, YesReally};
+use extrasafe::syscalls::Sysno;
+
+use std::collections::HashSet;
+
+fn simple_rules(shared_memory: &SharedMemory) -> HashSet<Sysno> {
+    shared_memory.simple_rules().into_iter().collect()
+}
+
+#[test]
+fn shared_memory_nothing() {
+    let shared_memory = SharedMemory::nothing();
+    assert!(shared_memory.simple_rules().is_empty());
+    assert!(shared_memory.conditional_rules().is_empty());
+    assert_eq!(shared_memory.name(), "SharedMemory");
+}
+
+#[test]
+fn shared_memory_sysv() {
+    assert_eq!(simple_rules(&SharedMemory::nothing().allow_sysv_shm_detach()),
+        HashSet::from([Sysno::shmdt]));
+
+    let sysv: YesReally<SharedMemory> = SharedMemory::nothing().allow_sysv_shm();
+    assert_eq!(simple_rules(&sysv.yes_really()),
+        HashSet::from([Sysno::shmget, Sysno::shmat, Sysno::shmdt, Sysno::shmctl]));
+}
+
+#[test]
+fn shared_memory_posix_mq() {
+    let io = HashSet::from([Sysno::mq_timedsend, Sysno::mq_timedreceive, Sysno::mq_getsetattr, Sysno::mq_notify]);
+    assert_eq!(simple_rules(&SharedMemory::nothing().allow_posix_mq_io()), io);
+
+    let mq: YesReally<SharedMemory> = SharedMemory::nothing().allow_posix_mq();
+    let mut expected = io;
+    expected.extend([Sysno::mq_open, Sysno::mq_unlink]);
+    assert_eq!(simple_rules(&mq.yes_really()), expected);
+}
@@ -0,0 +1,43 @@
+#![allow(unsafe_code)]
+// allow unsafe to call sigpending directly
+
+use std::thread;
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{Signals, SystemIO};
+
+/// Call `rt_sigpending` and return the errno it fails with, or `None` if it succeeds.
+fn sigpending_errno() -> Option<i32> {
+    // SAFETY: set is a valid sigset_t
+    let mut set = unsafe { std::mem::zeroed::<libc::sigset_t>() };
+    // SAFETY: same as above
+    let rc = unsafe { libc::sigpending(&mut set) };
+    (rc == -1).then(|| std::io::Error::last_os_error().raw_os_error().unwrap())
+}
+
+#[test]
+/// The signal handler syscalls are allowed by default, but `rt_sigpending` is not.
+fn signals_sigpending() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .enable(Signals::handlers()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(sigpending_errno(), Some(libc::EPERM));
+    }).join().unwrap();
+
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .enable(Signals::nothing()
+                .allow_rt_sigpending()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(sigpending_errno(), None);
+    }).join().unwrap();
+}
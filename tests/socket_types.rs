@@ -0,0 +1,66 @@
+#![allow(unsafe_code)]
+// allow unsafe to call socket directly
+
+use extrasafe::*;
+use extrasafe::builtins::{Networking, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+fn socket_filters(networking: &Networking) -> Vec<Vec<SeccompArgumentFilter>> {
+    networking.conditional_rules()[&Sysno::socket].iter()
+        .map(|rule| rule.argument_filters.clone())
+        .collect()
+}
+
+#[test]
+/// The type is compared under a mask that excludes the flag bits.
+fn socket_type_comparators() {
+    let stream = Networking::nothing().allow_stream_sockets_only();
+    assert!(stream.simple_rules().is_empty());
+    assert_eq!(socket_filters(&stream), vec![vec![
+        SeccompArgumentFilter::new(1, SeccompilerComparator::MaskedEq(0xf), libc::SOCK_STREAM as u64),
+    ]]);
+
+    let dgram = Networking::nothing().allow_dgram_sockets_only();
+    assert_eq!(socket_filters(&dgram), vec![vec![
+        SeccompArgumentFilter::new(1, SeccompilerComparator::MaskedEq(0xf), libc::SOCK_DGRAM as u64),
+    ]]);
+
+    let flags = (libc::SOCK_CLOEXEC | libc::SOCK_NONBLOCK) as u64;
+    assert_eq!(flags & 0xf, 0);
+    assert_eq!(libc::SOCK_SEQPACKET as u64 & 0xf, libc::SOCK_SEQPACKET as u64);
+}
+
+#[test]
+/// Both can be allowed at once, and each rule is only added once.
+fn socket_types_combined() {
+    let both = Networking::nothing()
+        .allow_stream_sockets_only()
+        .allow_dgram_sockets_only()
+        .allow_stream_sockets_only();
+    assert_eq!(socket_filters(&both).len(), 2);
+}
+
+fn socket(socket_type: libc::c_int) -> Result<(), i32> {
+    // SAFETY: socket takes no pointers, and the fd is closed if it was created
+    let fd = unsafe { libc::socket(libc::AF_UNIX, socket_type, 0) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error().raw_os_error().unwrap());
+    }
+    // SAFETY: fd is a valid fd owned by this function
+    let _rc = unsafe { libc::close(fd) };
+    Ok(())
+}
+
+#[test]
+/// Stream sockets can be created with any flags, but other types can't.
+fn stream_sockets_only_allowed() {
+    SafetyContext::new()
+        .enable(SystemIO::stdio().allow_close()).unwrap()
+        .enable(Networking::nothing().allow_stream_sockets_only()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert_eq!(socket(libc::SOCK_STREAM), Ok(()));
+    assert_eq!(socket(libc::SOCK_STREAM | libc::SOCK_CLOEXEC | libc::SOCK_NONBLOCK), Ok(()));
+    assert_eq!(socket(libc::SOCK_DGRAM), Err(libc::EPERM));
+    assert_eq!(socket(libc::SOCK_SEQPACKET), Err(libc::EPERM));
+}
@@ -0,0 +1,126 @@
+use extrasafe::{seccomp_arg_filter, ExtraSafeError, RuleSet, SafetyContext, SeccompRule};
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+use std::collections::HashMap;
+use std::process::Command;
+
+#[test]
+/// Spawn a process with a restrictive filter and check that it fails, while the current process
+/// is not restricted.
+fn spawn_sandboxed_restricted() {
+    let mut cmd = Command::new("cat");
+    let _cmd = cmd.arg("/proc/cpuinfo");
+
+    let child = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .spawn_sandboxed(cmd);
+    assert!(child.is_ok(), "Error spawning child process: {:?}", child.unwrap_err());
+
+    // Like in tests/inherit_filters.rs, this fails because the new process is not allowed to open
+    // ld or glibc.
+    let status = child.unwrap().wait().unwrap();
+    assert!(!status.success(), "Child process suceeded incorrectly.");
+
+    let res = std::fs::read("/proc/cpuinfo");
+    assert!(res.is_ok(), "Current process was restricted: {:?}", res.unwrap_err());
+}
+
+/// The syscalls used by a dynamically linked program while starting up and exiting.
+struct Startup;
+impl RuleSet for Startup {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        vec![Sysno::arch_prctl, Sysno::set_tid_address, Sysno::rseq, Sysno::prlimit64,
+            Sysno::access, Sysno::faccessat, Sysno::faccessat2, Sysno::exit_group]
+    }
+
+    fn name(&self) -> &'static str {
+        "Startup"
+    }
+}
+
+#[test]
+/// A process with the syscalls it needs allowed runs successfully.
+fn spawn_sandboxed_allowed() {
+    let cmd = Command::new("true");
+
+    let child = SafetyContext::new()
+        .enable(SystemIO::everything()).unwrap()
+        .enable(Startup).unwrap()
+        .spawn_sandboxed(cmd);
+    let status = child.unwrap().wait().unwrap();
+    assert!(status.success(), "Child process failed incorrectly.");
+}
+
+/// Only allows `execve` with a null path, i.e. never.
+struct RestrictedExecve;
+impl RuleSet for RestrictedExecve {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        Vec::new()
+    }
+
+    fn conditional_rules(&self) -> HashMap<Sysno, Vec<SeccompRule>> {
+        let rule = SeccompRule::new(Sysno::execve)
+            .and_condition(seccomp_arg_filter!(arg0 == 0));
+        HashMap::from([(Sysno::execve, vec![rule])])
+    }
+
+    fn name(&self) -> &'static str {
+        "RestrictedExecve"
+    }
+}
+
+#[test]
+/// Conditional rules on `execve` are not silently replaced by the unconditional rule needed to
+/// start the program.
+fn spawn_sandboxed_conditional_execve() {
+    let res = SafetyContext::new()
+        .enable(RestrictedExecve).unwrap()
+        .spawn_sandboxed(Command::new("true"));
+    let err = res.unwrap_err().into_inner().unwrap();
+    assert!(matches!(err.downcast_ref(), Some(ExtraSafeError::ConditionalNoEffectError(Sysno::execve, ..))));
+}
+
+#[test]
+fn spawn_sandboxed_notify_rejected() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::everything()).unwrap()
+        .notify_on(&[Sysno::openat])
+        .spawn_sandboxed(Command::new("true"));
+    let err = res.unwrap_err().into_inner().unwrap();
+    assert!(matches!(err.downcast_ref(), Some(ExtraSafeError::NotifyListenerRequired)));
+}
+
+#[cfg(feature = "landlock")]
+#[test]
+fn spawn_sandboxed_landlock_rejected() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_read_path("/etc")).unwrap()
+        .spawn_sandboxed(Command::new("true"));
+    let err = res.unwrap_err().into_inner().unwrap();
+    assert!(matches!(err.downcast_ref(), Some(ExtraSafeError::LandlockNoSpawn)));
+}
+
+#[test]
+/// If the child can't report that `execve` failed because `write` is not allowed, spawning
+/// succeeds, but the child exits without running anything. If it can, spawning fails.
+fn spawn_sandboxed_nonexistent() {
+    let cmd = Command::new("/nonexistent/extrasafe-test-binary");
+    let child = SafetyContext::new()
+        .enable(SystemIO::nothing()).unwrap()
+        .spawn_sandboxed(cmd);
+    assert!(child.is_ok(), "Error spawning child process: {:?}", child.unwrap_err());
+    let status = child.unwrap().wait().unwrap();
+    assert!(!status.success(), "Child process suceeded incorrectly.");
+
+    let cmd = Command::new("/nonexistent/extrasafe-test-binary");
+    let child = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_write()).unwrap()
+        .spawn_sandboxed(cmd);
+    let err = child.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound, "{:?}", err);
+}
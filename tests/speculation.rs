@@ -0,0 +1,48 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+
+/// The current thread's speculative store bypass status from `/proc/thread-self/status`, e.g.
+/// "thread vulnerable" or "thread force mitigated".
+fn ssb_status() -> String {
+    let status = std::fs::read_to_string("/proc/thread-self/status").unwrap();
+    status.lines()
+        .find_map(|line| line.strip_prefix("Speculation_Store_Bypass:"))
+        .map(|value| value.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn context() -> SafetyContext {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_open_readonly()
+            .allow_read()
+            .allow_metadata()
+            .allow_close()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+}
+
+#[test]
+/// With speculation allowed, installing the filter does not change the SSB mitigation status.
+fn allow_speculation_keeps_status() {
+    std::thread::spawn(|| {
+        let before = ssb_status();
+        context()
+            .allow_speculation(true)
+            .apply_to_current_thread().unwrap();
+        assert_eq!(ssb_status(), before);
+    }).join().unwrap();
+}
+
+#[test]
+/// By default, the kernel may enable the SSB mitigation when the filter is installed, if the CPU
+/// is vulnerable and the kernel uses the seccomp mitigation mode.
+fn default_speculation_mitigated() {
+    std::thread::spawn(|| {
+        let before = ssb_status();
+        context()
+            .apply_to_current_thread().unwrap();
+        let after = ssb_status();
+        assert!(after == before || after == "thread force mitigated", "unexpected status {}", after);
+    }).join().unwrap();
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "landlock")]
+
+use std::path::PathBuf;
+use std::thread;
+
+use extrasafe::{RuleSet, SafetyContext};
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// The extra paths are added to the default ones.
+fn ssl_files_with_extra_paths() {
+    let extra = PathBuf::from("/opt/certs/ca-bundle.pem");
+    let paths: Vec<PathBuf> = SystemIO::nothing()
+        .allow_ssl_files_with(&[&extra])
+        .landlock_rules().into_iter()
+        .map(|rule| rule.path)
+        .collect();
+
+    let defaults: Vec<PathBuf> = SystemIO::nothing()
+        .allow_ssl_files()
+        .landlock_rules().into_iter()
+        .map(|rule| rule.path)
+        .collect();
+    assert!(!defaults.is_empty());
+
+    assert_eq!(paths.len(), defaults.len() + 1);
+    assert!(paths.contains(&extra));
+    for path in &defaults {
+        assert!(paths.contains(path), "missing default path {:?}", path);
+    }
+}
+
+#[test]
+/// A certificate bundle in a custom directory can be read after applying the filter.
+fn ssl_files_with_custom_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = dir.path().join("ca-bundle.pem");
+    std::fs::write(&bundle, "certificates").unwrap();
+    let other = tempfile::tempdir().unwrap();
+    let other_file = other.path().join("secret");
+    std::fs::write(&other_file, "secret").unwrap();
+
+    let dir_path = dir.path().to_path_buf();
+    thread::spawn(move || {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_ssl_files_with(&[dir_path])).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        let res = std::fs::read_to_string(&bundle);
+        assert_eq!(res.unwrap(), "certificates");
+
+        let res = std::fs::read_to_string(&other_file);
+        assert!(res.is_err(), "read a file outside of the allowed paths");
+    }).join().unwrap();
+}
@@ -0,0 +1,55 @@
+use extrasafe::*;
+use extrasafe::builtins::{Cwd, Pipes, SystemIO};
+use extrasafe::syscalls::Sysno;
+
+/// Allows installing further filters.
+struct Seccomp;
+impl RuleSet for Seccomp {
+    fn simple_rules(&self) -> Vec<Sysno> {
+        vec![Sysno::prctl, Sysno::seccomp]
+    }
+
+    fn name(&self) -> &'static str {
+        "seccomp"
+    }
+}
+
+#[test]
+/// Two stacked contexts only allow the syscalls allowed by both of them.
+fn stacked_contexts_both_restrict() {
+    std::thread::spawn(|| {
+        let initial = seccomp_filter_count().expect("could not read filter count");
+
+        // The framework's filter allows getcwd but not pipes.
+        SafetyContext::new()
+            .enable(SystemIO::everything()).unwrap()
+            .enable(Cwd::nothing()
+                .allow_getcwd()).unwrap()
+            .enable(Seccomp).unwrap()
+            .apply_stacked_onto_current_thread().unwrap();
+
+        assert_eq!(seccomp_filter_count(), Some(initial + 1));
+        assert!(std::env::current_dir().is_ok());
+        assert!(std::io::pipe().is_err());
+
+        // The app's filter allows pipes but not getcwd, which is part of SystemIO's metadata
+        // syscalls.
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_open_readonly()
+                .allow_read()
+                .allow_close()).unwrap()
+            .enable(Pipes).unwrap()
+            .apply_stacked_onto_current_thread().unwrap();
+
+        assert_eq!(seccomp_filter_count(), Some(initial + 2));
+        assert!(std::env::current_dir().is_err(), "second filter did not restrict getcwd");
+        assert!(std::io::pipe().is_err(), "second filter lifted the first filter's restriction");
+    }).join().unwrap();
+}
+
+#[test]
+fn filter_count_without_filters() {
+    let count = std::thread::spawn(seccomp_filter_count).join().unwrap();
+    assert!(count.is_some());
+}
@@ -0,0 +1,77 @@
+#![allow(unsafe_code)]
+// allow unsafe to call statx directly
+
+use std::thread;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+/// Call `statx` on the root directory and return its errno, or 0 if it succeeded. This uses the
+/// raw syscall because glibc's wrapper falls back to `fstatat` itself on `ENOSYS`.
+fn statx_errno() -> i32 {
+    let mut buf: libc::statx = unsafe { std::mem::zeroed() };
+    let buf_ptr: *mut libc::statx = &mut buf;
+    let rc = unsafe { libc::syscall(libc::SYS_statx, libc::AT_FDCWD, c"/".as_ptr(), 0, libc::STATX_BASIC_STATS, buf_ptr) };
+    if rc == 0 {
+        0
+    } else {
+        std::io::Error::last_os_error().raw_os_error().unwrap()
+    }
+}
+
+#[test]
+fn statx_rules() {
+    assert_eq!(SystemIO::nothing().allow_statx().simple_rules(), vec![Sysno::statx]);
+
+    let without = SystemIO::nothing()
+        .allow_metadata()
+        .without_statx()
+        .simple_rules();
+    assert!(!without.contains(&Sysno::statx));
+    assert!(without.contains(&Sysno::fstat));
+}
+
+#[test]
+fn allow_statx() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_statx()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(statx_errno(), 0);
+    }).join().unwrap();
+}
+
+#[test]
+/// Without `statx`, it takes the default action.
+fn without_statx_denied() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_metadata()
+                .without_statx()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(statx_errno(), libc::EPERM);
+    }).join().unwrap();
+}
+
+#[test]
+/// With `statx` failing with `ENOSYS`, the standard library falls back to the other `stat`
+/// syscalls.
+fn statx_enosys_falls_back() {
+    thread::spawn(|| {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_metadata()
+                .without_statx()).unwrap()
+            .return_errno_for(&[(Sysno::statx, libc::ENOSYS)])
+            .apply_to_current_thread().unwrap();
+
+        assert_eq!(statx_errno(), libc::ENOSYS);
+        let res = std::fs::metadata("/");
+        assert!(res.is_ok_and(|metadata| metadata.is_dir()));
+    }).join().unwrap();
+}
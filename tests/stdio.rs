@@ -0,0 +1,52 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+use std::io::Write;
+
+/// The fds each of `syscall`'s conditional rules compare against.
+fn fds(io: &SystemIO, syscall: Sysno) -> Vec<SeccompArgumentFilter> {
+    io.conditional_rules()[&syscall].iter()
+        .map(|rule| {
+            assert_eq!(rule.argument_filters.len(), 1);
+            rule.argument_filters[0].clone()
+        })
+        .collect()
+}
+
+#[test]
+/// `read` is only allowed on stdin and `write` only on stdout and stderr.
+fn stdio_comparators() {
+    let io = SystemIO::stdio();
+    assert!(io.simple_rules().is_empty());
+
+    let rules = io.conditional_rules();
+    let mut syscalls: Vec<&Sysno> = rules.keys().collect();
+    syscalls.sort();
+    let mut expected = vec![&Sysno::read, &Sysno::write];
+    expected.sort();
+    assert_eq!(syscalls, expected);
+
+    assert_eq!(fds(&io, Sysno::read), vec![seccomp_arg_filter!(arg0 == 0)]);
+    assert_eq!(fds(&io, Sysno::write), vec![seccomp_arg_filter!(arg0 == 1), seccomp_arg_filter!(arg0 == 2)]);
+}
+
+#[test]
+/// Writing to other fds fails.
+fn stdio_other_fds_denied() {
+    let (_dir, mut file) = {
+        let dir = tempfile::tempdir().unwrap();
+        let file = std::fs::File::create(dir.path().join("denied.txt")).unwrap();
+        (dir, file)
+    };
+
+    SafetyContext::new()
+        .enable(SystemIO::stdio()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    assert!(writeln!(std::io::stdout(), "stdout").is_ok());
+    assert!(writeln!(std::io::stderr(), "stderr").is_ok());
+
+    let res = file.write_all(b"denied");
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::PermissionDenied);
+}
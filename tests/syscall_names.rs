@@ -0,0 +1,35 @@
+use std::collections::BTreeSet;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+fn syscall_names_valid() {
+    let ctx = SafetyContext::new()
+        .allow_syscall_names(&["getppid", "getuid", "getppid"]).unwrap();
+    assert_eq!(ctx.enabled_syscalls(), BTreeSet::from([Sysno::getppid, Sysno::getuid]));
+
+    ctx.apply_to_current_thread().unwrap();
+    assert!(std::fs::File::open("/proc/self/status").is_err());
+}
+
+#[test]
+/// Unknown names are reported and nothing is added.
+fn syscall_names_invalid() {
+    let res = SafetyContext::new()
+        .allow_syscall_names(&["getppid", "not_a_syscall"]);
+
+    let err = res.unwrap_err();
+    assert!(matches!(&err, ExtraSafeError::UnknownSyscall(name) if name == "not_a_syscall"), "{:?}", err);
+    assert!(err.to_string().contains("`not_a_syscall`"), "{}", err);
+}
+
+#[test]
+/// Names of syscalls with conditional rules conflict as if they were enabled by a ruleset.
+fn syscall_names_conflict() {
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing().allow_stdout()).unwrap()
+        .allow_syscall_names(&["write"]);
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::write, "SystemIO", "SyscallNames", _))));
+}
@@ -0,0 +1,62 @@
+use std::os::unix::net::{UnixDatagram, UnixStream};
+
+use extrasafe::*;
+use extrasafe::builtins::Networking;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// The socket rule only matches unix datagram sockets.
+fn syslog_socket_rule() {
+    const AF_UNIX: u64 = libc::AF_UNIX as u64;
+    const SOCK_DGRAM: u64 = libc::SOCK_DGRAM as u64;
+
+    let rules = Networking::nothing()
+        .allow_syslog()
+        .conditional_rules();
+    let rules = &rules[&Sysno::socket];
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].argument_filters, vec![
+        seccomp_arg_filter!(arg0 == AF_UNIX),
+        seccomp_arg_filter!(arg1 & 0xf == SOCK_DGRAM),
+    ]);
+
+    let simple = Networking::nothing().allow_syslog().simple_rules();
+    assert!(simple.contains(&Sysno::connect));
+    assert!(simple.contains(&Sysno::sendto));
+    assert!(!simple.contains(&Sysno::write));
+    assert!(!simple.contains(&Sysno::bind));
+}
+
+#[test]
+/// A datagram can be sent to a local socket standing in for `/dev/log`, but other sockets can't
+/// be created.
+fn syslog_send() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("log");
+    let server = UnixDatagram::bind(&path).unwrap();
+
+    let client_path = path.clone();
+    std::thread::spawn(move || {
+        SafetyContext::new()
+            .enable(Networking::nothing()
+                .allow_syslog()).unwrap()
+            .apply_to_current_thread().unwrap();
+
+        let client = UnixDatagram::unbound().unwrap();
+        let _sent = client.send_to(b"<14>hello", &client_path).unwrap();
+
+        // `send` on a connected socket uses `write`, which is not allowed.
+        client.connect(&client_path).unwrap();
+        let res = client.send(b"<14>hello");
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EPERM));
+
+        let res = UnixStream::connect(&client_path);
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EPERM));
+        let res = std::net::UdpSocket::bind("127.0.0.1:0");
+        assert!(res.is_err(), "Incorrectly succeeded in creating an AF_INET socket");
+    }).join().unwrap();
+
+    let mut buf = [0; 16];
+    let len = server.recv(&mut buf).unwrap();
+    assert_eq!(&buf[..len], b"<14>hello");
+}
@@ -0,0 +1,89 @@
+#![allow(unsafe_code)]
+// allow unsafe to call ioctl directly
+
+use extrasafe::*;
+use extrasafe::builtins::{SystemIO, Terminal};
+use extrasafe::syscalls::Sysno;
+
+/// The request values of the filters on arg1 of each `ioctl` rule.
+fn requests(terminal: &Terminal) -> Vec<u64> {
+    terminal.conditional_rules()[&Sysno::ioctl].iter()
+        .map(|rule| {
+            assert_eq!(rule.argument_filters.len(), 1);
+            let filter = &rule.argument_filters[0];
+            assert_eq!(filter.arg_idx, 1);
+            assert!(!filter.is_64bit);
+            filter.value
+        })
+        .collect()
+}
+
+#[test]
+fn terminal_nothing() {
+    let terminal = Terminal::nothing();
+    assert!(terminal.simple_rules().is_empty());
+    assert!(terminal.conditional_rules().is_empty());
+    assert_eq!(terminal.name(), "Terminal");
+}
+
+#[test]
+fn terminal_raw_mode() {
+    let expected: Vec<u64> = [libc::TCGETS, libc::TCSETS, libc::TCSETSW, libc::TCSETSF].into_iter()
+        .map(|request| u64::from(request as u32))
+        .collect();
+    assert_eq!(requests(&Terminal::raw_mode()), expected);
+
+    let rule = &Terminal::raw_mode().conditional_rules()[&Sysno::ioctl][0];
+    assert_eq!(rule.argument_filters[0],
+        SeccompArgumentFilter::new32(1, SeccompilerComparator::Eq, libc::TCGETS as u32));
+}
+
+#[test]
+fn terminal_window_size() {
+    assert_eq!(requests(&Terminal::window_size()), vec![u64::from(libc::TIOCGWINSZ as u32)]);
+
+    let terminal = Terminal::nothing()
+        .allow_raw_mode()
+        .allow_window_size();
+    assert_eq!(requests(&terminal).len(), 5);
+}
+
+#[test]
+fn terminal_conflicts_with_ioctl() {
+    let res = SafetyContext::new()
+        .enable(Terminal::raw_mode()).unwrap()
+        .enable(SystemIO::nothing().allow_ioctl());
+    assert!(matches!(res, Err(ExtraSafeError::ConditionalNoEffectError(Sysno::ioctl, "Terminal", "SystemIO", _))));
+}
+
+#[test]
+fn terminal_other_ioctl_denied() {
+    let (read_fd, _write_fd) = {
+        let mut fds = [0; 2];
+        // SAFETY: fds is a valid array of two ints
+        let rc = unsafe { libc::pipe(fds.as_mut_ptr()) };
+        assert_eq!(rc, 0);
+        (fds[0], fds[1])
+    };
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Terminal::raw_mode()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // The pipe is not a terminal, so tcgetattr is allowed but fails with ENOTTY.
+    let mut termios = std::mem::MaybeUninit::<libc::termios>::uninit();
+    // SAFETY: termios is valid to write a termios struct to
+    let rc = unsafe { libc::tcgetattr(read_fd, termios.as_mut_ptr()) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::ENOTTY));
+
+    // Other ioctls are denied
+    let mut available: libc::c_int = 0;
+    // SAFETY: FIONREAD writes an int to the provided pointer
+    let rc = unsafe { libc::ioctl(read_fd, libc::FIONREAD, &mut available) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}
@@ -0,0 +1,35 @@
+use extrasafe::SafetyContext;
+use extrasafe::builtins::SystemIO;
+
+#[test]
+/// Every tokio worker applies the filter when it starts, while the calling thread is not
+/// sandboxed.
+fn tokio_workers_sandboxed() {
+    let hook = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .into_filter().unwrap()
+        .thread_start_hook();
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .on_thread_start(hook)
+        .build().unwrap();
+
+    let handles: Vec<_> = (0..4).map(|_| runtime.spawn(async {
+        std::fs::File::open("/proc/cpuinfo")
+            .map(|_| ())
+            .map_err(|err| err.kind())
+    })).collect();
+
+    runtime.block_on(async {
+        for handle in handles {
+            let res = handle.await.unwrap();
+            assert_eq!(res, Err(std::io::ErrorKind::PermissionDenied));
+        }
+    });
+
+    let res = std::fs::File::open("/proc/cpuinfo");
+    assert!(res.is_ok(), "Calling thread was sandboxed: {:?}", res.unwrap_err());
+}
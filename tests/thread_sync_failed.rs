@@ -0,0 +1,48 @@
+#![allow(unsafe_code)]
+// allow unsafe to call gettid
+
+use extrasafe::{ExtraSafeError, SafetyContext};
+use extrasafe::builtins::SystemIO;
+
+use std::sync::mpsc::sync_channel;
+use std::thread;
+
+#[test]
+/// Applying a filter to all threads fails with the id of a thread that already applied a
+/// different filter to itself.
+fn thread_sync_conflict_reports_tid() {
+    let (sender1, recv1) = sync_channel::<libc::pid_t>(0);
+    let (sender2, recv2) = sync_channel::<()>(0);
+
+    let filtered_thread = thread::spawn(move || {
+        // SAFETY: gettid can't fail
+        let tid = unsafe { libc::gettid() };
+
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .apply_to_current_thread().unwrap();
+        sender1.send(tid).unwrap();
+
+        // keep the thread, and therefore its filter, alive until the main thread is done
+        let _done = recv2.recv().unwrap();
+    });
+
+    let tid = recv1.recv().unwrap();
+    let res = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .apply_to_all_threads();
+
+    assert!(matches!(res, Err(ExtraSafeError::ThreadSyncFailed(failed)) if failed == tid),
+        "unexpected result: {:?}", res);
+    assert!(res.unwrap_err().to_string().contains(&tid.to_string()));
+
+    // the filter was not applied to this thread either
+    assert!(!extrasafe::is_seccomp_active());
+
+    sender2.send(()).unwrap();
+    filtered_thread.join().unwrap();
+}
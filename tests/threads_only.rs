@@ -0,0 +1,75 @@
+#![allow(unsafe_code)]
+// allow unsafe to call fork and clone directly
+
+use extrasafe::*;
+use extrasafe::builtins::danger_zone::Threads;
+use extrasafe::syscalls::Sysno;
+
+const CLONE_THREAD_FLAGS: u64 = (libc::CLONE_VM | libc::CLONE_SIGHAND | libc::CLONE_THREAD) as u64;
+const CLONE_NAMESPACE_FLAGS: u64 = (libc::CLONE_NEWNS | libc::CLONE_NEWCGROUP | libc::CLONE_NEWUTS
+    | libc::CLONE_NEWIPC | libc::CLONE_NEWUSER | libc::CLONE_NEWPID | libc::CLONE_NEWNET) as u64;
+
+#[test]
+/// `clone` gets a single rule checking the thread flags and the namespace flags, and `clone3` is
+/// not allowed.
+fn threads_only_comparators() {
+    let threads = Threads::nothing().allow_create_threads_only();
+    assert!(threads.simple_rules().is_empty());
+
+    let rules = threads.conditional_rules();
+    assert_eq!(rules.keys().collect::<Vec<_>>(), [&Sysno::clone]);
+    assert_eq!(rules[&Sysno::clone].len(), 1);
+
+    let filters = &rules[&Sysno::clone][0].argument_filters;
+    assert_eq!(filters.len(), 2);
+    assert_eq!(filters[0], SeccompArgumentFilter::new(0,
+        SeccompilerComparator::MaskedEq(CLONE_THREAD_FLAGS), CLONE_THREAD_FLAGS));
+    assert_eq!(filters[1], SeccompArgumentFilter::new(0,
+        SeccompilerComparator::MaskedEq(CLONE_NAMESPACE_FLAGS), 0));
+}
+
+#[test]
+/// `allow_create` overrides the restricted rule, regardless of order.
+fn threads_only_with_allow_create() {
+    let threads = Threads::nothing()
+        .allow_create_threads_only()
+        .allow_create();
+    assert!(threads.conditional_rules().is_empty());
+
+    let threads = Threads::nothing()
+        .allow_create()
+        .allow_create_threads_only();
+    assert!(threads.conditional_rules().is_empty());
+    assert!(threads.simple_rules().contains(&Sysno::clone));
+}
+
+#[test]
+/// Threads can be created, but processes and namespaces can't.
+fn threads_only_denies_processes() {
+    SafetyContext::new()
+        .with_errno(libc::ENOSYS as u32)
+        .enable(Threads::nothing()
+            .allow_create_threads_only()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // clone3 fails with ENOSYS, so std falls back to clone
+    let res = std::thread::spawn(|| 42).join();
+    assert_eq!(res.unwrap(), 42);
+
+    // SAFETY: the child exits immediately if the fork unexpectedly succeeds
+    let pid = unsafe { libc::fork() };
+    if pid == 0 {
+        // SAFETY: _exit is async-signal-safe
+        unsafe { libc::_exit(0) };
+    }
+    assert_eq!(pid, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::ENOSYS));
+
+    // The kernel would reject this with EINVAL, so the errno shows that the filter denied it
+    // before the kernel saw it.
+    let flags = CLONE_THREAD_FLAGS | libc::CLONE_NEWUSER as u64;
+    // SAFETY: this clone can't succeed, either the filter or the kernel rejects it
+    let rc = unsafe { libc::syscall(libc::SYS_clone, flags, 0, 0, 0, 0) };
+    assert_eq!(rc, -1);
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::ENOSYS));
+}
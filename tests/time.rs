@@ -0,0 +1,100 @@
+#![allow(unsafe_code)]
+// allow unsafe to call the timer functions directly
+
+use extrasafe::*;
+use extrasafe::builtins::{SystemIO, Time};
+use extrasafe::syscalls::Sysno;
+
+use std::collections::HashSet;
+
+#[test]
+fn time_nothing() {
+    let time = Time::nothing();
+    assert!(time.simple_rules().is_empty());
+    assert_eq!(time.name(), "Time");
+}
+
+#[test]
+fn time_gettime() {
+    let rules: HashSet<Sysno> = Time::nothing()
+        .allow_gettime()
+        .simple_rules().into_iter().collect();
+    assert_eq!(rules, HashSet::from([Sysno::clock_gettime, Sysno::clock_getres]));
+}
+
+#[test]
+fn time_posix_timers() {
+    let rules: HashSet<Sysno> = Time::nothing()
+        .allow_posix_timers()
+        .simple_rules().into_iter().collect();
+    assert_eq!(rules, HashSet::from([Sysno::timer_create, Sysno::timer_settime, Sysno::timer_gettime,
+        Sysno::timer_getoverrun, Sysno::timer_delete]));
+}
+
+#[test]
+fn time_posix_timers_allowed() {
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Time::nothing()
+            .allow_posix_timers()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // A timer that delivers no signal, so that it doesn't matter if it expires.
+    // SAFETY: sigevent is plain old data
+    let mut event: libc::sigevent = unsafe { std::mem::zeroed() };
+    event.sigev_notify = libc::SIGEV_NONE;
+    let mut timer: libc::timer_t = std::ptr::null_mut();
+    // SAFETY: event and timer are valid pointers
+    let rc = unsafe { libc::timer_create(libc::CLOCK_MONOTONIC, &mut event, &mut timer) };
+    assert_eq!(rc, 0);
+
+    // SAFETY: value is valid to write an itimerspec to
+    let mut value: libc::itimerspec = unsafe { std::mem::zeroed() };
+    // SAFETY: timer was created above
+    let rc = unsafe { libc::timer_gettime(timer, &mut value) };
+    assert_eq!(rc, 0);
+
+    // SAFETY: timer was created above and is not used afterwards
+    let rc = unsafe { libc::timer_delete(timer) };
+    assert_eq!(rc, 0);
+}
+
+#[test]
+fn time_set_non_realtime_clocks() {
+    let time = Time::nothing()
+        .allow_set_non_realtime_clocks();
+    assert!(time.simple_rules().is_empty());
+
+    let rules = time.conditional_rules();
+    for syscall in [Sysno::clock_settime, Sysno::clock_adjtime] {
+        let expected = SeccompRule::new(syscall)
+            .and_condition(SeccompArgumentFilter::new32(0, SeccompilerComparator::Ne, 0));
+        assert_eq!(rules[&syscall], vec![expected]);
+    }
+    assert!(!rules.contains_key(&Sysno::settimeofday));
+    assert!(!rules.contains_key(&Sysno::adjtimex));
+}
+
+#[test]
+/// `CLOCK_REALTIME` is denied by the filter, while other clocks reach the kernel, which rejects
+/// `CLOCK_MONOTONIC` with `EINVAL` before reading the null timespec.
+fn time_set_non_realtime_clocks_enforced() {
+    let res = std::thread::spawn(|| {
+        SafetyContext::new()
+            .enable(Time::nothing()
+                .allow_set_non_realtime_clocks()).unwrap()
+            .with_errno(libc::ENOTRECOVERABLE as u32)
+            .apply_to_current_thread().unwrap();
+
+        [libc::CLOCK_REALTIME, libc::CLOCK_MONOTONIC].map(|clock| {
+            // SAFETY: the null timespec is rejected without setting the clock
+            let rc = unsafe { libc::syscall(libc::SYS_clock_settime, clock, std::ptr::null::<libc::timespec>()) };
+            assert_eq!(rc, -1);
+            std::io::Error::last_os_error().raw_os_error().unwrap()
+        })
+    }).join().unwrap();
+
+    assert_eq!(res, [libc::ENOTRECOVERABLE, libc::EINVAL]);
+}
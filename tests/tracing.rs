@@ -0,0 +1,77 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use extrasafe::{RuleSet, SafetyContext};
+use extrasafe::builtins::SystemIO;
+
+/// A subscriber that records the fields of every event as `name=value` strings.
+#[derive(Clone, Default)]
+struct Capture {
+    events: Arc<Mutex<Vec<Vec<String>>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<String>);
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Vec::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        self.events.lock().unwrap().push(fields);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+/// Enabling a ruleset and applying the filter each emit an event.
+fn tracing_events_on_enable_and_apply() {
+    let capture = Capture::default();
+
+    let simple = SystemIO::nothing().allow_close().simple_rules().len();
+
+    tracing::subscriber::with_default(capture.clone(), || {
+        SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_close()).unwrap()
+            .apply_to_current_thread().unwrap();
+    });
+
+    let events = capture.events.lock().unwrap();
+    let enabled = events.iter()
+        .find(|fields| fields.contains(&"message=extrasafe: enabled ruleset".to_string()))
+        .expect("no event for enable");
+    assert!(enabled.contains(&"ruleset=\"SystemIO\"".to_string()), "{:?}", enabled);
+    assert!(enabled.contains(&format!("simple={}", simple)), "{:?}", enabled);
+    assert!(enabled.contains(&"conditional=1".to_string()), "{:?}", enabled);
+
+    let applied = events.iter()
+        .find(|fields| fields.contains(&"message=extrasafe: applying filter".to_string()))
+        .expect("no event for apply");
+    assert!(applied.contains(&"target=\"current_thread\"".to_string()), "{:?}", applied);
+    assert!(applied.iter().any(|field| field.starts_with("instructions=")), "{:?}", applied);
+}
@@ -0,0 +1,20 @@
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// A blocked syscall raises SIGSYS, which the handler records, and the syscall fails with EPERM.
+fn trap_logging_handler() {
+    trap::install_logging_handler().unwrap();
+    assert_eq!(trap::last_violation(), None);
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .trap_on_violation()
+        .apply_to_current_thread().unwrap();
+
+    let res = std::fs::File::open("/etc/passwd");
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EPERM));
+    assert_eq!(trap::last_violation(), Some(Sysno::openat));
+}
@@ -0,0 +1,62 @@
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+
+use extrasafe::*;
+use extrasafe::builtins::Networking;
+use extrasafe::syscalls::Sysno;
+
+#[test]
+/// Unix sockets can be created, bound, and connected, but internet sockets are rejected.
+fn unix_sockets_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sock");
+
+    SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_unix_sockets()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let listener = UnixListener::bind(&path).unwrap();
+    let mut client = UnixStream::connect(&path).unwrap();
+    let (mut server, _) = listener.accept().unwrap();
+
+    client.write_all(b"hello").unwrap();
+    let mut buf = [0; 5];
+    server.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+
+    let res = std::net::TcpListener::bind("127.0.0.1:0");
+    assert!(res.is_err(), "Incorrectly succeeded in creating an AF_INET socket");
+    let res = std::net::UdpSocket::bind("127.0.0.1:0");
+    assert!(res.is_err(), "Incorrectly succeeded in creating an AF_INET socket");
+}
+
+#[test]
+/// Enabling unix sockets together with tcp clients keeps the socket rules from both.
+fn unix_sockets_compose_with_tcp() {
+    const AF_UNIX: u64 = libc::AF_UNIX as u64;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sock");
+
+    let net = Networking::nothing()
+        .allow_start_tcp_clients()
+        .allow_unix_sockets();
+    let rules = &net.conditional_rules()[&Sysno::socket];
+    assert_eq!(rules.len(), 3);
+    assert_eq!(rules[2].argument_filters, vec![seccomp_arg_filter!(arg0 == AF_UNIX)]);
+
+    SafetyContext::new()
+        .enable(Networking::nothing()
+            .allow_start_tcp_clients()).unwrap()
+        .enable(Networking::nothing()
+            .allow_unix_sockets()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    let _listener = UnixListener::bind(&path).unwrap();
+
+    let res = std::net::TcpStream::connect("127.0.0.1:1");
+    // the socket is created, so the only possible error is from the connection itself
+    if let Err(err) = res {
+        assert_ne!(err.raw_os_error(), Some(libc::EPERM), "tcp socket creation was denied");
+    }
+}
@@ -0,0 +1,101 @@
+#![allow(unsafe_code)]
+// allow unsafe to receive and respond to notifications with ioctl directly
+
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::sync::mpsc;
+
+use extrasafe::*;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+/// Receive a single notification from `listener` and return its syscall number and id.
+fn receive(listener: &OwnedFd) -> (i32, u64) {
+    // SAFETY: seccomp_notif is plain data, and must be zeroed before receiving
+    let mut notif: libc::seccomp_notif = unsafe { std::mem::zeroed() };
+    // SAFETY: notif is a valid seccomp_notif struct
+    let rc = unsafe { libc::ioctl(listener.as_raw_fd(), libc::SECCOMP_IOCTL_NOTIF_RECV, &mut notif) };
+    assert_eq!(rc, 0, "{}", std::io::Error::last_os_error());
+
+    (notif.data.nr, notif.id)
+}
+
+/// Respond to the notification `id` with `error`, or let the syscall run if `error` is 0.
+fn respond(listener: &OwnedFd, id: u64, error: i32) {
+    let mut resp = libc::seccomp_notif_resp {
+        id,
+        val: 0,
+        error,
+        flags: if error == 0 { libc::SECCOMP_USER_NOTIF_FLAG_CONTINUE as u32 } else { 0 },
+    };
+    // SAFETY: resp is a valid seccomp_notif_resp struct
+    let rc = unsafe { libc::ioctl(listener.as_raw_fd(), libc::SECCOMP_IOCTL_NOTIF_SEND, &mut resp) };
+    assert_eq!(rc, 0, "{}", std::io::Error::last_os_error());
+}
+
+#[test]
+/// A supervisor on another thread allows one notified syscall and denies another.
+fn user_notif_supervisor() {
+    let (sender, receiver) = mpsc::channel();
+
+    let sandboxed = std::thread::spawn(move || {
+        let listener = SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()
+                .allow_stderr()).unwrap()
+            .notify_on(&[Sysno::getppid, Sysno::getuid])
+            .apply_with_listener().unwrap();
+        sender.send(listener).unwrap();
+
+        // SAFETY: getppid and getuid take no arguments
+        let ppid = unsafe { libc::syscall(libc::SYS_getppid) };
+        // SAFETY: same as above
+        let uid = unsafe { libc::syscall(libc::SYS_getuid) };
+        let errno = std::io::Error::last_os_error().raw_os_error();
+        (ppid, uid, errno)
+    });
+
+    let listener = receiver.recv().unwrap();
+    for _ in 0..2 {
+        let (nr, id) = receive(&listener);
+        if i64::from(nr) == libc::SYS_getppid {
+            respond(&listener, id, 0);
+        } else {
+            assert_eq!(i64::from(nr), libc::SYS_getuid);
+            respond(&listener, id, -libc::EACCES);
+        }
+    }
+
+    let (ppid, uid, errno) = sandboxed.join().unwrap();
+    assert_eq!(ppid, i64::from(std::os::unix::process::parent_id()));
+    assert_eq!(uid, -1);
+    assert_eq!(errno, Some(libc::EACCES));
+}
+
+#[test]
+/// Applying without a listener is an error when syscalls are marked for notification.
+fn user_notif_requires_listener() {
+    let ctx = SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()).unwrap()
+        .notify_on(&[Sysno::getuid]);
+
+    let res = ctx.clone().apply_to_current_thread();
+    assert!(matches!(res, Err(ExtraSafeError::NotifyListenerRequired)));
+    let res = ctx.apply_to_all_threads();
+    assert!(matches!(res, Err(ExtraSafeError::NotifyListenerRequired)));
+}
+
+#[test]
+/// Applying with a listener is an error when no syscalls are marked for notification, and
+/// nothing is applied.
+fn user_notif_requires_syscalls() {
+    std::thread::spawn(|| {
+        let res = SafetyContext::new()
+            .enable(SystemIO::nothing()
+                .allow_stdout()).unwrap()
+            .apply_with_listener();
+        assert!(matches!(res, Err(ExtraSafeError::NoNotifySyscalls)), "{:?}", res);
+
+        assert!(!is_seccomp_active());
+    }).join().unwrap();
+}
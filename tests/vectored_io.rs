@@ -0,0 +1,27 @@
+use std::collections::HashSet;
+
+use extrasafe::RuleSet;
+use extrasafe::builtins::SystemIO;
+use extrasafe::syscalls::Sysno;
+
+const VECTORED_READ: &[Sysno] = &[Sysno::readv, Sysno::pread64, Sysno::preadv, Sysno::preadv2];
+const VECTORED_WRITE: &[Sysno] = &[Sysno::writev, Sysno::pwrite64, Sysno::pwritev, Sysno::pwritev2];
+
+#[test]
+/// The vectored and positional variants are allowed along with `read` and `write`, and only
+/// the ones for the same direction.
+fn vectored_io_membership() {
+    let read: HashSet<Sysno> = SystemIO::nothing().allow_read().simple_rules().into_iter().collect();
+    let write: HashSet<Sysno> = SystemIO::nothing().allow_write().simple_rules().into_iter().collect();
+
+    assert!(read.contains(&Sysno::read));
+    assert!(write.contains(&Sysno::write));
+    for syscall in VECTORED_READ {
+        assert!(read.contains(syscall), "allow_read is missing {}", syscall);
+        assert!(!write.contains(syscall), "allow_write allows {}", syscall);
+    }
+    for syscall in VECTORED_WRITE {
+        assert!(write.contains(syscall), "allow_write is missing {}", syscall);
+        assert!(!read.contains(syscall), "allow_read allows {}", syscall);
+    }
+}
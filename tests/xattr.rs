@@ -0,0 +1,32 @@
+#![allow(unsafe_code)]
+// allow unsafe to call the xattr functions directly
+
+use std::ffi::CString;
+use std::os::fd::AsRawFd;
+
+use extrasafe::SafetyContext;
+use extrasafe::builtins::{SystemIO, Xattr};
+
+#[test]
+fn xattr_read_allowed() {
+    let file = tempfile::tempfile().unwrap();
+    let fd = file.as_raw_fd();
+
+    SafetyContext::new()
+        .enable(SystemIO::nothing()
+            .allow_stdout()
+            .allow_stderr()).unwrap()
+        .enable(Xattr::nothing()
+            .allow_read()).unwrap()
+        .apply_to_current_thread().unwrap();
+
+    // SAFETY: a null list with size 0 only returns the size of the list
+    let rc = unsafe { libc::flistxattr(fd, std::ptr::null_mut(), 0) };
+    assert!(rc >= 0, "flistxattr failed: {:?}", std::io::Error::last_os_error());
+
+    let name = CString::new("user.extrasafe").unwrap();
+    // SAFETY: name is a valid nul-terminated string and the value is 1 byte long
+    let rc = unsafe { libc::fsetxattr(fd, name.as_ptr(), b"1".as_ptr().cast(), 1, 0) };
+    assert_eq!(rc, -1, "fsetxattr succeeded incorrectly");
+    assert_eq!(std::io::Error::last_os_error().raw_os_error(), Some(libc::EPERM));
+}